@@ -1,29 +1,783 @@
-use crate::table::handlers::service::DataTable;
-// use crate::table::value::{ColumnType, Value};
+use crate::command::output::{self, CliError, OutputFormat};
+use crate::command::{daemon, merge};
+use crate::menu::{interactive::read_confirmed_passphrase, tui};
+use std::env;
+use table::TableError;
+use table::config::{self, Config};
+use table::handlers::{
+    audit::{self, AuditEntry},
+    crypto::{self, NEW_PASSPHRASE_ENV, PASSPHRASE_ENV},
+    export::ExportFormat,
+    hooks,
+    json_rows::{JsonShape, value_to_json},
+    pager::ViewOptions,
+    search::{SearchOptions, SearchQuery},
+    service::DataTable,
+    snapshot,
+    sql_dump::{self, SqlDialect},
+};
+use table::models::{HookEvent, HookTiming, RowTable, SavedView, SortKey, Trigger, TriggerAction};
+use table::tr;
+use table::value::{ColumnType, Value};
+
+// Daftar perintah (dipakai juga untuk tab completion di REPL)
+pub const VERBS: &[&str] = &[
+    "add_column",
+    "add_computed_column",
+    "add_row",
+    "set_primary",
+    "set_value_where",
+    "remove_column",
+    "remove_row",
+    "show_column_types",
+    "print",
+    "tui",
+    "export_json",
+    "import_json",
+    "export_sqlite",
+    "import_sqlite",
+    "dump_sql",
+    "encrypt",
+    "decrypt",
+    "rekey",
+    "config",
+    "view",
+    "trigger",
+    "history",
+    "log",
+    "snapshot",
+    "diff",
+    "merge",
+    "search",
+];
+
+// Perintah baca yang bisa memakai view sebagai sumber (`--view NAMA`)
+const VIEW_SOURCES: &[&str] = &[
+    "print",
+    "show_column_types",
+    "export_json",
+    "export_sqlite",
+    "dump_sql",
+];
+
+pub fn run_command(
+    table: &mut DataTable,
+    mut args: Vec<String>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let Some(cmd) = args.first().cloned() else {
+        return Err(tr!("cli.no_command").into());
+    };
+
+    // Jalankan pada hasil view; perubahan pada tabel hasil tidak ikut disimpan
+    if VIEW_SOURCES.contains(&cmd.as_str())
+        && let Some(name) = take_option(&mut args, "--view")?
+    {
+        let mut source = table.view_table(&name)?;
+        return run_command(&mut source, args, output);
+    }
 
-pub fn run_command(table: &mut DataTable, args: Vec<String>) -> Result<(), String> {
-    let cmd = &args[0];
     match cmd.as_str() {
         "add_column" => {
-            /* parse args[1..] → add_column */
-            Ok(())
+            // add_column NAMA TIPE [NAMA TIPE ...]
+            let pairs = &args[1..];
+            if pairs.is_empty() || !pairs.len().is_multiple_of(2) {
+                return Err(tr!("cli.usage_add_column").into());
+            }
+            let mut columns: Vec<(&str, ColumnType)> = Vec::new();
+            for pair in pairs.chunks(2) {
+                columns.push((pair[0].as_str(), ColumnType::parse(&pair[1])?));
+            }
+            Ok(table.add_column(columns)?)
+        }
+        "add_computed_column" => {
+            // add_computed_column NAMA "EKSPRESI" (ekspresi boleh dipecah ke beberapa argumen)
+            let (Some(colname), true) = (args.get(1), args.len() > 2) else {
+                return Err(tr!("cli.usage_add_computed_column").into());
+            };
+            Ok(table.add_computed_column(colname, &args[2..].join(" "))?)
         }
         "add_row" => {
-            /* parse args[1..] → add_row */
-            Ok(())
+            // add_row NILAI [NILAI ...] (urut sesuai kolom, sisanya Empty)
+            let values = parse_values(table, &args[1..])?;
+            Ok(table.add_row(values)?)
         }
         "set_primary" => {
-            /* args[1] → set_primary */
-            Ok(())
+            // set_primary KOLOM
+            let colname = args.get(1).ok_or_else(|| tr!("cli.missing_column", cmd))?;
+            Ok(table.set_primary(colname)?)
         }
         "set_value_where" => {
-            /* args[1..] → set_value_where */
+            // set_value_where KOLOM_KONDISI NILAI_KONDISI KOLOM_TARGET NILAI_BARU
+            let [_, cond_col, cond_val, target_col, new_val] = args.as_slice() else {
+                return Err(tr!("cli.usage_set_value_where").into());
+            };
+            let cond_value = table.parse_value(cond_col, cond_val)?;
+            let new_value = table.parse_value(target_col, new_val)?;
+            Ok(table.set_value_where(cond_col, cond_value, target_col, new_value)?)
+        }
+        "remove_column" => {
+            // remove_column KOLOM
+            let colname = args.get(1).ok_or_else(|| tr!("cli.missing_column", cmd))?;
+            table.remove_column(colname)?;
+            // View yang memakai kolom ini tetap disimpan, tapi tidak bisa dipakai lagi
+            for (name, err) in table.invalid_views() {
+                eprintln!("{}", tr!("view.invalidated", name, err));
+            }
+            Ok(())
+        }
+        "remove_row" => {
+            // remove_row KOLOM NILAI
+            let [_, colname, input] = args.as_slice() else {
+                return Err(tr!("cli.usage_remove_row").into());
+            };
+            let value = table.parse_value(colname, input)?;
+            Ok(table.remove_row(colname, value)?)
+        }
+        "show_column_types" => {
+            if output.is_machine() {
+                print!("{}", output::render_column_types(table, output));
+            } else {
+                table.show_column_types();
+            }
             Ok(())
         }
         "print" => {
-            table.show_data_table();
+            // print [--format table|md|html|text] [--out FILE] [--where KOLOM=NILAI]
+            //       [--page N] [--limit N] [--col N] [--wrap] [--no-pin]
+            let format = match take_option(&mut args, "--format")? {
+                Some(f) => ExportFormat::parse(&f)?,
+                None => ExportFormat::Table,
+            };
+            let out = take_option(&mut args, "--out")?;
+            let page = take_number(&mut args, "--page")?;
+            let limit = take_number(&mut args, "--limit")?;
+            let col = take_number(&mut args, "--col")?;
+            let rows = match take_option(&mut args, "--where")? {
+                Some(cond) => filter_rows(table, &cond)?,
+                None => table.row.iter().collect(),
+            };
+
+            let mut opts = ViewOptions {
+                page: page.unwrap_or(1).saturating_sub(1),
+                col_offset: col.unwrap_or(1).saturating_sub(1),
+                wrap: take_flag(&mut args, "--wrap"),
+                pin_primary: !take_flag(&mut args, "--no-pin"),
+                ..ViewOptions::default()
+            };
+            // Tanpa --page/--limit → semua baris dalam satu halaman
+            opts.limit = match (page, limit) {
+                (None, None) => rows.len().max(1),
+                (_, limit) => limit.unwrap_or(opts.limit),
+            };
+
+            let view = table.page_view(&rows, &opts);
+            if output.is_machine() {
+                let text = output::render_rows(table, view.rows, output);
+                return Ok(write_output(&text, out.as_deref())?);
+            }
+            if format == ExportFormat::Table && out.is_none() {
+                table.show_data_table(&rows, &opts);
+                return Ok(());
+            }
+            Ok(table.export_rows(view.rows, format, out.as_deref())?)
+        }
+        "tui" => tui::run_tui(table).map_err(CliError::general),
+        "export_json" => {
+            // export_json [--lines] [--out FILE] [--where KOLOM=NILAI]
+            let lines = take_flag(&mut args, "--lines");
+            let out = take_option(&mut args, "--out")?;
+            let shape = match &out {
+                _ if lines => JsonShape::Lines,
+                Some(path) => JsonShape::from_path(path),
+                None => JsonShape::Array,
+            };
+            let rows = match take_option(&mut args, "--where")? {
+                Some(cond) => filter_rows(table, &cond)?,
+                None => table.row.iter().collect(),
+            };
+            Ok(table.export_json(&rows, shape, out.as_deref())?)
+        }
+        "import_json" => {
+            // import_json FILE [--lines]
+            let lines = take_flag(&mut args, "--lines");
+            let path = args
+                .get(1)
+                .ok_or_else(|| tr!("cli.missing_path", cmd))?
+                .clone();
+            let shape = if lines {
+                JsonShape::Lines
+            } else {
+                JsonShape::from_path(&path)
+            };
+            let data = std::fs::read_to_string(&path).map_err(TableError::io(&path))?;
+            let count = table.import_json(&data, shape)?;
+            println!("{}", tr!("cli.imported_json", count));
+            Ok(())
+        }
+        "export_sqlite" => {
+            // export_sqlite FILE [--table NAMA] [--replace]
+            let name = take_option(&mut args, "--table")?.unwrap_or_else(|| "mytabel".into());
+            let replace = take_flag(&mut args, "--replace");
+            let path = args.get(1).ok_or_else(|| tr!("cli.missing_path", cmd))?;
+            table.export_sqlite(path, &name, replace)?;
+            println!("{}", tr!("cli.exported_sqlite", path, name));
+            Ok(())
+        }
+        "import_sqlite" => {
+            // import_sqlite FILE --table NAMA [--primary KOLOM] [--replace]
+            let name = take_option(&mut args, "--table")?
+                .ok_or_else(|| tr!("cli.missing_option", cmd, "--table"))?;
+            let primary = take_option(&mut args, "--primary")?;
+            let replace = take_flag(&mut args, "--replace");
+            let path = args.get(1).ok_or_else(|| tr!("cli.missing_path", cmd))?;
+
+            // Jangan timpa data yang ada tanpa --replace
+            if !table.column.is_empty() && !replace {
+                return Err(tr!("cli.not_empty", cmd).into());
+            }
+            let imported = DataTable::import_sqlite(path, &name, primary.as_deref())?;
+            let passphrase = table.passphrase.take();
+            table.replace_keeping_hooks(imported);
+            table.passphrase = passphrase;
+            println!("{}", tr!("cli.imported_sqlite", table.row.len(), name));
+            Ok(())
+        }
+        "dump_sql" => {
+            // dump_sql [--dialect postgres|mysql|sqlite] [--table NAMA] [--batch N] [--out FILE]
+            let dialect = match take_option(&mut args, "--dialect")? {
+                Some(d) => SqlDialect::parse(&d)?,
+                None => SqlDialect::Postgres,
+            };
+            let name = take_option(&mut args, "--table")?.unwrap_or_else(|| "mytabel".into());
+            let batch = match take_option(&mut args, "--batch")? {
+                Some(n) => n.parse::<usize>().map_err(|_| tr!("opt.batch_number", n))?,
+                None => sql_dump::DEFAULT_BATCH,
+            };
+            let out = take_option(&mut args, "--out")?;
+            Ok(table.export_sql_dump(&name, dialect, batch, out.as_deref())?)
+        }
+        "encrypt" => {
+            daemon::ensure_not_running()?;
+            let passphrase = match crypto::passphrase_from_env(PASSPHRASE_ENV) {
+                Some(p) => p,
+                None => read_confirmed_passphrase()?,
+            };
+            table.encrypt(&passphrase)?;
+            println!("{}", tr!("cli.will_encrypt"));
+            Ok(())
+        }
+        "decrypt" => {
+            daemon::ensure_not_running()?;
+            table.decrypt()?;
+            println!("{}", tr!("cli.will_decrypt"));
+            Ok(())
+        }
+        "rekey" => {
+            daemon::ensure_not_running()?;
+            let passphrase = match crypto::passphrase_from_env(NEW_PASSPHRASE_ENV) {
+                Some(p) => p,
+                None => read_confirmed_passphrase()?,
+            };
+            table.rekey(&passphrase)?;
+            println!("{}", tr!("cli.rekeyed"));
+            Ok(())
+        }
+        "config" => run_config(args, output),
+        "view" => run_view(table, args, output),
+        "trigger" => run_trigger(table, args, output),
+        "history" => run_history(table, args, output),
+        "log" => run_log(table, args, output),
+        "snapshot" => run_snapshot(table, args, output),
+        "diff" => run_diff(table, args, output),
+        "merge" => merge::run_merge(args, output),
+        "search" => run_search(table, args, output),
+        _ => Err(tr!("cli.unknown_command", cmd).into()),
+    }
+}
+
+// config list | config get KEY | config set KEY NILAI [--project]
+// Perubahan berlaku mulai perintah berikutnya
+pub fn run_config(mut args: Vec<String>, output: OutputFormat) -> Result<(), CliError> {
+    let project = take_flag(&mut args, "--project");
+    let layers = config::load_layers()?;
+
+    match &args[1..] {
+        [sub] if sub == "list" => {
+            let entries: Vec<(String, toml::Value, String)> = Config::from_layers(&layers)?
+                .entries()
+                .into_iter()
+                .map(|(key, value)| {
+                    let source = config::origin(&layers, &key)
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_else(|| "default".into());
+                    (key, value, source)
+                })
+                .collect();
+            if output.is_machine() {
+                print!("{}", output::render_config(&entries, output));
+            } else {
+                for (key, value, source) in &entries {
+                    println!("{} = {}  # {}", key, value, source);
+                }
+            }
+            Ok(())
+        }
+        [sub, key] if sub == "get" => {
+            let entries = Config::from_layers(&layers)?.entries();
+            let value = entries
+                .get(key)
+                .ok_or_else(|| TableError::InvalidOption(tr!("config.unknown_key", key)))?;
+            println!("{}", output::plain_config_value(value));
+            Ok(())
+        }
+        [sub, key, value] if sub == "set" => {
+            let path = if project {
+                env::current_dir()
+                    .map_err(|e| CliError::general(e.to_string()))?
+                    .join(config::PROJECT_FILE)
+            } else {
+                config::global_path().ok_or_else(|| CliError::general(tr!("config.no_global")))?
+            };
+            config::set_value(&path, key, value)?;
+            println!("{}", tr!("config.saved", key, path.display()));
             Ok(())
         }
-        _ => Err(format!("Perintah '{}' tidak dikenali", cmd)),
+        _ => Err(tr!("config.usage").into()),
+    }
+}
+
+// view list | view save NAMA [--columns A,B] [--filter EKSPRESI] [--sort A,-B] [--limit N]
+// view show NAMA [opsi print] | view remove NAMA
+fn run_view(
+    table: &mut DataTable,
+    mut args: Vec<String>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let sub = args.get(1).cloned().unwrap_or_default();
+    let name = args.get(2).cloned();
+    match (sub.as_str(), name) {
+        ("list", None) => {
+            if output.is_machine() {
+                print!("{}", output::render_views(table, output));
+            } else if table.views.is_empty() {
+                println!("{}", tr!("view.none"));
+            } else {
+                for (name, view) in &table.views {
+                    let status = match table.check_view(view) {
+                        Ok(()) => String::new(),
+                        Err(e) => format!("  # {}", tr!("view.invalid", e)),
+                    };
+                    println!("{} {}{}", name, output::view_spec(view), status);
+                }
+            }
+            Ok(())
+        }
+        ("save", Some(name)) => {
+            let columns = take_option(&mut args, "--columns")?
+                .map(|c| split_list(&c))
+                .unwrap_or_default();
+            let filter = take_option(&mut args, "--filter")?;
+            let sort = match take_option(&mut args, "--sort")? {
+                Some(keys) => split_list(&keys)
+                    .iter()
+                    .map(|k| SortKey::parse(k))
+                    .collect::<Result<Vec<_>, _>>()?,
+                None => Vec::new(),
+            };
+            let limit = take_number(&mut args, "--limit")?;
+            if args.len() > 3 {
+                return Err(tr!("view.usage").into());
+            }
+            let view = SavedView {
+                columns,
+                filter,
+                sort,
+                limit,
+            };
+            table.save_view(&name, view)?;
+            println!("{}", tr!("view.saved", name));
+            Ok(())
+        }
+        ("show", Some(name)) => {
+            // Sama dengan `print --view NAMA`; opsi print lain tetap berlaku
+            let mut print_args = vec!["print".to_string(), "--view".into(), name];
+            print_args.extend(args.drain(3..));
+            run_command(table, print_args, output)
+        }
+        ("remove", Some(name)) if args.len() == 3 => {
+            table.remove_view(&name)?;
+            println!("{}", tr!("view.removed", name));
+            Ok(())
+        }
+        _ => Err(tr!("view.usage").into()),
+    }
+}
+
+// trigger list | trigger remove NAMA
+// trigger add NAMA before|after add_row|set_value_where|remove_row AKSI ...
+//   AKSI: touch KOLOM | set KOLOM EKSPRESI | reject KONDISI [PESAN] | log FILE
+fn run_trigger(
+    table: &mut DataTable,
+    args: Vec<String>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match &args[1..] {
+        ["list"] => {
+            if output.is_machine() {
+                print!("{}", output::render_triggers(&table.triggers, output));
+            } else if table.triggers.is_empty() {
+                println!("{}", tr!("trigger.none"));
+            } else {
+                for t in &table.triggers {
+                    let action = output::action_args(&t.action).join(" ");
+                    let name = hooks::action_name(&t.action);
+                    println!(
+                        "{} {} {} {} {}",
+                        t.name,
+                        t.timing.name(),
+                        t.event.name(),
+                        name,
+                        action
+                    );
+                }
+            }
+            Ok(())
+        }
+        ["remove", name] => {
+            table.remove_trigger(name)?;
+            println!("{}", tr!("trigger.removed", name));
+            Ok(())
+        }
+        ["add", name, timing, event, action @ ..] => {
+            let action = match action {
+                ["touch", column] => TriggerAction::Touch {
+                    column: column.to_string(),
+                },
+                ["set", column, expr] => TriggerAction::Set {
+                    column: column.to_string(),
+                    expr: expr.to_string(),
+                },
+                ["reject", condition] | ["reject", condition, _] => TriggerAction::Reject {
+                    condition: condition.to_string(),
+                    message: action.get(2).map(|m| m.to_string()),
+                },
+                // Path relatif → relatif ke direktori saat trigger dibuat
+                ["log", path] => TriggerAction::Log {
+                    path: env::current_dir()
+                        .map(|dir| dir.join(path).display().to_string())
+                        .unwrap_or_else(|_| path.to_string()),
+                },
+                _ => return Err(tr!("trigger.usage").into()),
+            };
+            table.add_trigger(Trigger {
+                name: name.to_string(),
+                timing: HookTiming::parse(timing)?,
+                event: HookEvent::parse(event)?,
+                action,
+            })?;
+            println!("{}", tr!("trigger.added", name));
+            Ok(())
+        }
+        _ => Err(tr!("trigger.usage").into()),
+    }
+}
+
+// history PRIMARY — semua perubahan satu baris, urut waktu
+fn run_history(table: &DataTable, args: Vec<String>, output: OutputFormat) -> Result<(), CliError> {
+    let [_, input] = args.as_slice() else {
+        return Err(tr!("audit.usage_history").into());
+    };
+    let primary = table.primary_index.ok_or(TableError::NoPrimary)?;
+    let key = value_to_json(&table.parse_value(&table.column[primary].colname, input)?);
+    let entries = read_audit_entries(table)?;
+    let history = audit::row_history(&entries, &key);
+    print_audit(&history, output, false);
+    Ok(())
+}
+
+// log [--since WAKTU] [--limit N] — perubahan terbaru (N catatan terakhir)
+fn run_log(table: &DataTable, mut args: Vec<String>, output: OutputFormat) -> Result<(), CliError> {
+    let since = take_option(&mut args, "--since")?
+        .map(|s| audit::parse_since(&s))
+        .transpose()?;
+    let limit = take_number(&mut args, "--limit")?;
+    if args.len() > 1 {
+        return Err(tr!("audit.usage_log").into());
+    }
+    let entries = read_audit_entries(table)?;
+    let mut recent = audit::entries_since(&entries, since);
+    if let Some(limit) = limit {
+        recent.drain(..recent.len().saturating_sub(limit));
+    }
+    print_audit(&recent, output, true);
+    Ok(())
+}
+
+fn read_audit_entries(table: &DataTable) -> Result<Vec<AuditEntry>, TableError> {
+    let path = audit::audit_path(&config::current().storage_path());
+    audit::read_audit(&path, table.passphrase.as_deref())
+}
+
+fn print_audit(entries: &[&AuditEntry], output: OutputFormat, with_key: bool) {
+    if output.is_machine() {
+        print!("{}", output::render_audit(entries, output));
+    } else if entries.is_empty() {
+        println!("{}", tr!("audit.none"));
+    } else {
+        for entry in entries {
+            println!("{}", output::audit_line(entry, with_key));
+        }
+    }
+}
+
+// snapshot list | snapshot save NAMA | snapshot remove NAMA
+fn run_snapshot(
+    table: &DataTable,
+    args: Vec<String>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let dir = snapshot::snapshot_dir(&config::current().storage_path());
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match &args[1..] {
+        ["list"] => {
+            let snapshots = snapshot::list_snapshots(&dir)?;
+            if output.is_machine() {
+                print!("{}", output::render_snapshots(&snapshots, output));
+            } else if snapshots.is_empty() {
+                println!("{}", tr!("snapshot.none"));
+            } else {
+                for s in &snapshots {
+                    let created = s
+                        .created
+                        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                        .unwrap_or_default();
+                    println!("{}  {}", s.name, created);
+                }
+            }
+            Ok(())
+        }
+        ["save", name] => {
+            let saved = table.save_snapshot(&dir, name)?;
+            println!("{}", tr!("snapshot.saved", name, saved.path));
+            Ok(())
+        }
+        ["remove", name] => {
+            snapshot::remove_snapshot(&dir, name)?;
+            println!("{}", tr!("snapshot.removed", name));
+            Ok(())
+        }
+        _ => Err(tr!("snapshot.usage").into()),
+    }
+}
+
+// diff LAMA [BARU] — LAMA/BARU: nama snapshot atau path file tabel;
+// tanpa BARU → dibandingkan dengan tabel saat ini
+fn run_diff(table: &DataTable, args: Vec<String>, output: OutputFormat) -> Result<(), CliError> {
+    let (old, new) = match args.as_slice() {
+        [_, old] => (diff_source(table, old)?, table.clone()),
+        [_, old, new] => (diff_source(table, old)?, diff_source(table, new)?),
+        _ => return Err(tr!("diff.usage").into()),
+    };
+    let diff = new.diff(&old)?;
+    if output.is_machine() {
+        print!("{}", output::render_diff(&diff, output));
+    } else {
+        // Tanpa primary baris dikenali lewat nomor urut
+        let key_name = new
+            ._get_column_is_primary_active()
+            .or(old._get_column_is_primary_active())
+            .unwrap_or("#");
+        output::print_diff(&diff, key_name);
+    }
+    Ok(())
+}
+
+// Snapshot dengan nama itu jika ada, selain itu file tabel (passphrase sama dengan tabel)
+fn diff_source(table: &DataTable, source: &str) -> Result<DataTable, TableError> {
+    let dir = snapshot::snapshot_dir(&config::current().storage_path());
+    match DataTable::load_snapshot(&dir, source, table.passphrase.as_deref()) {
+        Err(TableError::SnapshotNotFound(_) | TableError::InvalidValue(_))
+            if std::path::Path::new(source).is_file() =>
+        {
+            DataTable::load_with_passphrase(source, table.passphrase.as_deref())
+        }
+        result => result,
+    }
+}
+
+// search KATA... [--columns A,B] [--exact] [--limit N]
+// Semua kata harus cocok; hasil urut skor dengan bagian yang cocok ditandai
+fn run_search(
+    table: &DataTable,
+    mut args: Vec<String>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let opts = SearchOptions {
+        fuzzy: !take_flag(&mut args, "--exact"),
+        columns: take_option(&mut args, "--columns")?
+            .map(|c| split_list(&c))
+            .unwrap_or_default(),
+        limit: take_number(&mut args, "--limit")?,
+    };
+    let query = SearchQuery::parse(&args[1..].join(" "), opts.fuzzy);
+    if query.is_empty() {
+        return Err(tr!("search.usage").into());
+    }
+
+    // Sekali cari: membangun indeks lebih mahal daripada memindai semua baris
+    let hits = table.search(&query, &opts, None)?;
+    if output.is_machine() {
+        print!("{}", output::render_search(table, &hits, output));
+    } else if hits.is_empty() {
+        println!("{}", tr!("search.none"));
+    } else {
+        let rows: Vec<&RowTable> = hits.iter().map(|h| h.row).collect();
+        let opts = ViewOptions {
+            limit: rows.len(),
+            highlight: Some(query),
+            ..ViewOptions::default()
+        };
+        table.show_data_table(&rows, &opts);
+    }
+    Ok(())
+}
+
+// `a, b,c` → ["a", "b", "c"]
+fn split_list(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+// Ambil opsi `--nama nilai` atau `--nama=nilai` lalu buang dari args
+pub fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    let prefix = format!("{}=", name);
+    let Some(pos) = args
+        .iter()
+        .position(|a| a == name || a.starts_with(&prefix))
+    else {
+        return Ok(None);
+    };
+
+    let arg = args.remove(pos);
+    if let Some(value) = arg.strip_prefix(&prefix) {
+        return Ok(Some(value.to_string()));
+    }
+    if pos < args.len() {
+        return Ok(Some(args.remove(pos)));
+    }
+    Err(tr!("opt.needs_value", name))
+}
+
+// Tulis ke file, atau stdout jika path None
+fn write_output(text: &str, path: Option<&str>) -> Result<(), TableError> {
+    match path {
+        Some(path) => std::fs::write(path, text).map_err(TableError::io(path)),
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+// Ambil opsi angka positif (mis. `--page 2`)
+pub fn take_number(args: &mut Vec<String>, name: &str) -> Result<Option<usize>, String> {
+    match take_option(args, name)? {
+        Some(n) => match n.parse::<usize>() {
+            Ok(v) if v > 0 => Ok(Some(v)),
+            _ => Err(tr!("opt.positive", name, n)),
+        },
+        None => Ok(None),
+    }
+}
+
+// Ambil flag tanpa nilai (mis. `--lines`)
+pub fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    match args.iter().position(|a| a == name) {
+        Some(pos) => {
+            args.remove(pos);
+            true
+        }
+        None => false,
+    }
+}
+
+// Parse kondisi `KOLOM=NILAI` lalu ambil baris yang cocok
+fn filter_rows<'a>(table: &'a DataTable, cond: &str) -> Result<Vec<&'a RowTable>, TableError> {
+    let (colname, input) = cond
+        .split_once('=')
+        .ok_or_else(|| TableError::InvalidOption(tr!("opt.condition", cond)))?;
+    let value = table.parse_value(colname, input)?;
+    table.rows_where(colname, &value)
+}
+
+// Parse nilai baris sesuai urutan kolom
+fn parse_values(table: &DataTable, inputs: &[String]) -> Result<Vec<Value>, TableError> {
+    if inputs.len() > table.column.len() {
+        return Err(TableError::TooManyValues {
+            given: inputs.len(),
+            columns: table.column.len(),
+        });
+    }
+    inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| {
+            table._parse_input_to_value_type(i, input).map_err(|e| {
+                TableError::InvalidValue(tr!("val.in_column", table.column[i].colname, e))
+            })
+        })
+        .collect()
+}
+
+// Pecah satu baris perintah menjadi argumen: spasi sebagai pemisah,
+// "..." / '...' untuk nilai berspasi, \ untuk escape, \<newline> untuk sambung baris
+pub fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => match chars.next() {
+                Some('\n') => {}
+                Some(next) => {
+                    current.push(next);
+                    in_word = true;
+                }
+                None => current.push('\\'),
+            },
+            (Some(_), c) => current.push(c),
+            (None, '"') | (None, '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err(tr!("cli.unclosed_quote"));
+    }
+    if in_word {
+        args.push(current);
     }
+    Ok(args)
 }
@@ -0,0 +1,448 @@
+use crate::command::cli::{self, VERBS};
+use crate::command::output::{CliError, OutputFormat};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, Write};
+use std::os::fd::AsRawFd;
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use table::TableError;
+use table::config;
+use table::handlers::{pager, service::DataTable};
+use table::i18n::{self, Lang};
+use table::tr;
+
+// Perintah yang tetap dijalankan langsung: butuh terminal atau passphrase dari pengguna
+const LOCAL_ONLY: &[&str] = &["tui", "encrypt", "decrypt", "rekey"];
+
+// Batas tunggu baca/tulis per koneksi klien
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Satu baris JSON dari klien
+#[derive(Serialize, Deserialize)]
+struct DaemonRequest {
+    args: Vec<String>,
+    output: String,
+    lang: String,
+    cwd: Option<PathBuf>,
+    width: Option<usize>,
+}
+
+// Satu baris JSON balasan daemon; stdout/stderr sudah dalam format output klien
+#[derive(Serialize, Deserialize)]
+struct DaemonReply {
+    status: i32,
+    stdout: String,
+    stderr: String,
+}
+
+impl DaemonReply {
+    fn error(err: CliError, output: OutputFormat) -> Self {
+        Self {
+            status: err.status,
+            stdout: String::new(),
+            stderr: format!("{}\n", err.render(output)),
+        }
+    }
+}
+
+// Socket di samping file tabel: satu daemon per file
+pub fn socket_path() -> PathBuf {
+    PathBuf::from(format!("{}.sock", config::current().storage_path()))
+}
+
+// Kirim perintah CLI ke daemon; None → daemon tidak jalan / perintah harus lokal.
+// Return: exit status dari daemon
+pub fn forward(args: &[String]) -> Option<i32> {
+    let cmd = args.first()?.as_str();
+    if !VERBS.contains(&cmd) || LOCAL_ONLY.contains(&cmd) {
+        return None;
+    }
+
+    // --output salah → biarkan mode lokal yang melaporkan
+    let mut args = args.to_vec();
+    let output = cli::take_option(&mut args, "--output")
+        .ok()?
+        .map(|o| OutputFormat::parse(&o))
+        .transpose()
+        .ok()?
+        .unwrap_or(OutputFormat::Table);
+    let stream = UnixStream::connect(socket_path()).ok()?;
+
+    let request = DaemonRequest {
+        args,
+        output: output.name().into(),
+        lang: i18n::lang().code().into(),
+        cwd: env::current_dir().ok(),
+        width: pager::terminal_width(),
+    };
+    // Sudah terhubung: jangan jatuh ke mode lokal, perintah mungkin sudah jalan
+    let reply = exchange(stream, &request).unwrap_or_else(|e| {
+        DaemonReply::error(CliError::general(tr!("daemon.bad_request", e)), output)
+    });
+    print!("{}", reply.stdout);
+    eprint!("{}", reply.stderr);
+    Some(reply.status)
+}
+
+// Encrypt/decrypt/rekey lokal saat daemon aktif menulis file dengan kunci yang tidak
+// dimiliki daemon → setiap muat ulang di daemon gagal. Tolak sampai daemon dihentikan
+pub fn ensure_not_running() -> Result<(), TableError> {
+    let path = socket_path();
+    match UnixStream::connect(&path) {
+        Ok(_) => Err(TableError::Crypto(tr!(
+            "daemon.crypto_running",
+            path.display()
+        ))),
+        Err(_) => Ok(()),
+    }
+}
+
+// Hentikan daemon yang berjalan; return pesan dari daemon
+pub fn stop() -> Result<String, String> {
+    let path = socket_path();
+    let stream =
+        UnixStream::connect(&path).map_err(|_| tr!("daemon.not_running", path.display()))?;
+    let request = DaemonRequest {
+        args: vec!["daemon".into(), "stop".into()],
+        output: OutputFormat::Table.name().into(),
+        lang: i18n::lang().code().into(),
+        cwd: None,
+        width: None,
+    };
+    let reply = exchange(stream, &request).map_err(|e| tr!("daemon.bad_request", e))?;
+    Ok(reply.stdout)
+}
+
+fn exchange(mut stream: UnixStream, request: &DaemonRequest) -> io::Result<DaemonReply> {
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    Ok(serde_json::from_str(&reply)?)
+}
+
+// Jalankan daemon: tabel tetap di memori, request diproses satu per satu.
+// Setiap perintah disimpan lewat `save` seperti mode CLI biasa
+pub fn run_daemon<F>(table: &mut DataTable, save: F) -> Result<(), String>
+where
+    F: Fn(&DataTable) -> Result<(), TableError>,
+{
+    let path = socket_path();
+    let shown = path.display().to_string();
+    if UnixStream::connect(&path).is_ok() {
+        return Err(tr!("daemon.running", shown));
+    }
+    let _ = fs::remove_file(&path); // sisa daemon yang berhenti paksa
+    let listener = UnixListener::bind(&path).map_err(|e| tr!("daemon.bind_failed", shown, e))?;
+    // Hanya pemilik file yang boleh terhubung
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600))
+        .map_err(|e| tr!("daemon.bind_failed", shown, e))?;
+    println!("{}", tr!("daemon.listening", shown));
+
+    let mut state = DaemonState {
+        storage: config::current().storage_path(),
+        home: env::current_dir().map_err(|e| e.to_string())?,
+        loaded_at: None,
+    };
+    state.loaded_at = state.modified();
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        if serve_client(stream, table, &mut state, &save) {
+            break;
+        }
+    }
+
+    let _ = fs::remove_file(&path);
+    Ok(())
+}
+
+struct DaemonState {
+    storage: String,
+    // Direktori kerja daemon, dipulihkan setelah tiap request
+    home: PathBuf,
+    // mtime file saat terakhir dimuat/disimpan
+    loaded_at: Option<SystemTime>,
+}
+
+impl DaemonState {
+    fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.storage).and_then(|m| m.modified()).ok()
+    }
+}
+
+// Layani satu koneksi (boleh beberapa baris request); return true jika diminta berhenti
+fn serve_client<F>(
+    stream: UnixStream,
+    table: &mut DataTable,
+    state: &mut DaemonState,
+    save: &F,
+) -> bool
+where
+    F: Fn(&DataTable) -> Result<(), TableError>,
+{
+    // Klien yang diam tidak boleh menahan klien lain (request diproses berurutan)
+    let timeout = Some(CLIENT_TIMEOUT);
+    if stream.set_read_timeout(timeout).is_err() || stream.set_write_timeout(timeout).is_err() {
+        return false;
+    }
+    let Ok(mut writer) = stream.try_clone() else {
+        return false;
+    };
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else { break };
+        let (reply, stop) = match serde_json::from_str::<DaemonRequest>(&line) {
+            Ok(request) if request.args == ["daemon", "stop"] => (
+                DaemonReply {
+                    status: 0,
+                    stdout: format!("{}\n", tr!("daemon.stopped")),
+                    stderr: String::new(),
+                },
+                true,
+            ),
+            Ok(request) => (execute(table, request, state, save), false),
+            Err(e) => (
+                DaemonReply::error(
+                    CliError::general(tr!("daemon.bad_request", e)),
+                    OutputFormat::Table,
+                ),
+                false,
+            ),
+        };
+
+        let mut text = serde_json::to_string(&reply).unwrap_or_default();
+        text.push('\n');
+        if writer.write_all(text.as_bytes()).is_err() || stop {
+            return stop;
+        }
+    }
+    false
+}
+
+fn execute<F>(
+    table: &mut DataTable,
+    request: DaemonRequest,
+    state: &mut DaemonState,
+    save: &F,
+) -> DaemonReply
+where
+    F: Fn(&DataTable) -> Result<(), TableError>,
+{
+    let output = OutputFormat::parse(&request.output).unwrap_or(OutputFormat::Table);
+    if let Some(lang) = Lang::parse(&request.lang) {
+        i18n::set_lang(lang);
+    }
+    if request.args.is_empty() {
+        return DaemonReply::error(CliError::from(tr!("cli.no_command")), output);
+    }
+
+    // File diubah proses lain (mis. `tui` langsung) → muat ulang dulu
+    if state.modified() != state.loaded_at {
+        match DataTable::load_with_passphrase(&state.storage, table.passphrase.as_deref()) {
+            Ok(fresh) => table.replace_keeping_hooks(fresh),
+            Err(e) => {
+                let mut err = CliError::from(e);
+                err.message = tr!("main.load_failed", err.message);
+                return DaemonReply::error(err, output);
+            }
+        }
+    }
+
+    // Path relatif & lebar layar mengikuti klien selama perintah berjalan
+    pager::override_terminal_width(request.width);
+    if let Some(cwd) = &request.cwd {
+        let _ = env::set_current_dir(cwd);
+    }
+    let captured = capture_stdout(|| cli::run_command(table, request.args, output));
+    let _ = env::set_current_dir(&state.home);
+
+    let (result, stdout) = match captured {
+        Ok(captured) => captured,
+        Err(e) => {
+            let err = CliError::general(tr!("daemon.capture_failed", e));
+            return DaemonReply::error(err, output);
+        }
+    };
+
+    // Urutan error sama dengan mode CLI: gagal simpan dilaporkan lebih dulu
+    let saved = save(table);
+    state.loaded_at = state.modified();
+    let error = match (saved, result) {
+        (Err(e), _) => {
+            let mut err = CliError::from(e);
+            err.message = tr!("main.save_failed", err.message);
+            Some(err)
+        }
+        (Ok(()), Err(err)) => Some(err),
+        (Ok(()), Ok(())) => None,
+    };
+    match error {
+        Some(err) => DaemonReply {
+            stdout,
+            ..DaemonReply::error(err, output)
+        },
+        None => DaemonReply {
+            status: 0,
+            stdout,
+            stderr: String::new(),
+        },
+    }
+}
+
+// Tangkap semua yang ditulis ke stdout selama `f` berjalan (println! di perintah CLI).
+// Aman karena daemon hanya memakai satu thread
+fn capture_stdout<T>(f: impl FnOnce() -> T) -> io::Result<(T, String)> {
+    let mut file = capture_file()?;
+
+    io::stdout().flush()?;
+    // SAFETY: dup/dup2/close hanya menyalin & menutup descriptor milik proses ini
+    let saved = unsafe { libc::dup(libc::STDOUT_FILENO) };
+    if saved < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if unsafe { libc::dup2(file.as_raw_fd(), libc::STDOUT_FILENO) } < 0 {
+        let err = io::Error::last_os_error();
+        unsafe { libc::close(saved) };
+        return Err(err);
+    }
+
+    let value = f();
+
+    let _ = io::stdout().flush();
+    unsafe {
+        libc::dup2(saved, libc::STDOUT_FILENO);
+        libc::close(saved);
+    }
+
+    file.rewind()?;
+    let mut out = String::new();
+    file.read_to_string(&mut out)?;
+    Ok((value, out))
+}
+
+// File sementara baru (bukan file/symlink yang sudah ada), hanya bisa dibaca pemilik;
+// langsung dihapus dari folder karena cukup diakses lewat file descriptor
+fn capture_file() -> io::Result<fs::File> {
+    let mut attempt = 0;
+    loop {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or_default();
+        let path = env::temp_dir().join(format!(
+            "mytabel-daemon-{}-{}-{}.out",
+            std::process::id(),
+            nanos,
+            attempt
+        ));
+        let opened = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .custom_flags(libc::O_NOFOLLOW)
+            .open(&path);
+        match opened {
+            Ok(file) => {
+                let _ = fs::remove_file(&path);
+                return Ok(file);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempt < 16 => attempt += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::MetadataExt;
+    use std::thread;
+    use std::time::Instant;
+
+    fn state() -> DaemonState {
+        DaemonState {
+            storage: env::temp_dir()
+                .join("mytabel-test-daemon-tidak-ada.json")
+                .display()
+                .to_string(),
+            home: env::temp_dir(),
+            loaded_at: None,
+        }
+    }
+
+    fn request(args: &[&str]) -> String {
+        let request = DaemonRequest {
+            args: args.iter().map(|a| a.to_string()).collect(),
+            output: "json".into(),
+            lang: String::new(),
+            cwd: None,
+            width: None,
+        };
+        serde_json::to_string(&request).unwrap()
+    }
+
+    fn serve(client_lines: Vec<String>) -> (bool, Vec<DaemonReply>) {
+        let (server, client) = UnixStream::pair().unwrap();
+        let handle = thread::spawn(move || {
+            let mut writer = client.try_clone().unwrap();
+            for line in client_lines {
+                writeln!(writer, "{}", line).unwrap();
+            }
+            writer.shutdown(std::net::Shutdown::Write).unwrap();
+            BufReader::new(client)
+                .lines()
+                .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+                .collect()
+        });
+        let save = |_: &DataTable| Ok(());
+        let stop = serve_client(server, &mut DataTable::default(), &mut state(), &save);
+        (stop, handle.join().unwrap())
+    }
+
+    #[test]
+    fn file_tangkapan_privat_dan_tidak_tertinggal() {
+        let file = capture_file().unwrap();
+        let meta = file.metadata().unwrap();
+        assert_eq!(meta.mode() & 0o777, 0o600);
+        assert_eq!(meta.nlink(), 0);
+    }
+
+    #[test]
+    fn satu_koneksi_boleh_beberapa_request() {
+        let (stop, replies) = serve(vec!["bukan json".into(), request(&[])]);
+        assert!(!stop);
+        assert_eq!(replies.len(), 2);
+        assert!(replies.iter().all(|r| r.status != 0 && r.stdout.is_empty()));
+        let error: serde_json::Value = serde_json::from_str(&replies[1].stderr).unwrap();
+        assert_eq!(error["error"]["code"], "USAGE");
+    }
+
+    #[test]
+    fn stop_menghentikan_daemon_dan_sisa_request_diabaikan() {
+        let (stop, replies) = serve(vec![request(&["daemon", "stop"]), request(&[])]);
+        assert!(stop);
+        assert_eq!(replies.len(), 1);
+        assert_eq!(replies[0].status, 0);
+    }
+
+    #[test]
+    fn klien_diam_dilepas_setelah_timeout() {
+        let (server, _client) = UnixStream::pair().unwrap();
+        let start = Instant::now();
+        let save = |_: &DataTable| Ok(());
+        assert!(!serve_client(
+            server,
+            &mut DataTable::default(),
+            &mut state(),
+            &save
+        ));
+        assert!(start.elapsed() >= CLIENT_TIMEOUT);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::command::cli::{take_flag, take_option};
+use crate::command::output::{self, CliError, OutputFormat};
+use crate::menu::interactive::read_input;
+use table::TableError;
+use table::config;
+use table::handlers::{
+    merge::{MergeConflict, Resolution},
+    service::DataTable,
+};
+use table::tr;
+
+// merge BASE OURS THEIRS [--prefer ours|theirs] [--interactive] [--into FILE]
+// Hasil ditulis ke OURS (atau --into); masih ada konflik → tidak ada yang ditulis
+pub fn run_merge(mut args: Vec<String>, output: OutputFormat) -> Result<(), CliError> {
+    let prefer = take_option(&mut args, "--prefer")?
+        .map(|p| Resolution::parse(&p))
+        .transpose()?;
+    let interactive = take_flag(&mut args, "--interactive");
+    let into = take_option(&mut args, "--into")?;
+    let [_, base, ours, theirs] = args.as_slice() else {
+        return Err(tr!("merge.usage").into());
+    };
+
+    let base_table = DataTable::load(base)?;
+    let ours_table = DataTable::load(ours)?;
+    let theirs_table = DataTable::load(theirs)?;
+    let outcome = DataTable::merge3(&base_table, &ours_table, &theirs_table, |conflict| {
+        if interactive {
+            ask(conflict).or(prefer)
+        } else {
+            prefer
+        }
+    })?;
+
+    let target = into.as_deref().unwrap_or(ours);
+    let written = outcome.unresolved.is_empty();
+    if written {
+        outcome
+            .table
+            .save_with_backups(target, config::current().backup_count)?;
+    }
+
+    if output.is_machine() {
+        print!(
+            "{}",
+            output::render_merge(&outcome, written.then_some(target), output)
+        );
+    } else {
+        for (conflict, choice) in &outcome.resolved {
+            println!(
+                "{}",
+                tr!(
+                    "merge.resolved",
+                    output::conflict_line(conflict),
+                    choice.name()
+                )
+            );
+        }
+        for conflict in &outcome.unresolved {
+            eprintln!("{}", tr!("merge.conflict", output::conflict_line(conflict)));
+        }
+        if written {
+            println!("{}", tr!("merge.written", outcome.table.row.len(), target));
+        }
+    }
+
+    match written {
+        true => Ok(()),
+        false => Err(TableError::MergeConflicts(outcome.unresolved.len()).into()),
+    }
+}
+
+// Tanya pilihan untuk satu konflik; kosong/`s` → lewati (pakai --prefer jika ada)
+fn ask(conflict: &MergeConflict) -> Option<Resolution> {
+    println!("{}", tr!("merge.conflict", output::conflict_line(conflict)));
+    loop {
+        match read_input(&tr!("prompt.merge_choice"))
+            .to_lowercase()
+            .as_str()
+        {
+            "o" | "ours" => return Some(Resolution::Ours),
+            "t" | "theirs" => return Some(Resolution::Theirs),
+            "" | "s" | "skip" => return None,
+            _ => println!("{}", tr!("msg.invalid_choice")),
+        }
+    }
+}
@@ -1 +1,7 @@
 pub mod cli;
+pub mod daemon;
+pub mod merge;
+pub mod on_save;
+pub mod output;
+pub mod script;
+pub mod serve;
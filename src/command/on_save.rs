@@ -0,0 +1,164 @@
+use serde_json::json;
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use table::config;
+use table::handlers::{diff::ChangeSummary, json_rows::value_to_json, service::DataTable};
+use table::tr;
+
+// Versi tabel di disk sebelum disimpan; None → hook tidak aktif.
+// File belum ada / gagal dibaca → dianggap tabel kosong
+pub fn previous(path: &str, table: &DataTable) -> Option<DataTable> {
+    if config::current().on_save.trim().is_empty() {
+        return None;
+    }
+    let before =
+        DataTable::load_with_passphrase(path, table.passphrase.as_deref()).unwrap_or_default();
+    Some(before)
+}
+
+// Jalankan perintah `on_save` lewat `sh -c` setelah simpan berhasil.
+// Gagal atau timeout hanya dilaporkan ke stderr, exit status tidak berubah
+pub fn run(path: &str, before: &DataTable, after: &DataTable) {
+    let settings = config::current();
+    // Kunci ganda/Empty → hook tetap jalan, hanya tanpa daftar perubahan
+    let summary = after.change_summary(before).unwrap_or_else(|e| {
+        eprintln!("{}", tr!("on_save.summary_failed", e));
+        ChangeSummary::default()
+    });
+    // encrypt/decrypt/rekey mengubah file walau isinya sama
+    let encryption_changed = before.passphrase != after.passphrase;
+    if summary.is_empty() && !encryption_changed && same_content(before, after) {
+        return; // tidak ada yang berubah
+    }
+
+    let command = settings.on_save.trim();
+    let timeout = Duration::from_secs(settings.on_save_timeout as u64);
+    match execute(command, path, &summary, timeout) {
+        Ok(Some(status)) if status.success() => {}
+        Ok(Some(status)) => eprintln!("{}", tr!("on_save.failed", command, status)),
+        Ok(None) => eprintln!(
+            "{}",
+            tr!("on_save.timeout", command, settings.on_save_timeout)
+        ),
+        Err(e) => eprintln!("{}", tr!("on_save.spawn_failed", command, e)),
+    }
+}
+
+// Skema, view & trigger ikut dibandingkan (perubahan baris sudah di ringkasan)
+fn same_content(before: &DataTable, after: &DataTable) -> bool {
+    serde_json::to_value(before).ok() == serde_json::to_value(after).ok()
+}
+
+// Return None jika melewati batas waktu (proses dihentikan beserta semua turunannya)
+fn execute(
+    command: &str,
+    path: &str,
+    summary: &ChangeSummary,
+    timeout: Duration,
+) -> io::Result<Option<ExitStatus>> {
+    let keys = |values: &[table::value::Value]| -> Vec<serde_json::Value> {
+        values.iter().map(value_to_json).collect()
+    };
+    let changes = json!({
+        "added": keys(&summary.added),
+        "removed": keys(&summary.removed),
+        "modified": keys(&summary.modified),
+    });
+
+    // stdout perintah diarahkan ke stderr agar output mesin (--output json) tetap bersih
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("MYTABEL_FILE", path)
+        .env("MYTABEL_ADDED", summary.added.len().to_string())
+        .env("MYTABEL_REMOVED", summary.removed.len().to_string())
+        .env("MYTABEL_MODIFIED", summary.modified.len().to_string())
+        .env("MYTABEL_CHANGES", changes.to_string())
+        .stdin(Stdio::null())
+        .stdout(io::stderr())
+        // Grup proses sendiri: saat timeout, proses yang dijalankan `sh` ikut dihentikan
+        .process_group(0)
+        .spawn()?;
+
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if started.elapsed() >= timeout {
+            // SAFETY: kill hanya mengirim sinyal ke grup proses milik child (pgid = pid)
+            unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) };
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::{env, fs};
+    use table::value::Value;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("mytabel-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn timeout_kills_whole_process_group() {
+        let dir = test_dir("on-save-timeout");
+        let marker = dir.join("masih-hidup");
+        // Proses turunan `sh` yang menulis file setelah timeout lewat
+        let command = format!("(sleep 1; touch '{}') & sleep 5", marker.display());
+        let started = Instant::now();
+        let status = execute(
+            &command,
+            "t.json",
+            &ChangeSummary::default(),
+            Duration::from_millis(200),
+        )
+        .unwrap();
+        assert!(status.is_none());
+        assert!(started.elapsed() < Duration::from_secs(2));
+        thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists(), "proses turunan harus ikut dihentikan");
+    }
+
+    #[test]
+    fn perubahan_diteruskan_lewat_env() {
+        let dir = test_dir("on-save-env");
+        let out = dir.join("env.txt");
+        let command = format!(
+            "printf '%s|%s|%s|%s|%s' \"$MYTABEL_FILE\" \"$MYTABEL_ADDED\" \
+             \"$MYTABEL_REMOVED\" \"$MYTABEL_MODIFIED\" \"$MYTABEL_CHANGES\" > '{}'",
+            out.display()
+        );
+        let summary = ChangeSummary {
+            added: vec![Value::Numb(3), Value::Numb(4)],
+            removed: vec![Value::Text("a b".into())],
+            modified: Vec::new(),
+        };
+        let status = execute(&command, "t.json", &summary, Duration::from_secs(5)).unwrap();
+        assert!(status.is_some_and(|s| s.success()));
+
+        let text = fs::read_to_string(&out).unwrap();
+        let mut parts = text.splitn(5, '|');
+        assert_eq!(parts.next(), Some("t.json"));
+        assert_eq!(parts.next(), Some("2"));
+        assert_eq!(parts.next(), Some("1"));
+        assert_eq!(parts.next(), Some("0"));
+        let changes: serde_json::Value = serde_json::from_str(parts.next().unwrap()).unwrap();
+        assert_eq!(
+            changes,
+            json!({ "added": [3, 4], "removed": ["a b"], "modified": [] })
+        );
+    }
+}
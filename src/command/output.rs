@@ -0,0 +1,1028 @@
+use prettytable::{Attr, Cell, Row, color};
+use serde_json::{Map, Value as Json, json};
+use table::TableError;
+use table::config;
+use table::handlers::audit::{AuditEntry, AuditOp};
+use table::handlers::diff::{KeyedRow, SchemaChange, TableDiff};
+use table::handlers::hooks::action_name;
+use table::handlers::merge::{MergeConflict, MergeOutcome};
+use table::handlers::search::SearchHit;
+use table::handlers::snapshot::SnapshotInfo;
+use table::handlers::{
+    json_rows::{JsonShape, value_to_json},
+    service::DataTable,
+};
+use table::models::{RowTable, SavedView, Trigger, TriggerAction};
+use table::tr;
+use table::value::{ColumnType, Value};
+
+// Format output global CLI (--output)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Table, // tampilan untuk manusia (default)
+    Json,
+    Csv,
+    Tsv,
+}
+
+impl OutputFormat {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.to_lowercase().as_str() {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "tsv" => Ok(OutputFormat::Tsv),
+            _ => Err(tr!("opt.output", input)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+        }
+    }
+
+    pub fn is_machine(self) -> bool {
+        self != OutputFormat::Table
+    }
+}
+
+// Baris data dalam format mesin; header = nama kolom (tanpa tanda primary)
+pub fn render_rows(table: &DataTable, rows: &[&RowTable], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            table.rows_to_json_string(rows, JsonShape::Array)
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let header: Vec<String> = table.column.iter().map(|c| c.colname.clone()).collect();
+            let mut out = delimited_line(&header, format);
+            for row in rows {
+                let cells: Vec<String> = row.value.iter().map(plain_value).collect();
+                out.push_str(&delimited_line(&cells, format));
+            }
+            out
+        }
+    }
+}
+
+// Skema kolom dalam format mesin
+pub fn render_column_types(table: &DataTable, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            let columns: Vec<serde_json::Value> = table
+                .column
+                .iter()
+                .map(|c| {
+                    json!({
+                        "name": c.colname,
+                        "type": format!("{:?}", c.coltype),
+                        "primary": c.is_primary,
+                        "expr": c.expr,
+                    })
+                })
+                .collect();
+            let mut out = serde_json::to_string_pretty(&columns).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let header = ["name", "type", "primary", "expr"].map(String::from);
+            let mut out = delimited_line(&header, format);
+            for c in &table.column {
+                let cells = [
+                    c.colname.clone(),
+                    format!("{:?}", c.coltype),
+                    c.is_primary.to_string(),
+                    c.expr.clone().unwrap_or_default(),
+                ];
+                out.push_str(&delimited_line(&cells, format));
+            }
+            out
+        }
+    }
+}
+
+// Daftar config (key, nilai efektif, sumber) dalam format mesin
+pub fn render_config(entries: &[(String, toml::Value, String)], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            let items: Vec<serde_json::Value> = entries
+                .iter()
+                .map(|(key, value, source)| json!({ "key": key, "value": value, "source": source }))
+                .collect();
+            let mut out = serde_json::to_string_pretty(&items).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let mut out = delimited_line(&["key".into(), "value".into(), "source".into()], format);
+            for (key, value, source) in entries {
+                let cells = [key.clone(), plain_config_value(value), source.clone()];
+                out.push_str(&delimited_line(&cells, format));
+            }
+            out
+        }
+    }
+}
+
+// Daftar view tersimpan; `error` berisi alasan jika view tidak valid lagi
+pub fn render_views(table: &DataTable, format: OutputFormat) -> String {
+    let status = |view: &SavedView| table.check_view(view).err().map(|e| e.to_string());
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            let items: Vec<serde_json::Value> = table
+                .views
+                .iter()
+                .map(|(name, view)| {
+                    json!({
+                        "name": name,
+                        "columns": view.columns,
+                        "filter": view.filter,
+                        "sort": view.sort,
+                        "limit": view.limit,
+                        "error": status(view),
+                    })
+                })
+                .collect();
+            let mut out = serde_json::to_string_pretty(&items).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let header = ["name", "columns", "filter", "sort", "limit", "error"].map(String::from);
+            let mut out = delimited_line(&header, format);
+            for (name, view) in &table.views {
+                let cells = [
+                    name.clone(),
+                    view.columns.join(","),
+                    view.filter.clone().unwrap_or_default(),
+                    sort_spec(view),
+                    view.limit.map(|n| n.to_string()).unwrap_or_default(),
+                    status(view).unwrap_or_default(),
+                ];
+                out.push_str(&delimited_line(&cells, format));
+            }
+            out
+        }
+    }
+}
+
+// Daftar trigger dalam format mesin
+pub fn render_triggers(triggers: &[Trigger], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            let mut out = serde_json::to_string_pretty(triggers).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let header = ["name", "timing", "event", "action", "args"].map(String::from);
+            let mut out = delimited_line(&header, format);
+            for t in triggers {
+                let cells = [
+                    t.name.clone(),
+                    t.timing.name().into(),
+                    t.event.name().into(),
+                    action_name(&t.action).into(),
+                    action_args(&t.action).join(" "),
+                ];
+                out.push_str(&delimited_line(&cells, format));
+            }
+            out
+        }
+    }
+}
+
+// Catatan audit dalam format mesin; csv/tsv: satu baris per kolom yang berubah
+pub fn render_audit(entries: &[&AuditEntry], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            let mut out = serde_json::to_string_pretty(entries).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let header = ["time", "user", "op", "key", "column", "old", "new"].map(String::from);
+            let mut out = delimited_line(&header, format);
+            for entry in entries {
+                for column in changed_columns(entry) {
+                    let cell = |row: &Option<Map<String, Json>>| {
+                        row.as_ref()
+                            .and_then(|r| r.get(&column))
+                            .map(plain_json)
+                            .unwrap_or_default()
+                    };
+                    let cells = [
+                        entry.time.to_rfc3339(),
+                        entry.user.clone(),
+                        entry.op.name().into(),
+                        plain_json(&entry.key),
+                        column.clone(),
+                        cell(&entry.old),
+                        cell(&entry.new),
+                    ];
+                    out.push_str(&delimited_line(&cells, format));
+                }
+            }
+            out
+        }
+    }
+}
+
+// Satu catatan audit untuk manusia, mis. `2024-05-01 10:00:00  budi  update  umur: 20 → 21`
+pub fn audit_line(entry: &AuditEntry, with_key: bool) -> String {
+    let mut line = format!(
+        "{}  {}  {}",
+        entry.time.format("%Y-%m-%d %H:%M:%S"),
+        entry.user,
+        entry.op.name()
+    );
+    if with_key {
+        line.push_str(&format!("  [{}]", plain_json(&entry.key)));
+    }
+    let value = |row: &Option<Map<String, Json>>, column: &str| {
+        row.as_ref()
+            .and_then(|r| r.get(column))
+            .map(plain_json)
+            .unwrap_or_default()
+    };
+    let changes: Vec<String> = changed_columns(entry)
+        .iter()
+        .map(|c| match entry.op {
+            AuditOp::Insert => format!("{}={}", c, value(&entry.new, c)),
+            AuditOp::Delete => format!("{}={}", c, value(&entry.old, c)),
+            AuditOp::Update => {
+                format!("{}: {} → {}", c, value(&entry.old, c), value(&entry.new, c))
+            }
+        })
+        .collect();
+    if !changes.is_empty() {
+        line.push_str("  ");
+        line.push_str(&changes.join(", "));
+    }
+    line
+}
+
+// Kolom yang tercatat di satu catatan (urut seperti di file)
+fn changed_columns(entry: &AuditEntry) -> Vec<String> {
+    entry
+        .new
+        .as_ref()
+        .or(entry.old.as_ref())
+        .map(|row| row.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+// Nilai JSON tanpa tanda kutip; null → `-`
+fn plain_json(value: &Json) -> String {
+    match value {
+        Json::Null => "-".into(),
+        Json::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+// Perbedaan dua versi tabel dalam format mesin; csv/tsv: satu baris per perubahan
+pub fn render_diff(diff: &TableDiff, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            let row = |r: &KeyedRow| {
+                let cells: Map<String, Json> = r
+                    .cells
+                    .iter()
+                    .map(|(c, v)| (c.clone(), value_to_json(v)))
+                    .collect();
+                json!({ "key": value_to_json(&r.key), "row": cells })
+            };
+            let modified: Vec<Json> = diff
+                .modified
+                .iter()
+                .map(|r| {
+                    let cells: Vec<Json> = r
+                        .cells
+                        .iter()
+                        .map(|c| {
+                            json!({
+                                "column": c.column,
+                                "old": value_to_json(&c.old),
+                                "new": value_to_json(&c.new),
+                            })
+                        })
+                        .collect();
+                    json!({ "key": value_to_json(&r.key), "cells": cells })
+                })
+                .collect();
+            let out = json!({
+                "schema": diff.schema.iter().map(schema_json).collect::<Vec<_>>(),
+                "added": diff.added.iter().map(row).collect::<Vec<_>>(),
+                "removed": diff.removed.iter().map(row).collect::<Vec<_>>(),
+                "modified": modified,
+            });
+            let mut out = serde_json::to_string_pretty(&out).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let header = ["change", "key", "column", "old", "new"].map(String::from);
+            let mut out = delimited_line(&header, format);
+            for change in &diff.schema {
+                let (kind, column, old, new) = schema_cells(change);
+                let cells = [kind.into(), String::new(), column, old, new];
+                out.push_str(&delimited_line(&cells, format));
+            }
+            let mut line = |kind: &str, key: &Value, column: &str, old: &Value, new: &Value| {
+                let cells = [
+                    kind.into(),
+                    plain_value(key),
+                    column.into(),
+                    plain_value(old),
+                    plain_value(new),
+                ];
+                out.push_str(&delimited_line(&cells, format));
+            };
+            for r in &diff.added {
+                for (c, v) in &r.cells {
+                    line("added", &r.key, c, &Value::Empty, v);
+                }
+            }
+            for r in &diff.removed {
+                for (c, v) in &r.cells {
+                    line("removed", &r.key, c, v, &Value::Empty);
+                }
+            }
+            for r in &diff.modified {
+                for c in &r.cells {
+                    line("modified", &r.key, &c.column, &c.old, &c.new);
+                }
+            }
+            out
+        }
+    }
+}
+
+// Tampilan diff untuk manusia: perubahan skema lalu tabel baris berwarna
+// (+ hijau = baris baru, - merah = baris dihapus, ~ kuning = sel berubah)
+pub fn print_diff(diff: &TableDiff, key_name: &str) {
+    if diff.is_empty() {
+        println!("{}", tr!("diff.none"));
+        return;
+    }
+    let settings = config::current();
+    for change in &diff.schema {
+        let (kind, column, old, new) = schema_cells(change);
+        let text = match change {
+            SchemaChange::ColumnAdded { .. } => format!("+ {} ({})", column, new),
+            SchemaChange::ColumnRemoved { .. } => format!("- {} ({})", column, old),
+            _ => format!(
+                "~ {}: {} → {}",
+                format!("{} {}", kind, column).trim_end(),
+                dash(&old),
+                dash(&new)
+            ),
+        };
+        println!("{}", tr!("diff.schema", text));
+    }
+
+    let mut pt = prettytable::Table::new();
+    pt.set_format(settings.table_format());
+    let header = [
+        "",
+        key_name,
+        &tr!("diff.column"),
+        &tr!("diff.old"),
+        &tr!("diff.new"),
+    ];
+    pt.add_row(Row::new(
+        header
+            .iter()
+            .map(|h| settings.style_header(Cell::new(h)))
+            .collect(),
+    ));
+    let colored =
+        |text: &str, color: color::Color| Cell::new(text).with_style(Attr::ForegroundColor(color));
+    let cells_text = |r: &KeyedRow| {
+        r.cells
+            .iter()
+            .map(|(c, v)| format!("{}={}", c, v))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    for r in &diff.added {
+        pt.add_row(Row::new(vec![
+            colored("+", color::GREEN),
+            Cell::new(&r.key.to_string()),
+            Cell::new(""),
+            Cell::new(""),
+            colored(&cells_text(r), color::GREEN),
+        ]));
+    }
+    for r in &diff.removed {
+        pt.add_row(Row::new(vec![
+            colored("-", color::RED),
+            Cell::new(&r.key.to_string()),
+            Cell::new(""),
+            colored(&cells_text(r), color::RED),
+            Cell::new(""),
+        ]));
+    }
+    for r in &diff.modified {
+        for c in &r.cells {
+            pt.add_row(Row::new(vec![
+                colored("~", color::YELLOW),
+                Cell::new(&r.key.to_string()),
+                Cell::new(&c.column),
+                colored(&c.old.to_string(), color::RED),
+                colored(&c.new.to_string(), color::GREEN),
+            ]));
+        }
+    }
+    if pt.len() > 1 {
+        pt.printstd();
+    }
+    println!(
+        "{}",
+        tr!(
+            "diff.summary",
+            diff.added.len(),
+            diff.removed.len(),
+            diff.modified.len(),
+            diff.schema.len()
+        )
+    );
+}
+
+fn schema_json(change: &SchemaChange) -> Json {
+    let (kind, column, old, new) = schema_cells(change);
+    let opt = |s: String| {
+        if s.is_empty() {
+            Json::Null
+        } else {
+            Json::String(s)
+        }
+    };
+    json!({ "change": kind, "column": opt(column), "old": opt(old), "new": opt(new) })
+}
+
+// (jenis, kolom, lama, baru); yang tidak relevan → string kosong
+fn schema_cells(change: &SchemaChange) -> (&'static str, String, String, String) {
+    let name = |t: &ColumnType| format!("{:?}", t);
+    match change {
+        SchemaChange::ColumnAdded { column, coltype } => {
+            ("column_added", column.clone(), String::new(), name(coltype))
+        }
+        SchemaChange::ColumnRemoved { column, coltype } => (
+            "column_removed",
+            column.clone(),
+            name(coltype),
+            String::new(),
+        ),
+        SchemaChange::TypeChanged { column, old, new } => {
+            ("type_changed", column.clone(), name(old), name(new))
+        }
+        SchemaChange::ExprChanged { column, old, new } => (
+            "expr_changed",
+            column.clone(),
+            old.clone().unwrap_or_default(),
+            new.clone().unwrap_or_default(),
+        ),
+        SchemaChange::PrimaryChanged { old, new } => (
+            "primary_changed",
+            String::new(),
+            old.clone().unwrap_or_default(),
+            new.clone().unwrap_or_default(),
+        ),
+    }
+}
+
+fn dash(text: &str) -> &str {
+    if text.is_empty() { "-" } else { text }
+}
+
+// Hasil merge dalam format mesin; `written` → file tujuan jika hasil ditulis
+pub fn render_merge(outcome: &MergeOutcome, written: Option<&str>, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            let resolved: Vec<Json> = outcome
+                .resolved
+                .iter()
+                .map(|(c, choice)| {
+                    let mut entry = conflict_json(c);
+                    entry["resolution"] = json!(choice.name());
+                    entry
+                })
+                .collect();
+            let out = json!({
+                "written": written,
+                "rows": outcome.table.row.len(),
+                "resolved": resolved,
+                "conflicts": outcome.unresolved.iter().map(conflict_json).collect::<Vec<_>>(),
+            });
+            let mut out = serde_json::to_string_pretty(&out).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let header =
+                ["status", "kind", "key", "column", "base", "ours", "theirs"].map(String::from);
+            let mut out = delimited_line(&header, format);
+            let all = outcome
+                .resolved
+                .iter()
+                .map(|(c, choice)| (c, choice.name()))
+                .chain(outcome.unresolved.iter().map(|c| (c, "conflict")));
+            for (conflict, status) in all {
+                let mut cells = vec![status.to_string()];
+                cells.extend(conflict_cells(conflict));
+                out.push_str(&delimited_line(&cells, format));
+            }
+            out
+        }
+    }
+}
+
+fn conflict_json(conflict: &MergeConflict) -> Json {
+    let row = |r: &Option<Vec<(String, Value)>>| {
+        r.as_ref().map(|cells| {
+            cells
+                .iter()
+                .map(|(c, v)| (c.clone(), value_to_json(v)))
+                .collect::<Map<String, Json>>()
+        })
+    };
+    match conflict {
+        MergeConflict::Cell {
+            key,
+            column,
+            base,
+            ours,
+            theirs,
+        } => json!({
+            "kind": "cell",
+            "key": value_to_json(key),
+            "column": column,
+            "base": value_to_json(base),
+            "ours": value_to_json(ours),
+            "theirs": value_to_json(theirs),
+        }),
+        MergeConflict::Row { key, ours, theirs } => json!({
+            "kind": "row",
+            "key": value_to_json(key),
+            "ours": row(ours),
+            "theirs": row(theirs),
+        }),
+        MergeConflict::Column {
+            column,
+            ours,
+            theirs,
+        } => json!({
+            "kind": "column",
+            "column": column,
+            "ours": format!("{:?}", ours),
+            "theirs": format!("{:?}", theirs),
+        }),
+        MergeConflict::Type {
+            key,
+            column,
+            coltype,
+            value,
+        } => json!({
+            "kind": "type",
+            "key": value_to_json(key),
+            "column": column,
+            "type": format!("{:?}", coltype),
+            "value": value_to_json(value),
+        }),
+    }
+}
+
+// (jenis, kunci, kolom, base, ours, theirs); baris dihapus → `deleted`
+fn conflict_cells(conflict: &MergeConflict) -> [String; 6] {
+    let row = |r: &Option<Vec<(String, Value)>>| match r {
+        Some(cells) => cells
+            .iter()
+            .map(|(c, v)| format!("{}={}", c, plain_value(v)))
+            .collect::<Vec<_>>()
+            .join(" "),
+        None => "deleted".into(),
+    };
+    match conflict {
+        MergeConflict::Cell {
+            key,
+            column,
+            base,
+            ours,
+            theirs,
+        } => [
+            "cell".into(),
+            plain_value(key),
+            column.clone(),
+            plain_value(base),
+            plain_value(ours),
+            plain_value(theirs),
+        ],
+        MergeConflict::Row { key, ours, theirs } => [
+            "row".into(),
+            plain_value(key),
+            String::new(),
+            String::new(),
+            row(ours),
+            row(theirs),
+        ],
+        MergeConflict::Column {
+            column,
+            ours,
+            theirs,
+        } => [
+            "column".into(),
+            String::new(),
+            column.clone(),
+            String::new(),
+            format!("{:?}", ours),
+            format!("{:?}", theirs),
+        ],
+        // Tipe kolom di posisi base, nilai bermasalah di posisi ours
+        MergeConflict::Type {
+            key,
+            column,
+            coltype,
+            value,
+        } => [
+            "type".into(),
+            plain_value(key),
+            column.clone(),
+            format!("{:?}", coltype),
+            plain_value(value),
+            String::new(),
+        ],
+    }
+}
+
+// Satu konflik merge untuk manusia
+pub fn conflict_line(conflict: &MergeConflict) -> String {
+    match conflict {
+        MergeConflict::Cell {
+            key,
+            column,
+            base,
+            ours,
+            theirs,
+        } => tr!("merge.cell", key, column, base, ours, theirs),
+        MergeConflict::Row { key, ours, .. } => match ours {
+            Some(_) => tr!("merge.row_deleted_theirs", key),
+            None => tr!("merge.row_deleted_ours", key),
+        },
+        MergeConflict::Column {
+            column,
+            ours,
+            theirs,
+        } => tr!(
+            "merge.column_type",
+            column,
+            format!("{:?}", ours),
+            format!("{:?}", theirs)
+        ),
+        MergeConflict::Type {
+            key,
+            column,
+            coltype,
+            value,
+        } => tr!("merge.type", key, column, value, format!("{:?}", coltype)),
+    }
+}
+
+// Hasil search dalam format mesin, urut skor
+pub fn render_search(table: &DataTable, hits: &[SearchHit], format: OutputFormat) -> String {
+    let names = |hit: &SearchHit| -> Vec<String> {
+        hit.columns
+            .iter()
+            .map(|&i| table.column[i].colname.clone())
+            .collect()
+    };
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            let list: Vec<Json> = hits
+                .iter()
+                .map(|h| {
+                    json!({
+                        "score": h.score,
+                        "matched": names(h),
+                        "row": table.row_to_json(h.row),
+                    })
+                })
+                .collect();
+            let mut out = serde_json::to_string_pretty(&list).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let mut header = vec!["score".to_string(), "matched".into()];
+            header.extend(table.column.iter().map(|c| c.colname.clone()));
+            let mut out = delimited_line(&header, format);
+            for h in hits {
+                let mut cells = vec![h.score.to_string(), names(h).join(" ")];
+                cells.extend(h.row.value.iter().map(plain_value));
+                out.push_str(&delimited_line(&cells, format));
+            }
+            out
+        }
+    }
+}
+
+// Daftar snapshot dalam format mesin
+pub fn render_snapshots(snapshots: &[SnapshotInfo], format: OutputFormat) -> String {
+    let created = |s: &SnapshotInfo| s.created.map(|t| t.to_rfc3339()).unwrap_or_default();
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            let list: Vec<Json> = snapshots
+                .iter()
+                .map(|s| json!({ "name": s.name, "created": created(s), "path": s.path }))
+                .collect();
+            let mut out = serde_json::to_string_pretty(&list).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let header = ["name", "created", "path"].map(String::from);
+            let mut out = delimited_line(&header, format);
+            for s in snapshots {
+                let cells = [s.name.clone(), created(s), s.path.clone()];
+                out.push_str(&delimited_line(&cells, format));
+            }
+            out
+        }
+    }
+}
+
+// Argumen aksi trigger seperti ditulis di `trigger add`
+pub fn action_args(action: &TriggerAction) -> Vec<String> {
+    match action {
+        TriggerAction::Touch { column } => vec![column.clone()],
+        TriggerAction::Set { column, expr } => vec![column.clone(), quoted(expr)],
+        TriggerAction::Reject { condition, message } => {
+            let mut args = vec![quoted(condition)];
+            args.extend(message.as_deref().map(quoted));
+            args
+        }
+        TriggerAction::Log { path } => vec![path.clone()],
+    }
+}
+
+fn quoted(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\\\""))
+}
+
+// Definisi view dalam bentuk opsi `view save`
+pub fn view_spec(view: &SavedView) -> String {
+    let mut parts = Vec::new();
+    if !view.columns.is_empty() {
+        parts.push(format!("--columns {}", view.columns.join(",")));
+    }
+    if let Some(filter) = &view.filter {
+        parts.push(format!("--filter {}", quoted(filter)));
+    }
+    if !view.sort.is_empty() {
+        parts.push(format!("--sort {}", sort_spec(view)));
+    }
+    if let Some(limit) = view.limit {
+        parts.push(format!("--limit {}", limit));
+    }
+    parts.join(" ")
+}
+
+fn sort_spec(view: &SavedView) -> String {
+    let keys: Vec<String> = view
+        .sort
+        .iter()
+        .map(|k| format!("{}{}", if k.descending { "-" } else { "" }, k.column))
+        .collect();
+    keys.join(",")
+}
+
+// Nilai config tanpa tanda kutip TOML
+pub fn plain_config_value(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+// Empty → string kosong (bukan "-" seperti tampilan tabel)
+fn plain_value(value: &Value) -> String {
+    match value {
+        Value::Empty => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn delimited_line(cells: &[String], format: OutputFormat) -> String {
+    let escaped: Vec<String> = cells
+        .iter()
+        .map(|cell| match format {
+            OutputFormat::Tsv => cell
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r"),
+            _ => csv_field(cell),
+        })
+        .collect();
+    let sep = if format == OutputFormat::Tsv {
+        "\t"
+    } else {
+        ","
+    };
+    format!("{}\n", escaped.join(sep))
+}
+
+// RFC 4180: kutip jika ada koma, kutip, atau baris baru
+fn csv_field(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+// Error CLI terstruktur: kode stabil + exit status berbeda per jenis
+#[derive(Debug, Clone)]
+pub struct CliError {
+    pub code: &'static str,
+    pub status: i32,
+    pub message: String,
+}
+
+impl CliError {
+    // Error umum di luar operasi tabel (mis. terminal)
+    pub fn general(message: String) -> Self {
+        Self {
+            code: "ERROR",
+            status: 1,
+            message,
+        }
+    }
+
+    // Teks untuk stderr sesuai format output
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Json => json!({
+                "error": { "code": self.code, "message": self.message }
+            })
+            .to_string(),
+            OutputFormat::Csv | OutputFormat::Tsv => {
+                format!("error\t{}\t{}", self.code, self.message.replace('\n', " "))
+            }
+            OutputFormat::Table => format!("Error [{}]: {}", self.code, self.message),
+        }
+    }
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+// Kode & exit status per jenis error tabel
+impl From<TableError> for CliError {
+    fn from(err: TableError) -> Self {
+        let (code, status) = match &err {
+            TableError::InvalidOption(_) => ("USAGE", 2),
+            TableError::ColumnNotFound(_)
+            | TableError::TableNotFound(_)
+            | TableError::NoMatchingRow
+            | TableError::ViewNotFound(_)
+            | TableError::TriggerNotFound(_)
+            | TableError::SnapshotNotFound(_) => ("NOT_FOUND", 3),
+            TableError::ColumnExists(_)
+            | TableError::TableExists(_)
+            | TableError::DuplicatePrimary
+            | TableError::ColumnReferenced { .. }
+            | TableError::TriggerExists(_)
+            | TableError::ColumnInTrigger { .. }
+            | TableError::SnapshotExists(_)
+            | TableError::MergeConflicts(_) => ("CONFLICT", 4),
+            TableError::TypeMismatch(_)
+            | TableError::InvalidValue(_)
+            | TableError::TooManyValues { .. }
+            | TableError::InvalidRecords(_)
+            | TableError::ComputedReadOnly(_)
+            | TableError::ViewInvalid { .. }
+            | TableError::TriggerRejected { .. } => ("INVALID_VALUE", 5),
+            TableError::NoPrimary
+            | TableError::EmptyPrimary
+            | TableError::PrimaryRemoval(_)
+            | TableError::PrimaryReadOnly(_) => ("PRIMARY", 6),
+            TableError::PassphraseRequired
+            | TableError::WrongPassphrase
+            | TableError::Crypto(_) => ("CRYPTO", 7),
+            TableError::Io { .. } | TableError::Sqlite(_) => ("IO", 8),
+            TableError::Parse(_) => ("PARSE", 9),
+            TableError::NoColumns => ("SCHEMA", 10),
+        };
+        Self {
+            code,
+            status,
+            message: err.to_string(),
+        }
+    }
+}
+
+// Pesan String dari parsing argumen CLI → salah pakai
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        Self {
+            code: "USAGE",
+            status: 2,
+            message,
+        }
+    }
+}
+
+impl From<&str> for CliError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![("id", ColumnType::Numb), ("ket", ColumnType::Text)])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        for (id, ket) in [(1, Value::Text("a,\"b\"\nc".into())), (2, Value::Empty)] {
+            table.add_row(vec![Value::Numb(id), ket]).unwrap();
+        }
+        table
+    }
+
+    fn rows(table: &DataTable, format: OutputFormat) -> String {
+        let rows: Vec<&RowTable> = table.row.iter().collect();
+        render_rows(table, &rows, format)
+    }
+
+    #[test]
+    fn csv_and_tsv_escape_cells() {
+        let table = table();
+        assert_eq!(
+            rows(&table, OutputFormat::Csv),
+            "id,ket\n1,\"a,\"\"b\"\"\nc\"\n2,\n"
+        );
+        assert_eq!(
+            rows(&table, OutputFormat::Tsv),
+            "id\tket\n1\ta,\"b\"\\nc\n2\t\n"
+        );
+        assert_eq!(
+            delimited_line(&["a\tb\\".into()], OutputFormat::Tsv),
+            "a\\tb\\\\\n"
+        );
+    }
+
+    #[test]
+    fn json_rows_keep_types() {
+        let out: Json = serde_json::from_str(&rows(&table(), OutputFormat::Json)).unwrap();
+        assert_eq!(
+            out,
+            json!([{ "id": 1, "ket": "a,\"b\"\nc" }, { "id": 2, "ket": null }])
+        );
+        let types = render_column_types(&table(), OutputFormat::Csv);
+        assert_eq!(
+            types,
+            "name,type,primary,expr\nid,Numb,true,\nket,Text,false,\n"
+        );
+    }
+
+    #[test]
+    fn errors_have_stable_codes_per_format() {
+        let err = CliError::from(TableError::ColumnNotFound("x".into()));
+        assert_eq!((err.code, err.status), ("NOT_FOUND", 3));
+        let rendered: Json = serde_json::from_str(&err.render(OutputFormat::Json)).unwrap();
+        assert_eq!(rendered["error"]["code"], "NOT_FOUND");
+        assert!(
+            err.render(OutputFormat::Csv)
+                .starts_with("error\tNOT_FOUND\t")
+        );
+
+        for (err, status) in [
+            (TableError::InvalidOption("x".into()), 2),
+            (TableError::DuplicatePrimary, 4),
+            (TableError::TypeMismatch("x".into()), 5),
+            (TableError::NoPrimary, 6),
+            (TableError::WrongPassphrase, 7),
+            (TableError::Parse("x".into()), 9),
+            (TableError::NoColumns, 10),
+        ] {
+            assert_eq!(CliError::from(err).status, status);
+        }
+        assert_eq!(CliError::from("salah").status, 2);
+        assert_eq!(OutputFormat::parse("TSV"), Ok(OutputFormat::Tsv));
+        assert!(OutputFormat::parse("xml").is_err());
+    }
+}
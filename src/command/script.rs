@@ -0,0 +1,191 @@
+use crate::command::cli;
+use crate::command::output::{CliError, OutputFormat};
+use std::fs;
+use std::io::{self, Read};
+use table::handlers::service::DataTable;
+use table::tr;
+
+// Perintah yang hanya menghasilkan output/efek di luar tabel;
+// saat dry-run hanya dicek namanya, tidak dijalankan
+const OUTPUT_VERBS: &[&str] = &[
+    "print",
+    "show_column_types",
+    "tui",
+    "export_json",
+    "export_sqlite",
+    "dump_sql",
+    "encrypt",
+    "decrypt",
+    "rekey",
+    "history",
+    "log",
+    "snapshot",
+    "diff",
+    "merge",
+    "search",
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct ScriptOptions {
+    pub stop_on_error: bool,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ScriptReport {
+    pub executed: usize,
+    pub errors: Vec<(usize, String)>, // (nomor baris, pesan)
+    pub stopped: bool,                // berhenti karena --stop-on-error
+}
+
+// Pecah script menjadi perintah: (nomor baris awal, argumen).
+// Baris kosong & komentar `#` dilewati, `\` di akhir baris menyambung baris berikutnya
+pub fn parse_script(source: &str) -> Vec<(usize, Result<Vec<String>, String>)> {
+    let mut commands = Vec::new();
+    let mut pending = String::new();
+    let mut start = 0;
+
+    for (i, line) in source.lines().enumerate() {
+        if pending.is_empty() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            start = i + 1;
+        } else {
+            pending.push('\n');
+        }
+        pending.push_str(line);
+
+        let trailing_backslashes = line.chars().rev().take_while(|c| *c == '\\').count();
+        if trailing_backslashes % 2 == 1 {
+            continue;
+        }
+        commands.push((start, cli::split_args(&std::mem::take(&mut pending))));
+    }
+    if !pending.is_empty() {
+        commands.push((start, Err(tr!("script.trailing_backslash"))));
+    }
+
+    commands
+}
+
+// Jalankan script; saat dry-run semua perintah dijalankan pada salinan tabel
+pub fn run_script(table: &mut DataTable, source: &str, opts: &ScriptOptions) -> ScriptReport {
+    let mut report = ScriptReport::default();
+    // Salinan berbagi log audit dengan tabel asli; catatan dry-run dibuang di akhir
+    let audit_mark = table.audit.len();
+    let mut scratch = opts.dry_run.then(|| table.clone());
+    let target = scratch.as_mut().unwrap_or(table);
+
+    for (line, parsed) in parse_script(source) {
+        // Sambungan `\` yang ternyata kosong → tidak ada perintah
+        if parsed.as_ref().is_ok_and(|args| args.is_empty()) {
+            continue;
+        }
+        let result = parsed.map_err(CliError::from).and_then(|args| {
+            if opts.dry_run && OUTPUT_VERBS.contains(&args[0].as_str()) {
+                return Ok(());
+            }
+            cli::run_command(target, args, OutputFormat::Table)
+        });
+
+        match result {
+            Ok(_) => report.executed += 1,
+            Err(e) => {
+                report.errors.push((line, e.message));
+                if opts.stop_on_error {
+                    report.stopped = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    if opts.dry_run {
+        table.audit.truncate(audit_mark);
+    }
+    report
+}
+
+// Baca script dari file, atau stdin jika path `-` / tidak diberikan
+pub fn read_script(path: Option<&str>) -> Result<String, String> {
+    match path {
+        Some(path) if path != "-" => {
+            fs::read_to_string(path).map_err(|e| tr!("script.read_file", path, e))
+        }
+        _ => {
+            let mut source = String::new();
+            io::stdin()
+                .read_to_string(&mut source)
+                .map_err(|e| tr!("script.read_stdin", e))?;
+            Ok(source)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETUP: &str = "# skema\n\
+        add_column id numb nama text\n\
+        \n\
+        set_primary id\n\
+        add_row 1 \\\n  \"kopi susu\"\n\
+        add_row x y\n\
+        add_row 2 teh\n";
+
+    #[test]
+    fn parse_skips_comments_and_joins_continued_lines() {
+        let commands = parse_script(SETUP);
+        let lines: Vec<usize> = commands.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, [2, 4, 5, 7, 8]);
+        assert_eq!(
+            commands[2].1.as_deref().unwrap(),
+            ["add_row", "1", "kopi susu"]
+        );
+        let open = parse_script("add_row 1 \\");
+        assert!(open[0].1.is_err());
+        assert!(
+            parse_script("print \"belum ditutup")
+                .last()
+                .unwrap()
+                .1
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn errors_report_line_and_stop_when_asked() {
+        let mut table = DataTable::default();
+        let report = run_script(&mut table, SETUP, &ScriptOptions::default());
+        assert_eq!(report.executed, 4);
+        assert_eq!(report.errors.iter().map(|e| e.0).collect::<Vec<_>>(), [7]);
+        assert!(!report.stopped);
+        assert_eq!(table.row.len(), 2);
+
+        let mut table = DataTable::default();
+        let opts = ScriptOptions {
+            stop_on_error: true,
+            ..ScriptOptions::default()
+        };
+        let report = run_script(&mut table, SETUP, &opts);
+        assert!(report.stopped);
+        assert_eq!(table.row.len(), 1);
+    }
+
+    #[test]
+    fn dry_run_leaves_table_untouched() {
+        let mut table = DataTable::default();
+        table.enable_audit();
+        let opts = ScriptOptions {
+            dry_run: true,
+            ..ScriptOptions::default()
+        };
+        let report = run_script(&mut table, SETUP, &opts);
+        assert_eq!(report.errors.len(), 1);
+        assert!(table.column.is_empty() && table.row.is_empty());
+        assert_eq!(table.audit.len(), 0);
+    }
+}
@@ -0,0 +1,634 @@
+use crate::command::cli::{take_number, take_option};
+use crate::command::output::CliError;
+use serde_json::{Map, Value as Json, json};
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use table::config;
+use table::handlers::service::DataTable;
+use table::models::RowTable;
+use table::tr;
+use table::value::Value;
+use table::{DataBase, TableError};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+// Nama tabel di API (sama dengan default export_sqlite)
+pub const TABLE_NAME: &str = "mytabel";
+pub const DEFAULT_PORT: u16 = 8080;
+// Jeda simpan otomatis ke file (detik); 0 → simpan setiap ada perubahan
+pub const DEFAULT_SAVE_INTERVAL: u64 = 5;
+// Batas ukuran body request
+const MAX_BODY: u64 = 1024 * 1024;
+// Jeda maksimum menunggu request sebelum cek sinyal berhenti
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Diset handler SIGINT/SIGTERM; loop server berhenti lalu tabel disimpan pemanggil
+static STOP: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn request_stop(_signal: libc::c_int) {
+    STOP.store(true, Ordering::SeqCst);
+}
+
+pub struct ServeOptions {
+    pub port: u16,
+    pub save_interval: Duration,
+}
+
+impl ServeOptions {
+    // serve [--port N] [--save-interval DETIK]
+    pub fn from_args(args: &mut Vec<String>) -> Result<Self, String> {
+        let port = match take_number(args, "--port")? {
+            Some(n) => u16::try_from(n).map_err(|_| tr!("opt.positive", "--port", n))?,
+            None => DEFAULT_PORT,
+        };
+        let save_interval = match take_option(args, "--save-interval")? {
+            Some(s) => s
+                .parse::<u64>()
+                .map_err(|_| tr!("opt.number", "--save-interval", s))?,
+            None => DEFAULT_SAVE_INTERVAL,
+        };
+        Ok(Self {
+            port,
+            save_interval: Duration::from_secs(save_interval),
+        })
+    }
+}
+
+// Jawaban sukses: status HTTP + body JSON (None → tanpa body)
+struct Reply {
+    status: u16,
+    body: Option<Json>,
+}
+
+impl Reply {
+    fn ok(body: Json) -> Self {
+        Self {
+            status: 200,
+            body: Some(body),
+        }
+    }
+
+    fn created(body: Json) -> Self {
+        Self {
+            status: 201,
+            body: Some(body),
+        }
+    }
+
+    fn no_content() -> Self {
+        Self {
+            status: 204,
+            body: None,
+        }
+    }
+}
+
+// Jawaban gagal: kode error sama dengan CLI, status HTTP sesuai jenisnya
+struct ApiError {
+    status: u16,
+    error: CliError,
+}
+
+impl ApiError {
+    fn new(status: u16, code: &'static str, message: String) -> Self {
+        Self {
+            status,
+            error: CliError {
+                code,
+                status: 1,
+                message,
+            },
+        }
+    }
+}
+
+impl From<TableError> for ApiError {
+    fn from(err: TableError) -> Self {
+        let error = CliError::from(err);
+        let status = match error.code {
+            "USAGE" | "PARSE" => 400,
+            "NOT_FOUND" => 404,
+            "CONFLICT" => 409,
+            "INVALID_VALUE" | "PRIMARY" | "SCHEMA" => 422,
+            _ => 500,
+        };
+        Self { status, error }
+    }
+}
+
+// Jalankan API di localhost. Satu thread: request diproses berurutan sehingga
+// akses ke state selalu serial; perubahan disimpan lewat `save` secara berkala
+pub fn run_server<F>(table: &mut DataTable, opts: &ServeOptions, save: F) -> Result<(), String>
+where
+    F: Fn(&DataTable) -> Result<(), TableError>,
+{
+    let addr = format!("127.0.0.1:{}", opts.port);
+    let server = Server::http(&addr).map_err(|e| tr!("serve.bind_failed", addr, e))?;
+
+    let mut db = DataBase::new();
+    db.tables.insert(TABLE_NAME.into(), std::mem::take(table));
+    db.lot.push(TABLE_NAME.into());
+    println!(
+        "{}",
+        tr!("serve.listening", addr, opts.save_interval.as_secs())
+    );
+
+    let handler = request_stop as extern "C" fn(libc::c_int);
+    unsafe {
+        libc::signal(libc::SIGINT, handler as libc::sighandler_t);
+        libc::signal(libc::SIGTERM, handler as libc::sighandler_t);
+    }
+
+    let mut dirty = false;
+    let mut last_save = Instant::now();
+    let result = loop {
+        if STOP.load(Ordering::SeqCst) {
+            println!("{}", tr!("serve.shutdown"));
+            break Ok(());
+        }
+        let wait = opts
+            .save_interval
+            .saturating_sub(last_save.elapsed())
+            .clamp(Duration::from_millis(100), POLL_INTERVAL);
+        match server.recv_timeout(wait) {
+            Ok(Some(request)) => dirty |= handle(&mut db, request),
+            Ok(None) => {}
+            Err(e) => break Err(tr!("serve.stopped", e)),
+        }
+
+        if dirty && last_save.elapsed() >= opts.save_interval {
+            // Gagal simpan → tetap dirty, dicoba lagi di putaran berikutnya
+            match db.tables.get(TABLE_NAME).map(&save) {
+                Some(Err(e)) => eprintln!("{}", tr!("main.save_failed", e)),
+                _ => dirty = false,
+            }
+            last_save = Instant::now();
+        }
+    };
+
+    *table = db.tables.remove(TABLE_NAME).unwrap_or_default();
+    result
+}
+
+// Proses satu request; return true jika state berubah
+fn handle(db: &mut DataBase, mut request: Request) -> bool {
+    let method = request.method().clone();
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| decode(s, false))
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    let mutating = !matches!(method, Method::Get | Method::Head);
+    let result = match mutating {
+        true => check_mutation(&request),
+        false => Ok(()),
+    }
+    .and_then(|_| read_body(&mut request))
+    .and_then(|body| {
+        // HEAD dijawab seperti GET; tiny_http tidak mengirim body untuk HEAD
+        let method = match method {
+            Method::Head => Method::Get,
+            ref other => other.clone(),
+        };
+        route(db, &method, &segments, path, query, &body)
+    });
+    let changed = result.is_ok() && mutating;
+
+    let (status, body) = match result {
+        Ok(reply) => (reply.status, reply.body),
+        Err(e) => (
+            e.status,
+            Some(json!({ "error": { "code": e.error.code, "message": e.error.message } })),
+        ),
+    };
+    let content_type = Header::from_bytes("Content-Type", "application/json; charset=utf-8")
+        .expect("header statis valid");
+    let text = body.map(|b| format!("{}\n", b)).unwrap_or_default();
+    let response = Response::from_string(text)
+        .with_status_code(status)
+        .with_header(content_type);
+    let _ = request.respond(response); // klien putus → abaikan
+    changed
+}
+
+// Request yang mengubah data harus JSON & tidak berasal dari halaman web lain:
+// form/fetch biasa dari situs mana pun bisa dikirim browser ke localhost
+fn check_mutation(request: &Request) -> Result<(), ApiError> {
+    let header = |name: &str| {
+        request
+            .headers()
+            .iter()
+            .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+            .map(|h| h.value.as_str().trim())
+    };
+    let is_json = header("Content-Type")
+        .and_then(|v| v.split(';').next())
+        .is_some_and(|v| v.trim().eq_ignore_ascii_case("application/json"));
+    if !is_json {
+        return Err(ApiError::new(415, "USAGE", tr!("serve.content_type")));
+    }
+    if let Some(origin) = header("Origin")
+        && !is_local_origin(origin)
+    {
+        return Err(ApiError::new(403, "USAGE", tr!("serve.origin", origin)));
+    }
+    Ok(())
+}
+
+// `http://localhost:8080`, `http://127.0.0.1`, `http://[::1]:3000`, ...
+fn is_local_origin(origin: &str) -> bool {
+    let Some((_, rest)) = origin.split_once("://") else {
+        return false;
+    };
+    let host = match rest.strip_prefix('[') {
+        Some(v6) => v6.split(']').next().unwrap_or_default(),
+        None => rest.split(':').next().unwrap_or_default(),
+    };
+    matches!(host, "localhost" | "127.0.0.1" | "::1")
+}
+
+fn read_body(request: &mut Request) -> Result<String, ApiError> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| ApiError::new(400, "PARSE", tr!("serve.body", e)))?;
+    if body.len() as u64 > MAX_BODY {
+        return Err(ApiError::new(
+            413,
+            "INVALID_VALUE",
+            tr!("serve.body_too_large", MAX_BODY),
+        ));
+    }
+    Ok(body)
+}
+
+//   GET    /tables                      daftar tabel
+//   GET    /tables/{nama}               skema kolom
+//   GET    /tables/{nama}/rows          ?page=&limit=&where=KOLOM=NILAI (boleh berulang)
+//   POST   /tables/{nama}/rows          body objek JSON → add_row
+//   GET    /tables/{nama}/rows/{pk}     satu baris berdasarkan primary
+//   PATCH  /tables/{nama}/rows/{pk}     body objek JSON sebagian → set_value_where
+//   DELETE /tables/{nama}/rows/{pk}     remove_row
+// HEAD boleh untuk semua rute GET (tanpa body).
+// POST/PATCH/DELETE wajib `Content-Type: application/json`; Origin selain localhost ditolak
+fn route(
+    db: &mut DataBase,
+    method: &Method,
+    segments: &[&str],
+    path: &str,
+    query: &str,
+    body: &str,
+) -> Result<Reply, ApiError> {
+    match (method, segments) {
+        (Method::Get, ["tables"]) => Ok(Reply::ok(list_tables(db))),
+        (Method::Get, ["tables", name]) => Ok(Reply::ok(schema(name, table(db, name)?))),
+        (Method::Get, ["tables", name, "rows"]) => list_rows(table(db, name)?, query),
+        (Method::Post, ["tables", name, "rows"]) => insert_row(table(db, name)?, body),
+        (Method::Get, ["tables", name, "rows", key]) => get_row(table(db, name)?, key),
+        (Method::Patch, ["tables", name, "rows", key]) => update_row(table(db, name)?, key, body),
+        (Method::Delete, ["tables", name, "rows", key]) => delete_row(table(db, name)?, key),
+        (_, ["tables"] | ["tables", _] | ["tables", _, "rows"] | ["tables", _, "rows", _]) => Err(
+            ApiError::new(405, "USAGE", tr!("serve.method", method, path)),
+        ),
+        _ => Err(ApiError::new(
+            404,
+            "NOT_FOUND",
+            tr!("serve.no_route", method, path),
+        )),
+    }
+}
+
+fn table<'a>(db: &'a mut DataBase, name: &str) -> Result<&'a mut DataTable, TableError> {
+    db.get_table(name)
+        .ok_or_else(|| TableError::TableNotFound(name.into()))
+}
+
+fn list_tables(db: &DataBase) -> Json {
+    let tables: Vec<Json> = db
+        .lot
+        .iter()
+        .filter_map(|name| db.tables.get(name).map(|t| (name, t)))
+        .map(|(name, t)| json!({ "name": name, "columns": t.column.len(), "rows": t.row.len() }))
+        .collect();
+    Json::Array(tables)
+}
+
+fn schema(name: &str, table: &DataTable) -> Json {
+    let columns: Vec<Json> = table
+        .column
+        .iter()
+        .map(|c| json!({ "name": c.colname, "type": format!("{:?}", c.coltype), "primary": c.is_primary }))
+        .collect();
+    let primary = table.primary_index.map(|i| table.column[i].colname.clone());
+    json!({ "name": name, "primary": primary, "columns": columns })
+}
+
+fn list_rows(table: &DataTable, query: &str) -> Result<Reply, ApiError> {
+    let mut page = 1;
+    let mut limit = config::current().page_size;
+    let mut conditions: Vec<(usize, Value)> = Vec::new();
+
+    for (key, value) in query_pairs(query) {
+        match key.as_str() {
+            "page" => page = positive(&key, &value)?,
+            "limit" => limit = positive(&key, &value)?,
+            "where" => {
+                let (colname, input) = value
+                    .split_once('=')
+                    .ok_or_else(|| TableError::InvalidOption(tr!("opt.condition", value)))?;
+                let index = table.column_position(colname)?;
+                conditions.push((index, table.parse_value(colname, input)?));
+            }
+            _ => return Err(TableError::InvalidOption(tr!("serve.query", key)).into()),
+        }
+    }
+
+    let matched: Vec<&RowTable> = table
+        .row
+        .iter()
+        .filter(|row| conditions.iter().all(|(i, v)| row.value.get(*i) == Some(v)))
+        .collect();
+    let rows: Vec<Json> = matched
+        .iter()
+        .skip((page - 1) * limit)
+        .take(limit)
+        .map(|row| Json::Object(table.row_to_json(row)))
+        .collect();
+
+    Ok(Reply::ok(json!({
+        "page": page,
+        "limit": limit,
+        "total": matched.len(),
+        "pages": matched.len().div_ceil(limit),
+        "rows": rows,
+    })))
+}
+
+fn positive(key: &str, value: &str) -> Result<usize, TableError> {
+    match value.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(TableError::InvalidOption(tr!("opt.positive", key, value))),
+    }
+}
+
+fn insert_row(table: &mut DataTable, body: &str) -> Result<Reply, ApiError> {
+    let record = json_object(body)?;
+    table.insert(&record)?;
+    let row = table.row.last().map(|r| table.row_to_json(r));
+    Ok(Reply::created(Json::Object(row.unwrap_or_default())))
+}
+
+fn get_row(table: &DataTable, key: &str) -> Result<Reply, ApiError> {
+    let position = find_row(table, key)?.1;
+    Ok(Reply::ok(Json::Object(
+        table.row_to_json(&table.row[position]),
+    )))
+}
+
+// Ubah beberapa kolom sekaligus; satu gagal → tidak ada yang berubah
+fn update_row(table: &mut DataTable, key: &str, body: &str) -> Result<Reply, ApiError> {
+    let (primary, position) = find_row(table, key)?;
+    let record = json_object(body)?;
+    let values = table.json_object_to_values(&record)?;
+
+    let mut updated = table.clone();
+    let primary_col = updated.column[primary].colname.clone();
+    let mut current = updated.row[position].value[primary].clone();
+    // Catatan audit dari perubahan yang batal ikut dibuang
+    let audit_mark = table.audit.len();
+    for colname in record.keys() {
+        let index = updated.column_position(colname)?;
+        let value = values[index].clone();
+        if updated.row[position].value[index] == value {
+            continue;
+        }
+        if let Err(e) =
+            updated.set_value_where(&primary_col, current.clone(), colname, value.clone())
+        {
+            table.audit.truncate(audit_mark);
+            return Err(e.into());
+        }
+        if index == primary {
+            current = value;
+        }
+    }
+
+    *table = updated;
+    Ok(Reply::ok(Json::Object(
+        table.row_to_json(&table.row[position]),
+    )))
+}
+
+fn delete_row(table: &mut DataTable, key: &str) -> Result<Reply, ApiError> {
+    let (primary, position) = find_row(table, key)?;
+    let colname = table.column[primary].colname.clone();
+    let value = table.row[position].value[primary].clone();
+    table.remove_row(&colname, value)?;
+    Ok(Reply::no_content())
+}
+
+// (index kolom primary, posisi baris) untuk primary `key` dari URL
+fn find_row(table: &DataTable, key: &str) -> Result<(usize, usize), TableError> {
+    let primary = table.primary_index.ok_or(TableError::NoPrimary)?;
+    let value = table.parse_value(&table.column[primary].colname, key)?;
+    table
+        .find_by_primary(&value)?
+        .map(|row| (primary, row.position()))
+        .ok_or(TableError::NoMatchingRow)
+}
+
+fn json_object(body: &str) -> Result<Map<String, Json>, TableError> {
+    match serde_json::from_str(body)? {
+        Json::Object(map) => Ok(map),
+        _ => Err(TableError::InvalidValue(tr!("val.record_object"))),
+    }
+}
+
+fn query_pairs(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key, true), decode(value, true))
+        })
+        .collect()
+}
+
+// Percent-decoding URL; `+` → spasi hanya di query string
+fn decode(input: &str, plus_as_space: bool) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(b) => {
+                        out.push(b);
+                        i += 3;
+                        continue;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            b'+' if plus_as_space => out.push(b' '),
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use table::value::ColumnType;
+
+    fn db() -> DataBase {
+        let mut db = DataBase::new();
+        db.add_table(TABLE_NAME).unwrap();
+        let table = db.get_table(TABLE_NAME).unwrap();
+        table
+            .add_column(vec![("id", ColumnType::Numb), ("nama", ColumnType::Text)])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        for (id, nama) in [(1, "budi santoso"), (2, "ani"), (3, "budi santoso")] {
+            table
+                .add_row(vec![Value::Numb(id), Value::Text(nama.into())])
+                .unwrap();
+        }
+        db
+    }
+
+    fn call(db: &mut DataBase, method: Method, path: &str, body: &str) -> Result<Reply, ApiError> {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        route(db, &method, &segments, path, query, body)
+    }
+
+    fn status(result: Result<Reply, ApiError>) -> u16 {
+        match result {
+            Ok(reply) => reply.status,
+            Err(err) => err.status,
+        }
+    }
+
+    #[test]
+    fn origin_lokal_saja_yang_diterima() {
+        for origin in [
+            "http://localhost:8080",
+            "http://127.0.0.1",
+            "http://[::1]:3000",
+        ] {
+            assert!(is_local_origin(origin), "{}", origin);
+        }
+        for origin in [
+            "http://localhost.evil.com",
+            "https://example.com",
+            "localhost",
+        ] {
+            assert!(!is_local_origin(origin), "{}", origin);
+        }
+    }
+
+    #[test]
+    fn query_string_didekode() {
+        assert_eq!(
+            query_pairs("where=nama%3Dbudi+santoso&&page=2"),
+            [
+                ("where".to_string(), "nama=budi santoso".to_string()),
+                ("page".to_string(), "2".to_string()),
+            ]
+        );
+        assert_eq!(decode("a+b%2", false), "a+b%2");
+        assert_eq!(decode("%zz%41", true), "%zzA");
+    }
+
+    #[test]
+    fn rute_crud_dan_status_http() {
+        let mut db = db();
+        let list = call(
+            &mut db,
+            Method::Get,
+            "/tables/mytabel/rows?where=nama%3Dbudi+santoso&limit=1",
+            "",
+        )
+        .ok()
+        .and_then(|r| r.body)
+        .unwrap();
+        assert_eq!(list["total"], 2);
+        assert_eq!(list["pages"], 2);
+        assert_eq!(list["rows"][0]["id"], 1);
+
+        let created = call(
+            &mut db,
+            Method::Post,
+            "/tables/mytabel/rows",
+            r#"{"id":4,"nama":"cici"}"#,
+        );
+        assert_eq!(status(created), 201);
+        assert_eq!(
+            status(call(
+                &mut db,
+                Method::Post,
+                "/tables/mytabel/rows",
+                r#"{"id":4}"#
+            )),
+            409
+        );
+        let patched = call(
+            &mut db,
+            Method::Patch,
+            "/tables/mytabel/rows/4",
+            r#"{"nama":"dedi"}"#,
+        )
+        .ok()
+        .and_then(|r| r.body)
+        .unwrap();
+        assert_eq!(patched["nama"], "dedi");
+        assert_eq!(
+            status(call(&mut db, Method::Delete, "/tables/mytabel/rows/4", "")),
+            204
+        );
+
+        assert_eq!(
+            status(call(&mut db, Method::Get, "/tables/mytabel/rows/4", "")),
+            404
+        );
+        assert_eq!(status(call(&mut db, Method::Get, "/tables/lain", "")), 404);
+        assert_eq!(
+            status(call(&mut db, Method::Put, "/tables/mytabel/rows", "")),
+            405
+        );
+        assert_eq!(
+            status(call(
+                &mut db,
+                Method::Get,
+                "/tables/mytabel/rows?page=0",
+                ""
+            )),
+            400
+        );
+        assert_eq!(status(call(&mut db, Method::Get, "/lain", "")), 404);
+    }
+
+    #[test]
+    fn patch_gagal_tidak_mengubah_apa_pun() {
+        let mut db = db();
+        let body = r#"{"nama":"baru","id":1}"#;
+        assert_eq!(
+            status(call(&mut db, Method::Patch, "/tables/mytabel/rows/2", body)),
+            409
+        );
+        let table = db.get_table(TABLE_NAME).unwrap();
+        assert_eq!(table.row[1].value[1], Value::Text("ani".into()));
+    }
+}
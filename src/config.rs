@@ -0,0 +1,385 @@
+use crate::error::TableError;
+use crate::i18n::Lang;
+use crate::table::handlers::pager::DEFAULT_PAGE_SIZE;
+use crate::tr;
+use prettytable::format::{self, TableFormat};
+use prettytable::{Attr, Cell, color};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::{env, fs};
+
+// Lokasi file penyimpanan tabel (default)
+pub const DEFAULT_STORAGE_PATH: &str = "/data/data/com.termux/files/home/.mytabel.json";
+// Env var untuk mengganti lokasi config global
+pub const CONFIG_ENV: &str = "MYTABEL_CONFIG";
+// Nama file config per proyek; dicari dari direktori kerja ke atas
+pub const PROJECT_FILE: &str = ".mytabel.toml";
+// Key yang hanya boleh di config global: file proyek bisa berasal dari repo orang lain,
+// jadi tidak boleh menentukan perintah shell yang dijalankan
+const GLOBAL_ONLY_KEYS: &[&str] = &["on_save"];
+
+// Gaya garis tabel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Border {
+    Box,   // garis unicode (default)
+    Ascii, // +---+ untuk terminal tanpa unicode
+    Clean, // tanpa garis
+}
+
+// Warna header tabel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeaderColor {
+    None,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+// Preferensi pengguna. Semua key opsional di file; yang tidak ada → default:
+//   storage_path  = "/data/data/com.termux/files/home/.mytabel.json"
+//   border        = "box"      (box | ascii | clean)
+//   empty_display = "-"        (tampilan nilai Empty)
+//   header_color  = "yellow"   (none | red | green | yellow | blue | magenta | cyan | white)
+//   lang          = "auto"     (auto | id | en; auto → LC_ALL / LC_MESSAGES / LANG)
+//   page_size     = 20         (baris per halaman)
+//   backup_count  = 0          (jumlah backup file tabel; 0 → tanpa backup)
+//   on_save       = ""         (perintah shell setelah tabel disimpan; kosong → tidak ada;
+//                               hanya dari config global)
+//   on_save_timeout = 10       (detik sebelum perintah on_save dihentikan)
+//   audit         = true       (catat perubahan baris ke `<storage_path>.audit`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub storage_path: String,
+    pub border: Border,
+    pub empty_display: String,
+    pub header_color: HeaderColor,
+    pub lang: String,
+    pub page_size: usize,
+    pub backup_count: usize,
+    pub on_save: String,
+    pub on_save_timeout: usize,
+    pub audit: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            storage_path: DEFAULT_STORAGE_PATH.into(),
+            border: Border::Box,
+            empty_display: "-".into(),
+            header_color: HeaderColor::Yellow,
+            lang: "auto".into(),
+            page_size: DEFAULT_PAGE_SIZE,
+            backup_count: 0,
+            on_save: String::new(),
+            on_save_timeout: 10,
+            audit: true,
+        }
+    }
+}
+
+// Satu file config yang ikut dibaca, urut dari prioritas terendah
+pub struct ConfigLayer {
+    pub path: PathBuf,
+    pub values: toml::Table,
+    pub global: bool, // false → file proyek
+}
+
+static CURRENT: OnceLock<Config> = OnceLock::new();
+
+// Pasang config aktif (sekali saat startup); belum dipasang → default
+pub fn install(config: Config) {
+    let _ = CURRENT.set(config);
+}
+
+pub fn current() -> &'static Config {
+    CURRENT.get_or_init(Config::default)
+}
+
+// Config global: $MYTABEL_CONFIG, lalu $XDG_CONFIG_HOME/mytabel, lalu ~/.config/mytabel
+pub fn global_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_ENV).filter(|p| !p.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(base.join("mytabel").join("config.toml"))
+}
+
+// File proyek dari direktori terluar ke direktori kerja (yang terdekat menang)
+pub fn project_paths() -> Vec<PathBuf> {
+    let Ok(cwd) = env::current_dir() else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = cwd
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_FILE))
+        .filter(|p| p.is_file())
+        .collect();
+    paths.reverse();
+    paths
+}
+
+// Baca semua file config yang ada: global dulu, lalu file proyek
+pub fn load_layers() -> Result<Vec<ConfigLayer>, TableError> {
+    let global = global_path().filter(|p| p.is_file());
+    let global_count = usize::from(global.is_some());
+    global
+        .into_iter()
+        .chain(project_paths())
+        .enumerate()
+        .map(|(i, path)| {
+            let values = read_table(&path)?;
+            Ok(ConfigLayer {
+                path,
+                values,
+                global: i < global_count,
+            })
+        })
+        .collect()
+}
+
+fn read_table(path: &Path) -> Result<toml::Table, TableError> {
+    let shown = path.display().to_string();
+    let text = fs::read_to_string(path).map_err(TableError::io(&shown))?;
+    text.parse::<toml::Table>()
+        .map_err(|e| TableError::Parse(tr!("config.parse_failed", shown, e)))
+}
+
+impl Config {
+    // Config efektif dari semua file + default
+    pub fn load() -> Result<Self, TableError> {
+        Self::from_layers(&load_layers()?)
+    }
+
+    pub fn from_layers(layers: &[ConfigLayer]) -> Result<Self, TableError> {
+        let mut merged = toml::Table::new();
+        for layer in layers {
+            if let Some(key) = GLOBAL_ONLY_KEYS
+                .iter()
+                .find(|key| !layer.global && layer.values.contains_key(**key))
+            {
+                return Err(TableError::InvalidValue(tr!(
+                    "config.global_only",
+                    key,
+                    layer.path.display()
+                )));
+            }
+            merged.extend(layer.values.clone());
+        }
+        let config: Config =
+            toml::Value::Table(merged)
+                .try_into()
+                .map_err(|e: toml::de::Error| {
+                    TableError::InvalidValue(tr!("config.invalid", e.message()))
+                })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), TableError> {
+        if self.lang != "auto" && Lang::parse(&self.lang).is_none() {
+            return Err(TableError::InvalidValue(tr!("opt.lang", self.lang)));
+        }
+        for (key, value) in [
+            ("page_size", self.page_size),
+            ("on_save_timeout", self.on_save_timeout),
+        ] {
+            if value == 0 {
+                return Err(TableError::InvalidValue(tr!("opt.positive", key, value)));
+            }
+        }
+        Ok(())
+    }
+
+    // Semua key beserta nilai efektif, urut sesuai definisi struct
+    pub fn entries(&self) -> toml::Table {
+        toml::Table::try_from(self).unwrap_or_default()
+    }
+
+    // Bahasa dari config; "auto" → None (pakai env)
+    pub fn lang(&self) -> Option<Lang> {
+        Lang::parse(&self.lang)
+    }
+
+    // Path tabel dengan `~/` diganti $HOME
+    pub fn storage_path(&self) -> String {
+        match (self.storage_path.strip_prefix("~/"), env::var("HOME")) {
+            (Some(rest), Ok(home)) => Path::new(&home).join(rest).display().to_string(),
+            _ => self.storage_path.clone(),
+        }
+    }
+
+    pub fn table_format(&self) -> TableFormat {
+        match self.border {
+            Border::Box => *format::consts::FORMAT_BOX_CHARS,
+            Border::Ascii => *format::consts::FORMAT_DEFAULT,
+            Border::Clean => *format::consts::FORMAT_CLEAN,
+        }
+    }
+
+    // Gaya sel header: tebal + warna sesuai config
+    pub fn style_header(&self, cell: Cell) -> Cell {
+        let cell = cell.style_spec("c").with_style(Attr::Bold);
+        let color = match self.header_color {
+            HeaderColor::None => return cell,
+            HeaderColor::Red => color::RED,
+            HeaderColor::Green => color::GREEN,
+            HeaderColor::Yellow => color::YELLOW,
+            HeaderColor::Blue => color::BLUE,
+            HeaderColor::Magenta => color::MAGENTA,
+            HeaderColor::Cyan => color::CYAN,
+            HeaderColor::White => color::WHITE,
+        };
+        cell.with_style(Attr::ForegroundColor(color))
+    }
+}
+
+// Ubah satu key di file config `path`; nilai dicek dulu terhadap semua layer
+pub fn set_value(path: &Path, key: &str, input: &str) -> Result<(), TableError> {
+    let defaults = Config::default().entries();
+    let value = match defaults.get(key) {
+        None => return Err(TableError::InvalidOption(tr!("config.unknown_key", key))),
+        Some(toml::Value::Integer(_)) => input
+            .parse::<i64>()
+            .map(toml::Value::Integer)
+            .map_err(|_| TableError::InvalidValue(tr!("config.number", key, input)))?,
+        Some(toml::Value::Boolean(_)) => match input.to_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => toml::Value::Boolean(true),
+            "false" | "no" | "off" | "0" => toml::Value::Boolean(false),
+            _ => return Err(TableError::InvalidValue(tr!("config.bool", key, input))),
+        },
+        Some(_) => toml::Value::String(input.into()),
+    };
+
+    let mut values = if path.is_file() {
+        read_table(path)?
+    } else {
+        toml::Table::new()
+    };
+    values.insert(key.into(), value);
+
+    // Validasi dengan layer lain ikut dihitung; file target menggantikan versi lamanya
+    let mut layers = load_layers()?;
+    let global = global_path().as_deref() == Some(path);
+    let updated = ConfigLayer {
+        path: path.to_path_buf(),
+        values: values.clone(),
+        global,
+    };
+    match layers.iter().position(|layer| layer.path == path) {
+        Some(i) => layers[i] = updated,
+        None if global => layers.insert(0, updated),
+        None => layers.push(updated),
+    }
+    Config::from_layers(&layers)?;
+
+    let shown = path.display().to_string();
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(TableError::io(&shown))?;
+    }
+    let text = toml::to_string(&values).map_err(|e| TableError::Parse(e.to_string()))?;
+    fs::write(path, text).map_err(TableError::io(&shown))
+}
+
+// File tempat sebuah key efektif berasal; None → default
+pub fn origin<'a>(layers: &'a [ConfigLayer], key: &str) -> Option<&'a Path> {
+    layers
+        .iter()
+        .rev()
+        .find(|layer| layer.values.contains_key(key))
+        .map(|layer| layer.path.as_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::handlers::test_dir;
+
+    fn layer(path: &str, text: &str, global: bool) -> ConfigLayer {
+        ConfigLayer {
+            path: PathBuf::from(path),
+            values: text.parse().unwrap(),
+            global,
+        }
+    }
+
+    #[test]
+    fn file_proyek_menimpa_global_dan_sisanya_default() {
+        let layers = [
+            layer("/global.toml", "border = \"ascii\"\npage_size = 5", true),
+            layer("/proyek/.mytabel.toml", "page_size = 50", false),
+        ];
+        let config = Config::from_layers(&layers).unwrap();
+        assert_eq!(config.border, Border::Ascii);
+        assert_eq!(config.page_size, 50);
+        assert_eq!(config.empty_display, "-");
+        assert_eq!(config.header_color, HeaderColor::Yellow);
+
+        assert_eq!(
+            origin(&layers, "page_size"),
+            Some(Path::new("/proyek/.mytabel.toml"))
+        );
+        assert_eq!(origin(&layers, "border"), Some(Path::new("/global.toml")));
+        assert_eq!(origin(&layers, "lang"), None);
+        assert_eq!(
+            Config::from_layers(&[]).unwrap().storage_path,
+            DEFAULT_STORAGE_PATH
+        );
+    }
+
+    #[test]
+    fn on_save_hanya_dari_config_global() {
+        let command = "on_save = \"git commit -am simpan\"";
+        let config = Config::from_layers(&[layer("/global.toml", command, true)]).unwrap();
+        assert_eq!(config.on_save, "git commit -am simpan");
+        assert!(matches!(
+            Config::from_layers(&[
+                layer("/global.toml", "", true),
+                layer("/repo/.mytabel.toml", command, false),
+            ]),
+            Err(TableError::InvalidValue(_))
+        ));
+        let timeout = layer("/repo/.mytabel.toml", "on_save_timeout = 3", false);
+        assert_eq!(Config::from_layers(&[timeout]).unwrap().on_save_timeout, 3);
+    }
+
+    #[test]
+    fn nilai_salah_ditolak() {
+        for text in [
+            "warna = \"merah\"",
+            "border = \"tebal\"",
+            "page_size = 0",
+            "lang = \"fr\"",
+            "audit = \"ya\"",
+        ] {
+            assert!(
+                matches!(
+                    Config::from_layers(&[layer("/c.toml", text, true)]),
+                    Err(TableError::InvalidValue(_))
+                ),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn file_rusak_dilaporkan_sebagai_parse() {
+        let path = test_dir("config-parse").join("config.toml");
+        fs::write(&path, "border = ").unwrap();
+        assert!(matches!(read_table(&path), Err(TableError::Parse(_))));
+        fs::write(&path, "border = \"clean\"").unwrap();
+        assert_eq!(read_table(&path).unwrap()["border"].as_str(), Some("clean"));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::table::handlers::crypto::PASSPHRASE_ENV;
+use crate::tr;
+use std::{fmt, io};
+
+// Semua error dari operasi tabel; frontend bisa match per jenis
+#[derive(Debug)]
+pub enum TableError {
+    ColumnNotFound(String),
+    ColumnExists(String),
+    TableNotFound(String),
+    TableExists(String),
+    NoPrimary,
+    DuplicatePrimary,
+    EmptyPrimary,
+    PrimaryRemoval(String),   // kolom primary tidak boleh dihapus
+    PrimaryReadOnly(String),  // primary hanya bisa diubah lewat set_value_where
+    ComputedReadOnly(String), // kolom hitung diisi otomatis dari ekspresinya
+    ColumnReferenced { column: String, by: String }, // kolom dipakai kolom hitung
+    ViewNotFound(String),
+    ViewInvalid { view: String, reason: String }, // mis. kolomnya sudah dihapus
+    TriggerExists(String),
+    TriggerNotFound(String),
+    TriggerRejected { trigger: String, message: String }, // dibatalkan before-trigger
+    ColumnInTrigger { column: String, trigger: String },  // kolom dipakai trigger
+    SnapshotExists(String),
+    SnapshotNotFound(String),
+    MergeConflicts(usize), // jumlah konflik yang belum diselesaikan
+    TypeMismatch(String),  // nama kolom
+    InvalidValue(String),  // input tidak sesuai tipe kolom
+    TooManyValues { given: usize, columns: usize },
+    NoMatchingRow,
+    NoColumns,
+    InvalidOption(String),       // format/dialect/opsi tidak dikenal
+    InvalidRecords(Vec<String>), // import: daftar record yang gagal
+    PassphraseRequired,
+    WrongPassphrase,
+    Crypto(String),
+    Io { path: String, source: io::Error },
+    Parse(String),
+    Sqlite(rusqlite::Error),
+}
+
+impl TableError {
+    // Bantu map_err untuk operasi file
+    pub fn io(path: &str) -> impl FnOnce(io::Error) -> TableError + '_ {
+        move |source| TableError::Io {
+            path: path.to_string(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            TableError::ColumnNotFound(c) => tr!("err.column_not_found", c),
+            TableError::ColumnExists(c) => tr!("err.column_exists", c),
+            TableError::TableNotFound(t) => tr!("err.table_not_found", t),
+            TableError::TableExists(t) => tr!("err.table_exists", t),
+            TableError::NoPrimary => tr!("err.no_primary"),
+            TableError::DuplicatePrimary => tr!("err.duplicate_primary"),
+            TableError::EmptyPrimary => tr!("err.empty_primary"),
+            TableError::PrimaryRemoval(c) => tr!("err.primary_removal", c),
+            TableError::PrimaryReadOnly(c) => tr!("err.primary_read_only", c),
+            TableError::ComputedReadOnly(c) => tr!("err.computed_read_only", c),
+            TableError::ColumnReferenced { column, by } => {
+                tr!("err.column_referenced", column, by)
+            }
+            TableError::ViewNotFound(v) => tr!("err.view_not_found", v),
+            TableError::ViewInvalid { view, reason } => tr!("err.view_invalid", view, reason),
+            TableError::TriggerExists(t) => tr!("err.trigger_exists", t),
+            TableError::TriggerNotFound(t) => tr!("err.trigger_not_found", t),
+            TableError::TriggerRejected { trigger, message } => {
+                tr!("err.trigger_rejected", trigger, message)
+            }
+            TableError::ColumnInTrigger { column, trigger } => {
+                tr!("err.column_in_trigger", column, trigger)
+            }
+            TableError::SnapshotExists(s) => tr!("err.snapshot_exists", s),
+            TableError::SnapshotNotFound(s) => tr!("err.snapshot_not_found", s),
+            TableError::MergeConflicts(n) => tr!("err.merge_conflicts", n),
+            TableError::TypeMismatch(c) => tr!("err.type_mismatch", c),
+            TableError::InvalidValue(msg) => msg.clone(),
+            TableError::TooManyValues { given, columns } => {
+                tr!("err.too_many_values", given, columns)
+            }
+            TableError::NoMatchingRow => tr!("err.no_matching_row"),
+            TableError::NoColumns => tr!("err.no_columns"),
+            TableError::InvalidOption(msg) => msg.clone(),
+            TableError::InvalidRecords(errors) => {
+                format!("{}\n{}", tr!("err.invalid_records"), errors.join("\n"))
+            }
+            TableError::PassphraseRequired => tr!("err.passphrase_required", PASSPHRASE_ENV),
+            TableError::WrongPassphrase => tr!("err.wrong_passphrase"),
+            TableError::Crypto(msg) => msg.clone(),
+            TableError::Io { path, source } => tr!("err.io", path, source),
+            TableError::Parse(msg) => tr!("err.parse", msg),
+            TableError::Sqlite(e) => format!("SQLite: {}", e),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for TableError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TableError::Io { source, .. } => Some(source),
+            TableError::Sqlite(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for TableError {
+    fn from(e: rusqlite::Error) -> Self {
+        TableError::Sqlite(e)
+    }
+}
+
+impl From<serde_json::Error> for TableError {
+    fn from(e: serde_json::Error) -> Self {
+        TableError::Parse(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ColumnType, DataTable, Value};
+    use std::error::Error;
+
+    #[test]
+    fn api_mengembalikan_jenis_error_yang_bisa_di_match() {
+        let mut table = DataTable::default();
+        table.add_column(vec![("id", ColumnType::Numb)]).unwrap();
+        assert!(matches!(
+            table.add_row(vec![Value::Numb(1)]),
+            Err(TableError::NoPrimary)
+        ));
+        table.set_primary("id").unwrap();
+        assert!(matches!(
+            table.add_row(vec![Value::Numb(1), Value::Numb(2)]),
+            Err(TableError::TooManyValues {
+                given: 2,
+                columns: 1
+            })
+        ));
+        assert!(matches!(
+            table.add_row(vec![Value::Text("a".into())]),
+            Err(TableError::TypeMismatch(c)) if c == "id"
+        ));
+        assert!(matches!(
+            table.set_primary("tidak_ada"),
+            Err(TableError::ColumnNotFound(c)) if c == "tidak_ada"
+        ));
+    }
+
+    #[test]
+    fn sumber_error_ikut_diteruskan() {
+        let err = TableError::io("/tidak/ada")(io::Error::from(io::ErrorKind::NotFound));
+        assert!(matches!(&err, TableError::Io { path, .. } if path == "/tidak/ada"));
+        assert!(err.source().is_some());
+
+        let parse: TableError = serde_json::from_str::<serde_json::Value>("{")
+            .unwrap_err()
+            .into();
+        assert!(matches!(parse, TableError::Parse(_)));
+        assert!(parse.source().is_none());
+        assert_eq!(TableError::InvalidValue("x".into()).to_string(), "x");
+    }
+}
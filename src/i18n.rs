@@ -0,0 +1,1129 @@
+use std::env;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// Bahasa pesan untuk pengguna
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    Id, // default
+    En,
+}
+
+impl Lang {
+    // Terima kode singkat ("en") maupun locale ("en_US.UTF-8")
+    pub fn parse(input: &str) -> Option<Self> {
+        let code = input
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match code.as_str() {
+            "id" | "in" => Some(Lang::Id),
+            "en" => Some(Lang::En),
+            _ => None,
+        }
+    }
+
+    // Urutan env sesuai POSIX: LC_ALL → LC_MESSAGES → LANG; tidak dikenal → Id
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok().filter(|v| !v.is_empty()))
+            .next()
+            .and_then(|v| Lang::parse(&v))
+            .unwrap_or(Lang::Id)
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::Id => "id",
+            Lang::En => "en",
+        }
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Lang::Id => ID,
+            Lang::En => EN,
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_lang(lang: Lang) {
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        _ => Lang::Id,
+    }
+}
+
+// Teks untuk `key` pada bahasa aktif; tidak ada → Indonesia → key itu sendiri
+pub fn text(key: &'static str) -> &'static str {
+    lookup(lang().catalog(), key)
+        .or_else(|| lookup(ID, key))
+        .unwrap_or(key)
+}
+
+fn lookup(catalog: &'static [(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    catalog.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+// Isi placeholder `{}` berurutan dengan `args`
+pub fn format(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let mut args = args.iter();
+    let mut parts = text(key).split("{}");
+    out.push_str(parts.next().unwrap_or_default());
+    for part in parts {
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+// tr!("key") / tr!("key", arg, ...) → String pada bahasa aktif
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::text($key).to_string()
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::format($key, &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+
+const ID: &[(&str, &str)] = &[
+    // --- Error tabel ---
+    ("err.column_not_found", "kolom `{}` tidak ditemukan"),
+    ("err.column_exists", "kolom `{}` sudah ada"),
+    ("err.table_not_found", "tabel `{}` tidak ditemukan"),
+    ("err.table_exists", "tabel `{}` sudah ada"),
+    ("err.no_primary", "primary belum ditentukan"),
+    ("err.duplicate_primary", "nilai primary duplikat"),
+    ("err.empty_primary", "primary tidak boleh Empty"),
+    (
+        "err.primary_removal",
+        "kolom `{}` adalah primary, tidak boleh dihapus",
+    ),
+    (
+        "err.primary_read_only",
+        "kolom primary `{}` hanya bisa diubah lewat set_value_where",
+    ),
+    ("err.type_mismatch", "tipe tidak cocok pada kolom `{}`"),
+    (
+        "err.computed_read_only",
+        "kolom `{}` adalah kolom hitung, nilainya tidak bisa diubah langsung",
+    ),
+    (
+        "err.column_referenced",
+        "kolom `{}` dipakai oleh kolom hitung `{}`",
+    ),
+    (
+        "err.too_many_values",
+        "nilai berlebih ({}), kolom hanya ({})",
+    ),
+    (
+        "err.no_matching_row",
+        "tidak ada baris yang cocok dengan kondisi",
+    ),
+    ("err.no_columns", "tabel belum punya kolom"),
+    ("err.invalid_records", "record tidak valid"),
+    (
+        "err.passphrase_required",
+        "file terenkripsi, passphrase dibutuhkan (set env `{}`)",
+    ),
+    ("err.wrong_passphrase", "passphrase salah atau file rusak"),
+    ("err.io", "gagal akses file `{}`: {}"),
+    ("err.parse", "gagal parse: {}"),
+    // --- Nilai & record ---
+    ("val.char_len", "Char harus 1 karakter"),
+    ("val.char_len_value", "Char harus 1 karakter: '{}'"),
+    ("val.numb", "Numb harus angka: '{}'"),
+    ("val.bool", "Bool harus true/false atau 1/0"),
+    (
+        "val.column_type",
+        "Tipe kolom `{}` tidak valid (Text, Numb, Bool, Char)",
+    ),
+    ("val.in_column", "kolom `{}`: {}"),
+    ("val.line", "baris {}: {}"),
+    ("val.row", "baris {}: {}"),
+    ("val.row_column", "baris {}, kolom `{}`: {}"),
+    ("val.record_object", "record harus berupa objek"),
+    ("val.record_struct", "record harus berupa struct/objek"),
+    ("val.json_i64", "angka `{}` bukan bilangan bulat i64"),
+    ("val.json_nested", "array/objek tidak didukung"),
+    ("val.sql_real", "nilai REAL `{}` tidak didukung"),
+    ("val.sql_blob", "nilai BLOB tidak didukung"),
+    ("val.sql_mismatch", "nilai `{}` tidak cocok dengan tipe {}"),
+    (
+        "val.struct_required",
+        "tipe harus berupa struct dengan field bernama",
+    ),
+    ("val.struct_empty", "struct tidak punya field"),
+    (
+        "val.field_unsupported",
+        "tipe tidak didukung (String, integer, bool, char, Option)",
+    ),
+    ("val.field_unknown", "field `{}`: tipe tidak dikenali"),
+    ("val.field", "field `{}`: {}"),
+    // --- Opsi ---
+    (
+        "opt.export_format",
+        "format `{}` tidak dikenali (table, md, html, text)",
+    ),
+    (
+        "opt.dialect",
+        "dialect `{}` tidak dikenali (postgres, mysql, sqlite)",
+    ),
+    ("opt.batch", "ukuran batch harus lebih dari 0"),
+    ("export.html_title", "Tabel"),
+    ("opt.batch_number", "--batch harus angka: '{}'"),
+    (
+        "opt.single_pk",
+        "tabel `{}` tidak punya PRIMARY KEY tunggal, tentukan kolom primary",
+    ),
+    (
+        "opt.output",
+        "Output `{}` tidak dikenali (json, csv, tsv, table)",
+    ),
+    ("opt.lang", "Bahasa `{}` tidak dikenali (id, en)"),
+    ("opt.needs_value", "Opsi `{}` membutuhkan nilai"),
+    ("opt.positive", "Opsi `{}` harus angka > 0: '{}'"),
+    ("opt.number", "Opsi `{}` harus angka: '{}'"),
+    ("opt.condition", "Kondisi `{}` harus berbentuk KOLOM=NILAI"),
+    // --- Enkripsi ---
+    ("crypto.empty", "passphrase tidak boleh kosong"),
+    ("crypto.derive", "gagal menurunkan kunci: {}"),
+    ("crypto.encrypt_failed", "gagal mengenkripsi data"),
+    ("crypto.bad_format", "format file terenkripsi tidak valid"),
+    ("crypto.already", "tabel sudah terenkripsi, gunakan rekey"),
+    ("crypto.not_encrypted", "tabel tidak terenkripsi"),
+    (
+        "crypto.not_encrypted_rekey",
+        "tabel tidak terenkripsi, gunakan encrypt",
+    ),
+    ("crypto.mismatch", "passphrase tidak sama"),
+    // --- CLI ---
+    (
+        "cli.usage_add_column",
+        "add_column: gunakan `add_column NAMA TIPE [NAMA TIPE ...]`",
+    ),
+    (
+        "cli.usage_set_value_where",
+        "set_value_where: gunakan `set_value_where KOLOM NILAI KOLOM_TARGET NILAI_BARU`",
+    ),
+    (
+        "cli.usage_remove_row",
+        "remove_row: gunakan `remove_row KOLOM NILAI`",
+    ),
+    (
+        "cli.usage_add_computed_column",
+        "add_computed_column: gunakan `add_computed_column NAMA \"EKSPRESI\"`",
+    ),
+    ("cli.missing_column", "{}: nama kolom belum diberikan"),
+    ("cli.missing_path", "{}: path file belum diberikan"),
+    ("cli.missing_option", "{}: opsi {} belum diberikan"),
+    (
+        "cli.not_empty",
+        "{}: tabel saat ini tidak kosong, gunakan --replace",
+    ),
+    ("cli.unknown_command", "Perintah '{}' tidak dikenali"),
+    ("cli.no_command", "Perintah belum diberikan"),
+    ("cli.unclosed_quote", "Tanda kutip belum ditutup"),
+    ("cli.exported_sqlite", "Tabel diexport ke `{}` sebagai `{}`"),
+    ("cli.imported_json", "{} baris berhasil diimport"),
+    ("cli.imported_sqlite", "{} baris diimport dari `{}`"),
+    ("cli.will_encrypt", "Tabel akan disimpan terenkripsi"),
+    ("cli.will_decrypt", "Tabel akan disimpan tanpa enkripsi"),
+    ("cli.rekeyed", "Passphrase berhasil diganti"),
+    ("main.load_failed", "Gagal memuat tabel: {}"),
+    ("main.save_failed", "Gagal menyimpan tabel: {}"),
+    ("main.config_failed", "Gagal membaca config: {}"),
+    // --- Config ---
+    (
+        "config.usage",
+        "config: gunakan `config list | config get KEY | config set KEY NILAI [--project]`",
+    ),
+    ("config.parse_failed", "gagal membaca config `{}`: {}"),
+    ("config.invalid", "config tidak valid: {}"),
+    ("config.unknown_key", "key config `{}` tidak dikenali"),
+    ("config.number", "config `{}` harus angka: '{}'"),
+    ("config.bool", "config `{}` harus true atau false: '{}'"),
+    (
+        "config.global_only",
+        "config `{}` hanya boleh di config global, bukan file proyek {}",
+    ),
+    (
+        "config.no_global",
+        "lokasi config global tidak diketahui, set MYTABEL_CONFIG atau HOME",
+    ),
+    ("config.saved", "`{}` disimpan di {}"),
+    // --- Serve ---
+    (
+        "serve.listening",
+        "API berjalan di http://{} (disimpan tiap {} detik, Ctrl-C untuk berhenti)",
+    ),
+    ("serve.bind_failed", "gagal membuka {}: {}"),
+    ("serve.stopped", "server berhenti: {}"),
+    ("serve.shutdown", "Server dihentikan, menyimpan tabel"),
+    ("serve.no_route", "route `{} {}` tidak ada"),
+    ("serve.method", "method {} tidak didukung untuk `{}`"),
+    (
+        "serve.query",
+        "parameter `{}` tidak dikenali (page, limit, where)",
+    ),
+    ("serve.body", "gagal membaca body: {}"),
+    (
+        "serve.content_type",
+        "request yang mengubah data harus memakai Content-Type: application/json",
+    ),
+    ("serve.origin", "request dari origin `{}` ditolak"),
+    ("serve.body_too_large", "body lebih dari {} byte"),
+    // --- Daemon ---
+    (
+        "daemon.listening",
+        "Daemon berjalan di {} (Ctrl-C atau `daemon stop` untuk berhenti)",
+    ),
+    ("daemon.running", "daemon sudah berjalan di {}"),
+    ("daemon.bind_failed", "gagal membuka socket {}: {}"),
+    ("daemon.not_running", "daemon tidak berjalan ({})"),
+    ("daemon.stopped", "Daemon dihentikan"),
+    ("daemon.bad_request", "request tidak valid: {}"),
+    ("daemon.capture_failed", "gagal menangkap output: {}"),
+    (
+        "daemon.crypto_running",
+        "daemon sedang berjalan di {}; hentikan dulu dengan `daemon stop` sebelum mengubah enkripsi",
+    ),
+    (
+        "daemon.usage",
+        "daemon: gunakan `daemon` atau `daemon stop`",
+    ),
+    // --- Script ---
+    ("script.line_error", "baris {}: Error: {}"),
+    (
+        "script.summary",
+        "Script selesai: {} perintah berhasil, {} error",
+    ),
+    ("script.dry_run", "Dry-run: tabel tidak disimpan"),
+    (
+        "script.stopped",
+        "Script dihentikan, perubahan tidak disimpan",
+    ),
+    ("script.trailing_backslash", "baris terakhir diakhiri `\\`"),
+    ("script.read_file", "SCRIPT: gagal baca file `{}`: {}"),
+    ("script.read_stdin", "SCRIPT: gagal baca stdin: {}"),
+    // --- Tampilan tabel ---
+    ("pager.status", "Halaman {}/{} · baris {}-{} dari {}"),
+    ("pager.hidden", " · {} kolom tersembunyi"),
+    (
+        "pager.help",
+        "[n] berikut [p] sebelum [>/<] geser kolom [w] bungkus teks [k] pin primary [j N] baris/halaman [0] kembali: ",
+    ),
+    // --- Ekspresi kolom hitung ---
+    ("expr.empty", "ekspresi kosong"),
+    (
+        "expr.unexpected_char",
+        "karakter `{}` tidak dikenali di ekspresi",
+    ),
+    ("expr.unclosed_string", "string di ekspresi belum ditutup"),
+    (
+        "expr.unclosed_name",
+        "nama kolom di ekspresi belum ditutup dengan `",
+    ),
+    ("expr.number", "angka `{}` di ekspresi terlalu besar"),
+    ("expr.unexpected_end", "ekspresi belum lengkap"),
+    (
+        "expr.too_deep",
+        "ekspresi terlalu dalam (maksimal {} tingkat kurung/operator)",
+    ),
+    ("expr.unexpected_token", "`{}` tidak terduga di ekspresi"),
+    (
+        "expr.unknown_function",
+        "fungsi `{}` tidak dikenal (yang ada: if)",
+    ),
+    (
+        "expr.if_args",
+        "if butuh 3 argumen: if(kondisi, nilai_benar, nilai_salah)",
+    ),
+    (
+        "expr.binary_type",
+        "operator `{}` tidak bisa dipakai untuk {} dan {}",
+    ),
+    (
+        "expr.unary_type",
+        "operator `{}` tidak bisa dipakai untuk {}",
+    ),
+    ("expr.condition", "kondisi if harus Bool, bukan {}"),
+    (
+        "expr.branches",
+        "kedua cabang if harus bertipe sama, bukan {} dan {}",
+    ),
+    // --- View ---
+    ("err.view_not_found", "view `{}` tidak ditemukan"),
+    ("err.view_invalid", "view `{}` tidak valid: {}"),
+    (
+        "view.filter_type",
+        "filter view harus bernilai Bool, bukan {}",
+    ),
+    (
+        "view.sort_key",
+        "urutan `{}` tidak valid (contoh: `nama` atau `-harga`)",
+    ),
+    (
+        "view.usage",
+        "view: gunakan `view list`, `view save NAMA [--columns A,B] [--filter EKSPRESI] [--sort A,-B] [--limit N]`, `view show NAMA` atau `view remove NAMA`",
+    ),
+    ("view.saved", "View `{}` disimpan"),
+    ("view.removed", "View `{}` dihapus"),
+    ("view.none", "Belum ada view"),
+    ("view.invalid", "tidak valid: {}"),
+    (
+        "view.invalidated",
+        "Peringatan: view `{}` tidak valid lagi: {}",
+    ),
+    // --- Trigger ---
+    ("err.trigger_exists", "trigger `{}` sudah ada"),
+    ("err.trigger_not_found", "trigger `{}` tidak ditemukan"),
+    ("err.trigger_rejected", "dibatalkan oleh trigger `{}`: {}"),
+    ("err.snapshot_exists", "snapshot `{}` sudah ada"),
+    ("err.snapshot_not_found", "snapshot `{}` tidak ditemukan"),
+    (
+        "err.merge_conflicts",
+        "{} konflik belum diselesaikan, tidak ada file yang ditulis (pakai --prefer ours|theirs atau --interactive)",
+    ),
+    (
+        "err.column_in_trigger",
+        "kolom `{}` dipakai oleh trigger `{}`",
+    ),
+    (
+        "trigger.timing",
+        "aksi `{}` hanya bisa dipakai di trigger before (touch/set: add_row & set_value_where)",
+    ),
+    (
+        "trigger.timing_name",
+        "waktu trigger `{}` tidak dikenal (pilihan: before, after)",
+    ),
+    (
+        "trigger.event_name",
+        "event trigger `{}` tidak dikenal (pilihan: add_row, set_value_where, remove_row)",
+    ),
+    (
+        "trigger.usage",
+        "trigger: gunakan `trigger list`, `trigger remove NAMA` atau `trigger add NAMA before|after add_row|set_value_where|remove_row AKSI` dengan AKSI `touch KOLOM`, `set KOLOM EKSPRESI`, `reject KONDISI [PESAN]` atau `log FILE`",
+    ),
+    ("trigger.none", "Belum ada trigger"),
+    ("trigger.added", "Trigger `{}` ditambahkan"),
+    ("trigger.removed", "Trigger `{}` dihapus"),
+    (
+        "trigger.log_encrypted",
+        "trigger log `{}` menulis baris tanpa enkripsi; tidak bisa dipakai di tabel terenkripsi",
+    ),
+    // --- on_save ---
+    ("on_save.failed", "on_save: perintah `{}` gagal ({})"),
+    (
+        "on_save.summary_failed",
+        "on_save: daftar perubahan tidak bisa dibuat: {}",
+    ),
+    (
+        "on_save.timeout",
+        "on_save: perintah `{}` dihentikan setelah {} detik",
+    ),
+    (
+        "on_save.spawn_failed",
+        "on_save: gagal menjalankan `{}`: {}",
+    ),
+    // --- Audit ---
+    (
+        "audit.write_failed",
+        "peringatan: gagal menulis log audit: {}",
+    ),
+    (
+        "audit.since",
+        "waktu `{}` tidak valid (contoh: 2024-05-01, \"2024-05-01 13:00\", 30m, 2h, 7d)",
+    ),
+    (
+        "audit.usage_history",
+        "history: gunakan `history NILAI_PRIMARY`",
+    ),
+    (
+        "audit.usage_log",
+        "log: gunakan `log [--since WAKTU] [--limit N]`",
+    ),
+    ("audit.none", "Belum ada perubahan tercatat"),
+    // --- Snapshot & diff ---
+    (
+        "snapshot.name",
+        "nama snapshot `{}` tidak valid (huruf, angka, `-`, `_`, `.`)",
+    ),
+    (
+        "snapshot.usage",
+        "snapshot: gunakan `snapshot list`, `snapshot save NAMA` atau `snapshot remove NAMA`",
+    ),
+    ("snapshot.none", "Belum ada snapshot"),
+    ("snapshot.saved", "Snapshot `{}` disimpan di {}"),
+    ("snapshot.removed", "Snapshot `{}` dihapus"),
+    (
+        "snapshot.rekey_failed",
+        "gagal menulis ulang snapshot dengan passphrase baru: {}",
+    ),
+    (
+        "diff.usage",
+        "diff: gunakan `diff LAMA [BARU]` (nama snapshot atau path file; tanpa BARU → tabel saat ini)",
+    ),
+    ("diff.none", "Tidak ada perbedaan"),
+    ("diff.schema", "skema: {}"),
+    ("diff.column", "kolom"),
+    ("diff.old", "lama"),
+    ("diff.new", "baru"),
+    (
+        "diff.summary",
+        "{} baris ditambah, {} dihapus, {} diubah; {} perubahan skema",
+    ),
+    // --- Merge ---
+    (
+        "merge.usage",
+        "merge: gunakan `merge BASE OURS THEIRS [--prefer ours|theirs] [--interactive] [--into FILE]`",
+    ),
+    (
+        "merge.prefer",
+        "pilihan --prefer `{}` tidak dikenal (pilihan: ours, theirs)",
+    ),
+    (
+        "merge.primary_differs",
+        "primary ours (`{}`) dan theirs (`{}`) berbeda, tabel tidak bisa di-merge",
+    ),
+    ("merge.cell", "{} `{}`: base {}, ours {}, theirs {}"),
+    (
+        "merge.row_deleted_ours",
+        "{}: ours menghapus baris, theirs mengubahnya",
+    ),
+    (
+        "merge.row_deleted_theirs",
+        "{}: theirs menghapus baris, ours mengubahnya",
+    ),
+    ("merge.column_type", "kolom `{}`: tipe ours {}, theirs {}"),
+    ("merge.type", "{} `{}`: nilai {} tidak cocok dengan tipe {}"),
+    ("merge.conflict", "konflik: {}"),
+    ("merge.resolved", "{} → {}"),
+    ("merge.written", "Hasil merge ({} baris) ditulis ke {}"),
+    ("prompt.merge_choice", "Pilih [o]urs / [t]heirs / [s]kip: "),
+    // --- Search ---
+    (
+        "search.usage",
+        "search: gunakan `search KATA... [--columns A,B] [--exact] [--limit N]`",
+    ),
+    ("search.none", "Tidak ada baris yang cocok"),
+    ("prompt.search", "Cari (kosong = kembali): "),
+    // --- Menu ---
+    ("menu.title", "\n=== Menu Tabel ==="),
+    ("menu.1", "1. Tambah Kolom"),
+    ("menu.2", "2. Tambah Row"),
+    ("menu.3", "3. Set Primary"),
+    ("menu.4", "4. Update Nilai"),
+    ("menu.5", "5. Hapus Kolom / Row"),
+    ("menu.6", "6. Tampilkan Tabel"),
+    ("menu.7", "7. Tampilkan Tipe Kolom"),
+    ("menu.8", "8. Enkripsi File"),
+    ("menu.9", "9. Mode Layar Penuh"),
+    ("menu.s", "s. Cari Data"),
+    ("menu.0", "0. Keluar"),
+    ("menu.choose", "Pilih menu: "),
+    ("menu.encrypted", "terenkripsi"),
+    ("menu.not_encrypted", "tidak terenkripsi"),
+    ("menu.status", "Status: {}"),
+    ("prompt.column_name", "Nama kolom: "),
+    (
+        "prompt.column_type",
+        "Tipe kolom (Text, Numb, Bool, Char) atau =EKSPRESI untuk kolom hitung: ",
+    ),
+    ("prompt.row_value", "Nilai untuk kolom '{}' (type {}): "),
+    ("prompt.primary_column", "Nama kolom untuk primary: "),
+    ("prompt.cond_column", "Kolom kondisi: "),
+    ("prompt.cond_value", "Nilai kondisi: "),
+    ("prompt.target_column", "Kolom target: "),
+    ("prompt.new_value", "Nilai baru: "),
+    ("prompt.remove_what", "Hapus kolom atau row? (kolom/row): "),
+    ("prompt.remove_value", "Nilai untuk hapus: "),
+    (
+        "prompt.crypto_action",
+        "Pilih aksi (encrypt/decrypt/rekey): ",
+    ),
+    ("prompt.new_passphrase", "Passphrase baru: "),
+    ("prompt.repeat_passphrase", "Ulangi passphrase: "),
+    ("prompt.table_passphrase", "Passphrase tabel: "),
+    ("msg.invalid_choice", "Pilihan tidak valid"),
+    ("msg.invalid_column_type", "Tipe kolom tidak valid!"),
+    ("msg.column_added", "Kolom berhasil ditambahkan."),
+    ("msg.column_removed", "Kolom dihapus"),
+    (
+        "msg.no_columns_yet",
+        "Belum ada kolom, tambahkan kolom dulu!",
+    ),
+    ("msg.retry", "{}. Masukkan ulang."),
+    ("msg.row_added", "Row berhasil ditambahkan."),
+    ("msg.row_removed", "Row dihapus"),
+    ("msg.primary_set", "Primary column berhasil diatur"),
+    ("msg.value_updated", "Berhasil mengubah nilai."),
+    ("msg.crypto_done", "Berhasil, berlaku saat tabel disimpan."),
+    // --- REPL ---
+    ("repl.open_failed", "REPL: gagal membuka terminal: {}"),
+    (
+        "repl.welcome",
+        "Mode REPL. Ketik `help` untuk daftar perintah, `exit` untuk keluar.",
+    ),
+    ("repl.commands", "Perintah: {}"),
+    ("repl.history_failed", "REPL: gagal menyimpan history: {}"),
+    ("repl.prompt", "tabel> "),
+    // --- TUI ---
+    ("tui.open_failed", "TUI: gagal membuka terminal: {}"),
+    ("tui.hint", "q keluar · ? bantuan"),
+    (
+        "tui.help",
+        "panah/hjkl gerak · Enter edit · a tambah · d hapus · t tipe kolom · q keluar",
+    ),
+    ("tui.position", "Baris {}/{} · {}"),
+    ("tui.edit_prompt", "Nilai baru untuk '{}': {}▏"),
+    ("tui.confirm_delete", "Hapus row ini? (y/n)"),
+    ("tui.delete_cancelled", "Hapus dibatalkan"),
+    ("tui.edit_cancelled", "Edit dibatalkan"),
+    ("tui.insert_cancelled", "Tambah row dibatalkan"),
+    ("tui.title_table", " Tabel "),
+    ("tui.title_types", " Tipe Kolom "),
+];
+
+const EN: &[(&str, &str)] = &[
+    // --- Table errors ---
+    ("err.column_not_found", "column `{}` not found"),
+    ("err.column_exists", "column `{}` already exists"),
+    ("err.table_not_found", "table `{}` not found"),
+    ("err.table_exists", "table `{}` already exists"),
+    ("err.no_primary", "primary column is not set"),
+    ("err.duplicate_primary", "duplicate primary value"),
+    ("err.empty_primary", "primary value must not be Empty"),
+    (
+        "err.primary_removal",
+        "column `{}` is the primary and cannot be removed",
+    ),
+    (
+        "err.primary_read_only",
+        "primary column `{}` can only be changed with set_value_where",
+    ),
+    ("err.type_mismatch", "type mismatch in column `{}`"),
+    (
+        "err.computed_read_only",
+        "column `{}` is computed, its value cannot be changed directly",
+    ),
+    (
+        "err.column_referenced",
+        "column `{}` is used by computed column `{}`",
+    ),
+    (
+        "err.too_many_values",
+        "too many values ({}), table has only ({}) columns",
+    ),
+    ("err.no_matching_row", "no row matches the condition"),
+    ("err.no_columns", "table has no columns yet"),
+    ("err.invalid_records", "invalid records"),
+    (
+        "err.passphrase_required",
+        "file is encrypted, a passphrase is required (set env `{}`)",
+    ),
+    ("err.wrong_passphrase", "wrong passphrase or corrupted file"),
+    ("err.io", "cannot access file `{}`: {}"),
+    ("err.parse", "parse error: {}"),
+    // --- Values & records ---
+    ("val.char_len", "Char must be exactly 1 character"),
+    (
+        "val.char_len_value",
+        "Char must be exactly 1 character: '{}'",
+    ),
+    ("val.numb", "Numb must be a number: '{}'"),
+    ("val.bool", "Bool must be true/false or 1/0"),
+    (
+        "val.column_type",
+        "invalid column type `{}` (Text, Numb, Bool, Char)",
+    ),
+    ("val.in_column", "column `{}`: {}"),
+    ("val.line", "line {}: {}"),
+    ("val.row", "row {}: {}"),
+    ("val.row_column", "row {}, column `{}`: {}"),
+    ("val.record_object", "record must be an object"),
+    ("val.record_struct", "record must be a struct/object"),
+    ("val.json_i64", "number `{}` is not an i64 integer"),
+    ("val.json_nested", "arrays/objects are not supported"),
+    ("val.sql_real", "REAL value `{}` is not supported"),
+    ("val.sql_blob", "BLOB values are not supported"),
+    ("val.sql_mismatch", "value `{}` does not match type {}"),
+    (
+        "val.struct_required",
+        "type must be a struct with named fields",
+    ),
+    ("val.struct_empty", "struct has no fields"),
+    (
+        "val.field_unsupported",
+        "unsupported type (String, integer, bool, char, Option)",
+    ),
+    ("val.field_unknown", "field `{}`: unknown type"),
+    ("val.field", "field `{}`: {}"),
+    // --- Options ---
+    (
+        "opt.export_format",
+        "unknown format `{}` (table, md, html, text)",
+    ),
+    (
+        "opt.dialect",
+        "unknown dialect `{}` (postgres, mysql, sqlite)",
+    ),
+    ("opt.batch", "batch size must be greater than 0"),
+    ("export.html_title", "Table"),
+    ("opt.batch_number", "--batch must be a number: '{}'"),
+    (
+        "opt.single_pk",
+        "table `{}` has no single PRIMARY KEY, specify the primary column",
+    ),
+    ("opt.output", "unknown output `{}` (json, csv, tsv, table)"),
+    ("opt.lang", "unknown language `{}` (id, en)"),
+    ("opt.needs_value", "option `{}` requires a value"),
+    ("opt.positive", "option `{}` must be a number > 0: '{}'"),
+    ("opt.number", "option `{}` must be a number: '{}'"),
+    (
+        "opt.condition",
+        "condition `{}` must look like COLUMN=VALUE",
+    ),
+    // --- Encryption ---
+    ("crypto.empty", "passphrase must not be empty"),
+    ("crypto.derive", "failed to derive key: {}"),
+    ("crypto.encrypt_failed", "failed to encrypt data"),
+    ("crypto.bad_format", "invalid encrypted file format"),
+    ("crypto.already", "table is already encrypted, use rekey"),
+    ("crypto.not_encrypted", "table is not encrypted"),
+    (
+        "crypto.not_encrypted_rekey",
+        "table is not encrypted, use encrypt",
+    ),
+    ("crypto.mismatch", "passphrases do not match"),
+    // --- CLI ---
+    (
+        "cli.usage_add_column",
+        "add_column: usage `add_column NAME TYPE [NAME TYPE ...]`",
+    ),
+    (
+        "cli.usage_set_value_where",
+        "set_value_where: usage `set_value_where COLUMN VALUE TARGET_COLUMN NEW_VALUE`",
+    ),
+    (
+        "cli.usage_remove_row",
+        "remove_row: usage `remove_row COLUMN VALUE`",
+    ),
+    (
+        "cli.usage_add_computed_column",
+        "add_computed_column: usage `add_computed_column NAME \"EXPRESSION\"`",
+    ),
+    ("cli.missing_column", "{}: column name not given"),
+    ("cli.missing_path", "{}: file path not given"),
+    ("cli.missing_option", "{}: option {} not given"),
+    (
+        "cli.not_empty",
+        "{}: current table is not empty, use --replace",
+    ),
+    ("cli.unknown_command", "unknown command '{}'"),
+    ("cli.no_command", "no command given"),
+    ("cli.unclosed_quote", "unclosed quote"),
+    ("cli.exported_sqlite", "Table exported to `{}` as `{}`"),
+    ("cli.imported_json", "{} rows imported"),
+    ("cli.imported_sqlite", "{} rows imported from `{}`"),
+    ("cli.will_encrypt", "Table will be saved encrypted"),
+    ("cli.will_decrypt", "Table will be saved without encryption"),
+    ("cli.rekeyed", "Passphrase changed"),
+    ("main.load_failed", "Failed to load table: {}"),
+    ("main.save_failed", "Failed to save table: {}"),
+    ("main.config_failed", "Failed to read config: {}"),
+    // --- Config ---
+    (
+        "config.usage",
+        "config: usage `config list | config get KEY | config set KEY VALUE [--project]`",
+    ),
+    ("config.parse_failed", "failed to read config `{}`: {}"),
+    ("config.invalid", "invalid config: {}"),
+    ("config.unknown_key", "unknown config key `{}`"),
+    ("config.number", "config `{}` must be a number: '{}'"),
+    ("config.bool", "config `{}` must be true or false: '{}'"),
+    (
+        "config.global_only",
+        "config `{}` is only allowed in the global config, not in project file {}",
+    ),
+    (
+        "config.no_global",
+        "global config location unknown, set MYTABEL_CONFIG or HOME",
+    ),
+    ("config.saved", "`{}` saved to {}"),
+    // --- Serve ---
+    (
+        "serve.listening",
+        "API listening on http://{} (saved every {} seconds, Ctrl-C to stop)",
+    ),
+    ("serve.bind_failed", "failed to bind {}: {}"),
+    ("serve.stopped", "server stopped: {}"),
+    ("serve.shutdown", "Server stopped, saving the table"),
+    ("serve.no_route", "no route for `{} {}`"),
+    ("serve.method", "method {} not allowed for `{}`"),
+    ("serve.query", "unknown parameter `{}` (page, limit, where)"),
+    ("serve.body", "failed to read body: {}"),
+    (
+        "serve.content_type",
+        "requests that change data must use Content-Type: application/json",
+    ),
+    ("serve.origin", "request from origin `{}` rejected"),
+    ("serve.body_too_large", "body larger than {} bytes"),
+    // --- Daemon ---
+    (
+        "daemon.listening",
+        "Daemon listening on {} (Ctrl-C or `daemon stop` to stop)",
+    ),
+    ("daemon.running", "daemon already running on {}"),
+    ("daemon.bind_failed", "failed to open socket {}: {}"),
+    ("daemon.not_running", "daemon is not running ({})"),
+    ("daemon.stopped", "Daemon stopped"),
+    ("daemon.bad_request", "invalid request: {}"),
+    ("daemon.capture_failed", "failed to capture output: {}"),
+    (
+        "daemon.crypto_running",
+        "daemon is running on {}; stop it with `daemon stop` before changing encryption",
+    ),
+    ("daemon.usage", "daemon: usage `daemon` or `daemon stop`"),
+    // --- Script ---
+    ("script.line_error", "line {}: Error: {}"),
+    (
+        "script.summary",
+        "Script finished: {} commands succeeded, {} errors",
+    ),
+    ("script.dry_run", "Dry-run: table not saved"),
+    ("script.stopped", "Script stopped, changes not saved"),
+    ("script.trailing_backslash", "last line ends with `\\`"),
+    ("script.read_file", "SCRIPT: cannot read file `{}`: {}"),
+    ("script.read_stdin", "SCRIPT: cannot read stdin: {}"),
+    // --- Table view ---
+    ("pager.status", "Page {}/{} · rows {}-{} of {}"),
+    ("pager.hidden", " · {} columns hidden"),
+    (
+        "pager.help",
+        "[n] next [p] previous [>/<] scroll columns [w] wrap text [k] pin primary [j N] rows/page [0] back: ",
+    ),
+    // --- Computed column expressions ---
+    ("expr.empty", "empty expression"),
+    (
+        "expr.unexpected_char",
+        "unknown character `{}` in expression",
+    ),
+    ("expr.unclosed_string", "unclosed string in expression"),
+    (
+        "expr.unclosed_name",
+        "column name in expression is missing a closing `",
+    ),
+    ("expr.number", "number `{}` in expression is too large"),
+    ("expr.unexpected_end", "incomplete expression"),
+    (
+        "expr.too_deep",
+        "expression is nested too deeply (at most {} levels of parentheses/operators)",
+    ),
+    ("expr.unexpected_token", "unexpected `{}` in expression"),
+    (
+        "expr.unknown_function",
+        "unknown function `{}` (available: if)",
+    ),
+    (
+        "expr.if_args",
+        "if takes 3 arguments: if(condition, when_true, when_false)",
+    ),
+    (
+        "expr.binary_type",
+        "operator `{}` cannot be used with {} and {}",
+    ),
+    ("expr.unary_type", "operator `{}` cannot be used with {}"),
+    ("expr.condition", "if condition must be Bool, not {}"),
+    (
+        "expr.branches",
+        "both if branches must have the same type, not {} and {}",
+    ),
+    // --- Views ---
+    ("err.view_not_found", "view `{}` not found"),
+    ("err.view_invalid", "view `{}` is invalid: {}"),
+    ("view.filter_type", "view filter must be Bool, not {}"),
+    (
+        "view.sort_key",
+        "invalid sort key `{}` (e.g. `name` or `-price`)",
+    ),
+    (
+        "view.usage",
+        "view: usage `view list`, `view save NAME [--columns A,B] [--filter EXPRESSION] [--sort A,-B] [--limit N]`, `view show NAME` or `view remove NAME`",
+    ),
+    ("view.saved", "View `{}` saved"),
+    ("view.removed", "View `{}` removed"),
+    ("view.none", "No views yet"),
+    ("view.invalid", "invalid: {}"),
+    (
+        "view.invalidated",
+        "Warning: view `{}` is no longer valid: {}",
+    ),
+    // --- Triggers ---
+    ("err.trigger_exists", "trigger `{}` already exists"),
+    ("err.trigger_not_found", "trigger `{}` not found"),
+    ("err.trigger_rejected", "rejected by trigger `{}`: {}"),
+    ("err.snapshot_exists", "snapshot `{}` already exists"),
+    ("err.snapshot_not_found", "snapshot `{}` not found"),
+    (
+        "err.merge_conflicts",
+        "{} unresolved conflicts, nothing was written (use --prefer ours|theirs or --interactive)",
+    ),
+    (
+        "err.column_in_trigger",
+        "column `{}` is used by trigger `{}`",
+    ),
+    (
+        "trigger.timing",
+        "action `{}` can only be used in before triggers (touch/set: add_row & set_value_where)",
+    ),
+    (
+        "trigger.timing_name",
+        "unknown trigger timing `{}` (choices: before, after)",
+    ),
+    (
+        "trigger.event_name",
+        "unknown trigger event `{}` (choices: add_row, set_value_where, remove_row)",
+    ),
+    (
+        "trigger.usage",
+        "trigger: usage `trigger list`, `trigger remove NAME` or `trigger add NAME before|after add_row|set_value_where|remove_row ACTION` where ACTION is `touch COLUMN`, `set COLUMN EXPRESSION`, `reject CONDITION [MESSAGE]` or `log FILE`",
+    ),
+    ("trigger.none", "No triggers yet"),
+    ("trigger.added", "Trigger `{}` added"),
+    ("trigger.removed", "Trigger `{}` removed"),
+    (
+        "trigger.log_encrypted",
+        "log trigger `{}` writes rows unencrypted; it cannot be used on an encrypted table",
+    ),
+    // --- on_save ---
+    ("on_save.failed", "on_save: command `{}` failed ({})"),
+    (
+        "on_save.summary_failed",
+        "on_save: could not list the changes: {}",
+    ),
+    (
+        "on_save.timeout",
+        "on_save: command `{}` was stopped after {} seconds",
+    ),
+    ("on_save.spawn_failed", "on_save: could not run `{}`: {}"),
+    // --- Audit ---
+    (
+        "audit.write_failed",
+        "warning: failed to write audit log: {}",
+    ),
+    (
+        "audit.since",
+        "invalid time `{}` (examples: 2024-05-01, \"2024-05-01 13:00\", 30m, 2h, 7d)",
+    ),
+    (
+        "audit.usage_history",
+        "history: usage `history PRIMARY_VALUE`",
+    ),
+    (
+        "audit.usage_log",
+        "log: usage `log [--since TIME] [--limit N]`",
+    ),
+    ("audit.none", "No recorded changes yet"),
+    // --- Snapshot & diff ---
+    (
+        "snapshot.name",
+        "invalid snapshot name `{}` (letters, digits, `-`, `_`, `.`)",
+    ),
+    (
+        "snapshot.usage",
+        "snapshot: usage `snapshot list`, `snapshot save NAME` or `snapshot remove NAME`",
+    ),
+    ("snapshot.none", "No snapshots yet"),
+    ("snapshot.saved", "Snapshot `{}` saved to {}"),
+    ("snapshot.removed", "Snapshot `{}` removed"),
+    (
+        "snapshot.rekey_failed",
+        "failed to rewrite snapshots with the new passphrase: {}",
+    ),
+    (
+        "diff.usage",
+        "diff: usage `diff OLD [NEW]` (snapshot name or file path; without NEW → current table)",
+    ),
+    ("diff.none", "No differences"),
+    ("diff.schema", "schema: {}"),
+    ("diff.column", "column"),
+    ("diff.old", "old"),
+    ("diff.new", "new"),
+    (
+        "diff.summary",
+        "{} rows added, {} removed, {} modified; {} schema changes",
+    ),
+    // --- Merge ---
+    (
+        "merge.usage",
+        "merge: usage `merge BASE OURS THEIRS [--prefer ours|theirs] [--interactive] [--into FILE]`",
+    ),
+    (
+        "merge.prefer",
+        "unknown --prefer choice `{}` (choices: ours, theirs)",
+    ),
+    (
+        "merge.primary_differs",
+        "ours primary (`{}`) differs from theirs (`{}`), tables cannot be merged",
+    ),
+    ("merge.cell", "{} `{}`: base {}, ours {}, theirs {}"),
+    (
+        "merge.row_deleted_ours",
+        "{}: ours deleted the row, theirs modified it",
+    ),
+    (
+        "merge.row_deleted_theirs",
+        "{}: theirs deleted the row, ours modified it",
+    ),
+    ("merge.column_type", "column `{}`: ours type {}, theirs {}"),
+    ("merge.type", "{} `{}`: value {} does not match type {}"),
+    ("merge.conflict", "conflict: {}"),
+    ("merge.resolved", "{} → {}"),
+    ("merge.written", "Merge result ({} rows) written to {}"),
+    ("prompt.merge_choice", "Choose [o]urs / [t]heirs / [s]kip: "),
+    // --- Search ---
+    (
+        "search.usage",
+        "search: usage `search WORD... [--columns A,B] [--exact] [--limit N]`",
+    ),
+    ("search.none", "No matching rows"),
+    ("prompt.search", "Search (empty = back): "),
+    // --- Menu ---
+    ("menu.title", "\n=== Table Menu ==="),
+    ("menu.1", "1. Add Column"),
+    ("menu.2", "2. Add Row"),
+    ("menu.3", "3. Set Primary"),
+    ("menu.4", "4. Update Value"),
+    ("menu.5", "5. Remove Column / Row"),
+    ("menu.6", "6. Show Table"),
+    ("menu.7", "7. Show Column Types"),
+    ("menu.8", "8. File Encryption"),
+    ("menu.9", "9. Full-Screen Mode"),
+    ("menu.s", "s. Search"),
+    ("menu.0", "0. Exit"),
+    ("menu.choose", "Choose: "),
+    ("menu.encrypted", "encrypted"),
+    ("menu.not_encrypted", "not encrypted"),
+    ("menu.status", "Status: {}"),
+    ("prompt.column_name", "Column name: "),
+    (
+        "prompt.column_type",
+        "Column type (Text, Numb, Bool, Char) or =EXPRESSION for a computed column: ",
+    ),
+    ("prompt.row_value", "Value for column '{}' (type {}): "),
+    ("prompt.primary_column", "Primary column name: "),
+    ("prompt.cond_column", "Condition column: "),
+    ("prompt.cond_value", "Condition value: "),
+    ("prompt.target_column", "Target column: "),
+    ("prompt.new_value", "New value: "),
+    ("prompt.remove_what", "Remove column or row? (column/row): "),
+    ("prompt.remove_value", "Value to remove: "),
+    (
+        "prompt.crypto_action",
+        "Choose action (encrypt/decrypt/rekey): ",
+    ),
+    ("prompt.new_passphrase", "New passphrase: "),
+    ("prompt.repeat_passphrase", "Repeat passphrase: "),
+    ("prompt.table_passphrase", "Table passphrase: "),
+    ("msg.invalid_choice", "Invalid choice"),
+    ("msg.invalid_column_type", "Invalid column type!"),
+    ("msg.column_added", "Column added."),
+    ("msg.column_removed", "Column removed"),
+    ("msg.no_columns_yet", "No columns yet, add a column first!"),
+    ("msg.retry", "{}. Please try again."),
+    ("msg.row_added", "Row added."),
+    ("msg.row_removed", "Row removed"),
+    ("msg.primary_set", "Primary column set"),
+    ("msg.value_updated", "Value updated."),
+    (
+        "msg.crypto_done",
+        "Done, takes effect when the table is saved.",
+    ),
+    // --- REPL ---
+    ("repl.open_failed", "REPL: cannot open terminal: {}"),
+    (
+        "repl.welcome",
+        "REPL mode. Type `help` for the command list, `exit` to quit.",
+    ),
+    ("repl.commands", "Commands: {}"),
+    ("repl.history_failed", "REPL: cannot save history: {}"),
+    ("repl.prompt", "table> "),
+    // --- TUI ---
+    ("tui.open_failed", "TUI: cannot open terminal: {}"),
+    ("tui.hint", "q quit · ? help"),
+    (
+        "tui.help",
+        "arrows/hjkl move · Enter edit · a add · d delete · t column types · q quit",
+    ),
+    ("tui.position", "Row {}/{} · {}"),
+    ("tui.edit_prompt", "New value for '{}': {}▏"),
+    ("tui.confirm_delete", "Delete this row? (y/n)"),
+    ("tui.delete_cancelled", "Delete cancelled"),
+    ("tui.edit_cancelled", "Edit cancelled"),
+    ("tui.insert_cancelled", "Add row cancelled"),
+    ("tui.title_table", " Table "),
+    ("tui.title_types", " Column Types "),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn keys(catalog: &[(&'static str, &'static str)]) -> HashSet<&'static str> {
+        catalog.iter().map(|(k, _)| *k).collect::<HashSet<_>>()
+    }
+
+    #[test]
+    fn every_key_exists_in_every_locale() {
+        let locales = [Lang::Id, Lang::En];
+        for lang in locales {
+            let catalog = lang.catalog();
+            assert_eq!(
+                keys(catalog).len(),
+                catalog.len(),
+                "key duplikat di locale `{}`",
+                lang.code()
+            );
+            for other in locales {
+                let missing: Vec<_> = keys(other.catalog())
+                    .difference(&keys(catalog))
+                    .copied()
+                    .collect();
+                assert!(
+                    missing.is_empty(),
+                    "locale `{}` tidak punya key: {:?}",
+                    lang.code(),
+                    missing
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn placeholders_match_across_locales() {
+        for (key, id_text) in ID {
+            let en_text = lookup(EN, key).unwrap_or_default();
+            assert_eq!(
+                id_text.matches("{}").count(),
+                en_text.matches("{}").count(),
+                "jumlah placeholder beda untuk `{}`",
+                key
+            );
+        }
+    }
+}
@@ -0,0 +1,13 @@
+pub mod config;
+pub mod error;
+pub mod i18n;
+pub mod master;
+pub mod table;
+
+pub use error::TableError;
+pub use master::DataBase;
+pub use table::handlers::row::{Row, RowMut};
+pub use table::handlers::service::DataTable;
+pub use table::models::{ColumnTable, RowTable};
+pub use table::value::{ColumnType, Value};
+pub use table::{handlers, models, value};
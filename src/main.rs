@@ -1,42 +1,283 @@
 mod command;
-// mod master;
 mod menu;
-mod table;
 
+use command::daemon;
+use command::on_save;
+use command::output::{CliError, OutputFormat};
+use command::script::{self, ScriptOptions};
+use command::serve::{self, ServeOptions};
 use std::env;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::process;
+use table::TableError;
+use table::config::{self, Config};
+use table::handlers::crypto::{self, PASSPHRASE_ENV};
 use table::handlers::service::DataTable;
+use table::handlers::{audit, snapshot};
+use table::i18n::{self, Lang};
+use table::tr;
 
-// Lokasi file penyimpanan tabel
-const TABLE_FILE: &str = "/data/data/com.termux/files/home/.mytabel.json";
+// Load tabel dari lokasi di config; file belum ada → tabel baru, file ada tapi gagal → error
+fn load_table() -> Result<DataTable, TableError> {
+    let path = config::current().storage_path();
+    if !Path::new(&path).exists() {
+        return Ok(DataTable::new());
+    }
+
+    // Passphrase tidak ada di env → minta lewat terminal
+    if crypto::passphrase_from_env(PASSPHRASE_ENV).is_none()
+        && DataTable::is_encrypted_file(&path)
+        && io::stdin().is_terminal()
+    {
+        let passphrase = menu::interactive::read_passphrase(&tr!("prompt.table_passphrase"));
+        return DataTable::load_with_passphrase(&path, Some(&passphrase));
+    }
+
+    DataTable::load(&path)
+}
+
+// Simpan tabel ke lokasi di config, dengan backup sesuai `backup_count`,
+// lalu tulis catatan audit & jalankan perintah `on_save` jika ada yang berubah
+fn save_table(table: &DataTable) -> Result<(), TableError> {
+    let settings = config::current();
+    let path = settings.storage_path();
+    let before = on_save::previous(&path, table);
+    table.save_with_backups(&path, settings.backup_count)?;
+    // Tabel sudah tersimpan: gagal tulis audit cukup dilaporkan
+    if let Err(e) = table.flush_audit(&audit::audit_path(&path)) {
+        eprintln!("{}", tr!("audit.write_failed", e));
+    }
+    if let Err(e) = table.rekey_snapshots(&snapshot::snapshot_dir(&path)) {
+        eprintln!("{}", tr!("snapshot.rekey_failed", e));
+    }
+    if let Some(before) = before {
+        on_save::run(&path, &before, table);
+    }
+    Ok(())
+}
+
+// Bahasa pesan: --lang, lalu `lang` di config, lalu LC_ALL / LC_MESSAGES / LANG, default Indonesia
+fn select_lang(args: &mut Vec<String>) {
+    i18n::set_lang(config::current().lang().unwrap_or_else(Lang::from_env));
+    let result = command::cli::take_option(args, "--lang").and_then(|code| match code {
+        Some(code) => Lang::parse(&code)
+            .map(i18n::set_lang)
+            .ok_or_else(|| tr!("opt.lang", code)),
+        None => Ok(()),
+    });
+    if let Err(e) = result {
+        let err = CliError::from(e);
+        eprintln!("{}", err.render(OutputFormat::Table));
+        process::exit(err.status);
+    }
+}
+
+// Jalankan file script: --script [FILE|-] [--stop-on-error] [--dry-run]
+fn run_script_mode(table: &mut DataTable, mut args: Vec<String>) {
+    let opts = ScriptOptions {
+        stop_on_error: command::cli::take_flag(&mut args, "--stop-on-error"),
+        dry_run: command::cli::take_flag(&mut args, "--dry-run"),
+    };
+    let source = match script::read_script(args.get(1).map(String::as_str)) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let report = script::run_script(table, &source, &opts);
+    for (line, e) in &report.errors {
+        eprintln!("{}", tr!("script.line_error", line, e));
+    }
+    println!(
+        "{}",
+        tr!("script.summary", report.executed, report.errors.len())
+    );
+
+    if opts.dry_run {
+        println!("{}", tr!("script.dry_run"));
+    } else if report.stopped {
+        eprintln!("{}", tr!("script.stopped"));
+    } else if let Err(e) = save_table(table) {
+        eprintln!("{}", tr!("main.save_failed", e));
+    }
+
+    if !report.errors.is_empty() {
+        process::exit(1);
+    }
+}
+
+// Jalankan API HTTP lokal: serve [--port N] [--save-interval DETIK]
+fn run_serve_mode(table: &mut DataTable, mut args: Vec<String>) {
+    let opts = match ServeOptions::from_args(&mut args) {
+        Ok(opts) => opts,
+        Err(e) => {
+            let err = CliError::from(e);
+            eprintln!("{}", err.render(OutputFormat::Table));
+            process::exit(err.status);
+        }
+    };
+
+    let result = serve::run_server(table, &opts, save_table);
+    if let Err(e) = save_table(table) {
+        eprintln!("{}", tr!("main.save_failed", e));
+    }
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+// Jalankan daemon yang melayani perintah CLI lewat Unix socket
+fn run_daemon_mode(table: &mut DataTable, args: Vec<String>) {
+    if args.len() > 1 {
+        let err = CliError::from(tr!("daemon.usage"));
+        eprintln!("{}", err.render(OutputFormat::Table));
+        process::exit(err.status);
+    }
+
+    let result = daemon::run_daemon(table, save_table);
+    if let Err(e) = save_table(table) {
+        eprintln!("{}", tr!("main.save_failed", e));
+    }
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+// Ambil --output; nilai tidak dikenal → error USAGE
+fn parse_output(args: &mut Vec<String>) -> OutputFormat {
+    match command::cli::take_option(args, "--output")
+        .and_then(|o| o.map(|o| OutputFormat::parse(&o)).transpose())
+    {
+        Ok(output) => output.unwrap_or(OutputFormat::Table),
+        Err(e) => {
+            let err = CliError::from(e);
+            eprintln!("{}", err.render(OutputFormat::Table));
+            process::exit(err.status);
+        }
+    }
+}
+
+// Jalankan satu perintah; error → stderr terstruktur + exit status sesuai jenis
+fn run_cli_mode(table: &mut DataTable, mut args: Vec<String>) {
+    let output = parse_output(&mut args);
+
+    let result = if args.is_empty() {
+        Err(CliError::from(tr!("cli.no_command")))
+    } else {
+        command::cli::run_command(table, args, output)
+    };
+
+    // Simpan tabel setelah menjalankan command
+    if let Err(e) = save_table(table) {
+        let mut err = CliError::from(e);
+        err.message = tr!("main.save_failed", err.message);
+        eprintln!("{}", err.render(output));
+        process::exit(err.status);
+    }
+
+    if let Err(err) = result {
+        eprintln!("{}", err.render(output));
+        process::exit(err.status);
+    }
+}
 
 fn main() {
+    // Config dibaca paling awal: bahasa, lokasi tabel & tampilan ikut config
+    match Config::load() {
+        Ok(settings) => config::install(settings),
+        Err(e) => {
+            i18n::set_lang(Lang::from_env());
+            eprintln!("{}", tr!("main.config_failed", e));
+            process::exit(1);
+        }
+    }
+
+    // Ambil argumen; --lang dipilih sebelum pesan lain agar semua pesan ikut
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    select_lang(&mut args);
+
+    // config tidak butuh tabel (tetap jalan walau file tabel rusak/terenkripsi)
+    if args.first().is_some_and(|a| a == "config") {
+        let output = parse_output(&mut args);
+        if let Err(err) = command::cli::run_config(args, output) {
+            eprintln!("{}", err.render(output));
+            process::exit(err.status);
+        }
+        return;
+    }
+
+    // merge bekerja pada file yang diberikan, bukan tabel di config
+    if args.first().is_some_and(|a| a == "merge") {
+        let output = parse_output(&mut args);
+        if let Err(err) = command::merge::run_merge(args, output) {
+            eprintln!("{}", err.render(output));
+            process::exit(err.status);
+        }
+        return;
+    }
+
+    // Daemon aktif → perintah dijalankan di daemon, file tabel tidak perlu dibaca
+    if let Some(status) = daemon::forward(&args) {
+        process::exit(status);
+    }
+    if args.len() == 2 && args[0] == "daemon" && args[1] == "stop" {
+        match daemon::stop() {
+            Ok(message) => print!("{}", message),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
     // --- Load tabel jika file ada ---
-    let mut table = match DataTable::load(TABLE_FILE) {
+    let mut table = match load_table() {
         Ok(t) => t,
-        Err(_) => DataTable::new(), // jika gagal load, buat tabel baru
+        Err(e) => {
+            // Jangan lanjut dengan tabel kosong: save akan menimpa file
+            eprintln!("{}", tr!("main.load_failed", e));
+            process::exit(1);
+        }
     };
-
-    // Ambil argumen
-    let args: Vec<String> = env::args().skip(1).collect();
+    if config::current().audit {
+        table.enable_audit();
+    }
 
     if args.is_empty() {
         // --- Mode Menu Interaktif ---
         menu::interactive::run_menu(&mut table);
 
         // Simpan otomatis setelah keluar dari menu
-        if let Err(e) = table.save(TABLE_FILE) {
-            eprintln!("Gagal menyimpan tabel: {}", e);
+        if let Err(e) = save_table(&table) {
+            eprintln!("{}", tr!("main.save_failed", e));
         }
-    } else {
-        // --- Mode Command-Line ---
-        if let Err(e) = command::cli::run_command(&mut table, args) {
+    } else if args[0] == "--script" {
+        // --- Mode Script ---
+        run_script_mode(&mut table, args);
+    } else if args[0] == "serve" {
+        // --- Mode API HTTP ---
+        run_serve_mode(&mut table, args);
+    } else if args[0] == "daemon" {
+        // --- Mode Daemon (Unix socket) ---
+        run_daemon_mode(&mut table, args);
+    } else if args[0] == "repl" {
+        // --- Mode REPL ---
+        if let Err(e) = menu::repl::run_repl(&mut table) {
             eprintln!("Error: {}", e);
         }
 
-        // Simpan tabel setelah menjalankan command
-        if let Err(e) = table.save(TABLE_FILE) {
-            eprintln!("Gagal menyimpan tabel: {}", e);
+        if let Err(e) = save_table(&table) {
+            eprintln!("{}", tr!("main.save_failed", e));
         }
+    } else {
+        // --- Mode Command-Line ---
+        run_cli_mode(&mut table, args);
     }
 }
 
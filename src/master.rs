@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use crate::table::handlers::service::DataTable;
+use crate::error::TableError;
+use crate::table::handlers::{service::DataTable, sqlite};
+use rusqlite::Connection;
 
 pub struct DataBase {
     pub tables: HashMap<String, DataTable>,
@@ -9,6 +11,12 @@ pub struct DataBase {
     pub selected: Option<String>,
 }
 
+impl Default for DataBase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DataBase {
     pub fn new() -> Self {
         Self {
@@ -19,9 +27,9 @@ impl DataBase {
         }
     }
 
-    pub fn add_table(&mut self, name: &str) -> Result<(), String> {
+    pub fn add_table(&mut self, name: &str) -> Result<(), TableError> {
         if self.tables.contains_key(name) {
-            return Err(format!("Tabel `{}` sudah ada", name));
+            return Err(TableError::TableExists(name.into()));
         }
         self.tables.insert(name.to_string(), DataTable::new());
         Ok(())
@@ -31,11 +39,39 @@ impl DataBase {
         self.tables.get_mut(name)
     }
 
-    pub fn remove_table(&mut self, name: &str) -> Result<(), String> {
+    pub fn remove_table(&mut self, name: &str) -> Result<(), TableError> {
         if self.tables.remove(name).is_some() {
             Ok(())
         } else {
-            Err(format!("Tabel `{}` tidak ditemukan", name))
+            Err(TableError::TableNotFound(name.into()))
         }
     }
+
+    // Export semua tabel ke satu file SQLite (nama tabel = key)
+    pub fn export_sqlite(&self, path: &str, replace: bool) -> Result<(), TableError> {
+        let mut conn = Connection::open(path)?;
+        let tx = conn.transaction()?;
+        for (name, table) in &self.tables {
+            table.write_sqlite(&tx, name, replace)?;
+        }
+        tx.commit().map_err(TableError::from)
+    }
+
+    // Import semua tabel dari file SQLite; tabel yang sudah ada → error
+    pub fn import_sqlite(&mut self, path: &str) -> Result<(), TableError> {
+        let conn = Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+        let names = sqlite::sqlite_table_names(&conn)?;
+        if let Some(name) = names.iter().find(|n| self.tables.contains_key(*n)) {
+            return Err(TableError::TableExists(name.clone()));
+        }
+
+        let mut imported = Vec::new();
+        for name in names {
+            let table = DataTable::read_sqlite(&conn, &name, None)?;
+            imported.push((name, table));
+        }
+        self.tables.extend(imported);
+        Ok(())
+    }
 }
@@ -1,36 +1,50 @@
-use crate::table::handlers::service::DataTable;
-use crate::table::value::{ColumnType, Value};
+use crate::command::daemon;
+use crate::menu::tui;
 use std::io::{self, Write};
+use table::TableError;
+use table::handlers::{
+    pager::ViewOptions,
+    search::{SearchIndex, SearchOptions, SearchQuery},
+    service::DataTable,
+};
+use table::models::RowTable;
+use table::tr;
+use table::value::{ColumnType, Value};
 
 pub fn run_menu(table: &mut DataTable) {
     loop {
-        println!("\n=== Menu Tabel ===");
-        println!("1. Tambah Kolom");
-        println!("2. Tambah Row");
-        println!("3. Set Primary");
-        println!("4. Update Nilai");
-        println!("5. Hapus Kolom / Row");
-        println!("6. Tampilkan Tabel");
-        println!("7. Tampilkan Tipe Kolom"); // <-- opsi baru
-        println!("0. Keluar");
-
-        let choice = read_input("Pilih menu: ");
+        println!("{}", tr!("menu.title"));
+        for key in [
+            "menu.1", "menu.2", "menu.3", "menu.4", "menu.5", "menu.6", "menu.7", "menu.8",
+            "menu.9", "menu.s", "menu.0",
+        ] {
+            println!("{}", tr!(key));
+        }
+
+        let choice = read_input(&tr!("menu.choose"));
         match choice.as_str() {
             "1" => add_column_interactive(table),
             "2" => add_row_interactive(table),
             "3" => set_primary_interactive(table),
             "4" => set_value_interactive(table),
             "5" => remove_interactive(table),
-            "6" => table.show_data_table(),
+            "6" => show_table_interactive(table),
             "7" => table.show_column_types(), // <-- panggil method baru
+            "s" | "S" => search_interactive(table),
+            "8" => encryption_interactive(table),
+            "9" => {
+                if let Err(e) = tui::run_tui(table) {
+                    println!("Error: {}", e);
+                }
+            }
             "0" => break,
-            _ => println!("Pilihan tidak valid"),
+            _ => println!("{}", tr!("msg.invalid_choice")),
         }
     }
 }
 
-// --- Fungsi bantuan membaca input ---
-fn read_input(prompt: &str) -> String {
+// --- Fungsi bantuan membaca input (dipakai juga oleh merge) ---
+pub fn read_input(prompt: &str) -> String {
     print!("{}", prompt);
     io::stdout().flush().unwrap();
     let mut input = String::new();
@@ -38,42 +52,82 @@ fn read_input(prompt: &str) -> String {
     input.trim().to_string()
 }
 
+// Baca passphrase tanpa ditampilkan (dipakai juga oleh main & cli).
+// Bukan terminal (pipe/file) → dibaca biasa
+pub fn read_passphrase(prompt: &str) -> String {
+    let fd = libc::STDIN_FILENO;
+    let mut term: libc::termios = unsafe { std::mem::zeroed() };
+    if unsafe { libc::tcgetattr(fd, &mut term) } != 0 {
+        return read_input(prompt);
+    }
+    let original = term;
+    // Matikan echo; Enter tetap memberi baris baru
+    term.c_lflag &= !libc::ECHO;
+    term.c_lflag |= libc::ECHONL;
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &term) };
+    let input = read_input(prompt);
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &original) };
+    input
+}
+
+// Minta passphrase baru dua kali agar tidak salah ketik (dipakai juga oleh cli)
+pub fn read_confirmed_passphrase() -> Result<String, TableError> {
+    let first = read_passphrase(&tr!("prompt.new_passphrase"));
+    let second = read_passphrase(&tr!("prompt.repeat_passphrase"));
+    if first != second {
+        return Err(TableError::Crypto(tr!("crypto.mismatch")));
+    }
+    Ok(first)
+}
+
 // --- Fungsi interaktif ---
 fn add_column_interactive(table: &mut DataTable) {
-    let col_name = read_input("Nama kolom: ");
+    let col_name = read_input(&tr!("prompt.column_name"));
 
-    let col_type_str = read_input("Tipe kolom (Text, Numb, Bool, Char): ");
+    let col_type_str = read_input(&tr!("prompt.column_type"));
 
-    let col_type = match col_type_str.to_lowercase().as_str() {
-        "text" => ColumnType::Text,
-        "numb" => ColumnType::Numb,
-        "bool" => ColumnType::Bool,
-        "char" => ColumnType::Char,
-        _ => {
-            println!("Tipe kolom tidak valid!");
+    // `=EKSPRESI` → kolom hitung
+    if let Some(expr) = col_type_str.strip_prefix('=') {
+        match table.add_computed_column(&col_name, expr) {
+            Ok(_) => println!("{}", tr!("msg.column_added")),
+            Err(e) => println!("Error: {}", e),
+        }
+        return;
+    }
+
+    let col_type = match ColumnType::parse(&col_type_str) {
+        Ok(t) => t,
+        Err(_) => {
+            println!("{}", tr!("msg.invalid_column_type"));
             return;
         }
     };
 
     match table.add_column(vec![(col_name.as_str(), col_type)]) {
-        Ok(_) => println!("Kolom berhasil ditambahkan."),
+        Ok(_) => println!("{}", tr!("msg.column_added")),
         Err(e) => println!("Error: {}", e),
     }
 }
 
 fn add_row_interactive(table: &mut DataTable) {
     if table.column.is_empty() {
-        println!("Belum ada kolom, tambahkan kolom dulu!");
+        println!("{}", tr!("msg.no_columns_yet"));
         return;
     }
 
     let mut values: Vec<Value> = Vec::new();
 
     for (i, col) in table.column.iter().enumerate() {
+        // Kolom hitung diisi otomatis
+        if col.expr.is_some() {
+            values.push(Value::Empty);
+            continue;
+        }
         loop {
-            let input = read_input(&format!(
-                "Nilai untuk kolom '{}' (type {:?}): ",
-                col.colname, col.coltype
+            let input = read_input(&tr!(
+                "prompt.row_value",
+                col.colname,
+                format!("{:?}", col.coltype)
             ));
 
             match table._parse_input_to_value_type(i, &input) {
@@ -81,42 +135,92 @@ fn add_row_interactive(table: &mut DataTable) {
                     values.push(val);
                     break;
                 }
-                Err(e) => println!("Error: {}. Masukkan ulang.", e),
+                Err(e) => println!("Error: {}", tr!("msg.retry", e)),
             }
         }
     }
 
     match table.add_row(values) {
-        Ok(_) => println!("Row berhasil ditambahkan."),
+        Ok(_) => println!("{}", tr!("msg.row_added")),
         Err(e) => println!("Error: {}", e),
     }
 }
 
+// Tampilkan tabel per halaman dengan navigasi
+fn show_table_interactive(table: &DataTable) {
+    let rows: Vec<&RowTable> = table.row.iter().collect();
+    let mut opts = ViewOptions::default();
+
+    loop {
+        table.show_data_table(&rows, &opts);
+        let view = table.page_view(&rows, &opts);
+        let cmd = read_input(&tr!("pager.help"));
+        match cmd.as_str() {
+            "n" if opts.page + 1 < view.page_count => opts.page += 1,
+            "p" => opts.page = opts.page.saturating_sub(1),
+            ">" if view.hidden_right => opts.col_offset += 1,
+            "<" => opts.col_offset = opts.col_offset.saturating_sub(1),
+            "w" => opts.wrap = !opts.wrap,
+            "k" => opts.pin_primary = !opts.pin_primary,
+            "0" | "q" | "" => break,
+            other => match other.strip_prefix("j ").map(|n| n.trim().parse::<usize>()) {
+                Some(Ok(n)) if n > 0 => {
+                    opts.limit = n;
+                    opts.page = 0;
+                }
+                _ => println!("{}", tr!("msg.invalid_choice")),
+            },
+        }
+    }
+}
+
+// Tabel sebesar ini dicari lewat indeks kata (dibangun sekali per sesi cari)
+const SEARCH_INDEX_MIN_ROWS: usize = 1000;
+
+// Cari berulang di semua kolom; hasil urut skor dengan bagian yang cocok ditandai
+fn search_interactive(table: &DataTable) {
+    let opts = SearchOptions::default();
+    let index = (table.row.len() >= SEARCH_INDEX_MIN_ROWS)
+        .then(|| SearchIndex::build(table, &opts).ok())
+        .flatten();
+
+    loop {
+        let input = read_input(&tr!("prompt.search"));
+        if input.is_empty() {
+            break;
+        }
+        let query = SearchQuery::parse(&input, opts.fuzzy);
+        match table.search(&query, &opts, index.as_ref()) {
+            Ok(hits) if hits.is_empty() => println!("{}", tr!("search.none")),
+            Ok(hits) => {
+                let rows: Vec<&RowTable> = hits.iter().map(|h| h.row).collect();
+                let view = ViewOptions {
+                    highlight: Some(query),
+                    ..ViewOptions::default()
+                };
+                table.show_data_table(&rows, &view);
+            }
+            Err(e) => println!("Error: {}", e),
+        }
+    }
+}
+
 fn set_primary_interactive(table: &mut DataTable) {
-    let name = read_input("Nama kolom untuk primary: ");
+    let name = read_input(&tr!("prompt.primary_column"));
     match table.set_primary(&name) {
-        Ok(_) => println!("Primary column berhasil diatur"),
+        Ok(_) => println!("{}", tr!("msg.primary_set")),
         Err(e) => println!("Error: {}", e),
     }
 }
 
 fn set_value_interactive(table: &mut DataTable) {
-    let cond_col = read_input("Kolom kondisi: ");
-    let cond_val_str = read_input("Nilai kondisi: ");
-    let target_col = read_input("Kolom target: ");
-    let new_val_str = read_input("Nilai baru: ");
-
-    // Cari index kolom kondisi
-    let cond_index = match table.column.iter().position(|c| c.colname == cond_col) {
-        Some(i) => i,
-        None => {
-            println!("Kolom kondisi '{}' tidak ditemukan", cond_col);
-            return;
-        }
-    };
+    let cond_col = read_input(&tr!("prompt.cond_column"));
+    let cond_val_str = read_input(&tr!("prompt.cond_value"));
+    let target_col = read_input(&tr!("prompt.target_column"));
+    let new_val_str = read_input(&tr!("prompt.new_value"));
 
     // Parse nilai kondisi sesuai tipe kolom
-    let cond_value = match table._parse_input_to_value_type(cond_index, &cond_val_str) {
+    let cond_value = match table.parse_value(&cond_col, &cond_val_str) {
         Ok(v) => v,
         Err(e) => {
             println!("Error: {}", e);
@@ -124,17 +228,8 @@ fn set_value_interactive(table: &mut DataTable) {
         }
     };
 
-    // Cari index kolom target
-    let target_index = match table.column.iter().position(|c| c.colname == target_col) {
-        Some(i) => i,
-        None => {
-            println!("Kolom target '{}' tidak ditemukan", target_col);
-            return;
-        }
-    };
-
     // Parse nilai baru sesuai tipe kolom target
-    let new_value = match table._parse_input_to_value_type(target_index, &new_val_str) {
+    let new_value = match table.parse_value(&target_col, &new_val_str) {
         Ok(v) => v,
         Err(e) => {
             println!("Error: {}", e);
@@ -144,38 +239,35 @@ fn set_value_interactive(table: &mut DataTable) {
 
     // Set nilai
     match table.set_value_where(&cond_col, cond_value, &target_col, new_value) {
-        Ok(_) => println!("Berhasil mengubah nilai."),
+        Ok(_) => println!("{}", tr!("msg.value_updated")),
         Err(e) => println!("Error: {}", e),
     }
 }
 
 fn remove_interactive(table: &mut DataTable) {
-    let pilih: String = read_input("Hapus kolom atau row? (kolom/row): ");
+    let pilih: String = read_input(&tr!("prompt.remove_what"));
     match pilih.to_lowercase().as_str() {
-        "kolom" => {
-            let name: String = read_input("Nama kolom: ");
+        "kolom" | "column" => {
+            let name: String = read_input(&tr!("prompt.column_name"));
             match table.remove_column(&name) {
-                Ok(_) => println!("Kolom dihapus"),
+                Ok(_) => println!("{}", tr!("msg.column_removed")),
                 Err(e) => println!("Error: {}", e),
             }
         }
         "row" => {
-            let colname: String = read_input("Kolom kondisi: ");
+            let colname: String = read_input(&tr!("prompt.cond_column"));
 
-            // Cari index kolom
-            let col_index = match table.column.iter().position(|c| c.colname == colname) {
-                Some(i) => i,
-                None => {
-                    println!("Error: kolom `{}` tidak ditemukan", colname);
-                    return;
-                }
-            };
+            // Pastikan kolom ada sebelum minta nilai
+            if let Err(e) = table.column_position(&colname) {
+                println!("Error: {}", e);
+                return;
+            }
 
             // Baca input user
-            let input_val: String = read_input("Nilai untuk hapus: ");
+            let input_val: String = read_input(&tr!("prompt.remove_value"));
 
             // Parse input sesuai tipe kolom
-            let val = match table._parse_input_to_value_type(col_index, &input_val) {
+            let val = match table.parse_value(&colname, &input_val) {
                 Ok(v) => v,
                 Err(e) => {
                     println!("Error: {}", e);
@@ -185,10 +277,45 @@ fn remove_interactive(table: &mut DataTable) {
 
             // Hapus row
             match table.remove_row(&colname, val) {
-                Ok(_) => println!("Row dihapus"),
+                Ok(_) => println!("{}", tr!("msg.row_removed")),
                 Err(e) => println!("Error: {}", e),
             }
         }
-        _ => println!("Pilihan tidak valid"),
+        _ => println!("{}", tr!("msg.invalid_choice")),
+    }
+}
+
+fn encryption_interactive(table: &mut DataTable) {
+    let status = if table.is_encrypted() {
+        tr!("menu.encrypted")
+    } else {
+        tr!("menu.not_encrypted")
+    };
+    println!("{}", tr!("menu.status", status));
+    if let Err(e) = daemon::ensure_not_running() {
+        println!("Error: {}", e);
+        return;
+    }
+
+    let pilih = read_input(&tr!("prompt.crypto_action"));
+    let result = match pilih.to_lowercase().as_str() {
+        "encrypt" => {
+            let passphrase = read_confirmed_passphrase();
+            passphrase.and_then(|p| table.encrypt(&p))
+        }
+        "decrypt" => table.decrypt(),
+        "rekey" => {
+            let passphrase = read_confirmed_passphrase();
+            passphrase.and_then(|p| table.rekey(&p))
+        }
+        _ => {
+            println!("{}", tr!("msg.invalid_choice"));
+            return;
+        }
+    };
+
+    match result {
+        Ok(_) => println!("{}", tr!("msg.crypto_done")),
+        Err(e) => println!("Error: {}", e),
     }
 }
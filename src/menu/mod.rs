@@ -1 +1,3 @@
 pub mod interactive;
+pub mod repl;
+pub mod tui;
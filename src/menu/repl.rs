@@ -0,0 +1,277 @@
+use crate::command::cli::{self, VERBS};
+use crate::command::output::OutputFormat;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::{Context, Editor, Helper};
+use std::collections::BTreeSet;
+use std::env;
+use table::handlers::service::DataTable;
+use table::tr;
+use table::value::Value;
+
+// Batas jumlah nilai unik per kolom yang ditawarkan saat completion
+const MAX_VALUES_PER_COLUMN: usize = 200;
+
+// Opsi yang dikenal oleh perintah CLI
+const OPTIONS: &[&str] = &[
+    "--format",
+    "--out",
+    "--where",
+    "--page",
+    "--limit",
+    "--col",
+    "--wrap",
+    "--no-pin",
+    "--lines",
+    "--table",
+    "--replace",
+    "--primary",
+    "--dialect",
+    "--batch",
+];
+
+// Perintah khusus REPL (bukan bagian run_command)
+const REPL_VERBS: &[&str] = &["help", "exit", "quit"];
+
+// Lokasi file history: $HOME/.mytabel_history
+fn history_path() -> String {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".into());
+    format!("{}/.mytabel_history", home)
+}
+
+// Snapshot nama kolom & nilai untuk completion; diperbarui tiap perintah
+struct ReplHelper {
+    columns: Vec<String>,
+    values: Vec<Vec<String>>,
+}
+
+impl ReplHelper {
+    fn from_table(table: &DataTable) -> Self {
+        let columns: Vec<String> = table.column.iter().map(|c| c.colname.clone()).collect();
+        let values = (0..columns.len())
+            .map(|i| {
+                let unique: BTreeSet<String> = table
+                    .row
+                    .iter()
+                    .filter_map(|r| r.value.get(i))
+                    .filter(|v| !matches!(v, Value::Empty))
+                    .map(|v| v.to_string())
+                    .collect();
+                unique.into_iter().take(MAX_VALUES_PER_COLUMN).collect()
+            })
+            .collect();
+        Self { columns, values }
+    }
+
+    fn column_values(&self, colname: &str) -> &[String] {
+        self.columns
+            .iter()
+            .position(|c| c == colname)
+            .map(|i| self.values[i].as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+fn candidates<'a>(
+    prefix: &str,
+    items: impl IntoIterator<Item = &'a String>,
+    lead: &str,
+) -> Vec<Pair> {
+    items
+        .into_iter()
+        .filter(|item| item.starts_with(prefix))
+        .map(|item| Pair {
+            display: item.clone(),
+            replacement: format!("{}{} ", lead, quote_if_needed(item)),
+        })
+        .collect()
+}
+
+// Nilai berspasi/berkutip dibungkus "..." agar terbaca utuh oleh split_args
+fn quote_if_needed(value: &str) -> String {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || "\"'\\".contains(c)) {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let start = line[..pos]
+            .rfind(char::is_whitespace)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let word = &line[start..pos];
+        let prior = cli::split_args(&line[..start]).unwrap_or_default();
+
+        // Kata pertama → nama perintah
+        if prior.is_empty() {
+            let verbs: Vec<String> = VERBS
+                .iter()
+                .chain(REPL_VERBS)
+                .map(|v| v.to_string())
+                .collect();
+            return Ok((start, candidates(word, &verbs, "")));
+        }
+
+        if word.starts_with("--") {
+            let options: Vec<String> = OPTIONS.iter().map(|o| o.to_string()).collect();
+            return Ok((start, candidates(word, &options, "")));
+        }
+
+        // KOLOM=NILAI (mis. --where)
+        if let Some((colname, value)) = word.split_once('=') {
+            let lead = format!("{}=", colname);
+            return Ok((start, candidates(value, self.column_values(colname), &lead)));
+        }
+
+        // Nilai: setelah nama kolom, atau posisi kolom pada add_row
+        let mut found = candidates(word, &self.columns, "");
+        let last = prior.last().map(String::as_str).unwrap_or_default();
+        if prior[0] == "add_row" {
+            if let Some(values) = self.values.get(prior.len() - 1) {
+                found = candidates(word, values, "");
+            }
+        } else if self.columns.iter().any(|c| c == last) {
+            found.extend(candidates(word, self.column_values(last), ""));
+        }
+        Ok((start, found))
+    }
+}
+
+impl Validator for ReplHelper {
+    // Input belum lengkap → lanjut ke baris berikutnya
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        let input = ctx.input();
+        let trailing_backslashes = input.chars().rev().take_while(|c| *c == '\\').count();
+        if trailing_backslashes % 2 == 1 || cli::split_args(input).is_err() {
+            Ok(ValidationResult::Incomplete)
+        } else {
+            Ok(ValidationResult::Valid(None))
+        }
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+// Mode REPL: perintah sama dengan CLI, dengan history & tab completion
+pub fn run_repl(table: &mut DataTable) -> Result<(), String> {
+    let mut rl: Editor<ReplHelper, DefaultHistory> =
+        Editor::new().map_err(|e| tr!("repl.open_failed", e))?;
+    rl.set_helper(Some(ReplHelper::from_table(table)));
+
+    let history = history_path();
+    let _ = rl.load_history(&history); // belum ada history → abaikan
+
+    println!("{}", tr!("repl.welcome"));
+    loop {
+        let line = match rl.readline(&tr!("repl.prompt")) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue, // Ctrl-C: batalkan baris
+            Err(ReadlineError::Eof) => break,            // Ctrl-D: keluar
+            Err(e) => return Err(format!("REPL: {}", e)),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let _ = rl.add_history_entry(line.as_str());
+
+        let args = match cli::split_args(&line) {
+            Ok(args) if args.is_empty() => continue,
+            Ok(args) => args,
+            Err(e) => {
+                println!("Error: {}", e);
+                continue;
+            }
+        };
+
+        match args[0].as_str() {
+            "exit" | "quit" => break,
+            "help" => println!("{}", tr!("repl.commands", VERBS.join(", "))),
+            _ => {
+                if let Err(e) = cli::run_command(table, args, OutputFormat::Table) {
+                    println!("Error: {}", e);
+                }
+            }
+        }
+
+        if let Some(helper) = rl.helper_mut() {
+            *helper = ReplHelper::from_table(table);
+        }
+    }
+
+    rl.save_history(&history)
+        .map_err(|e| tr!("repl.history_failed", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use table::value::ColumnType;
+
+    fn helper() -> ReplHelper {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![("id", ColumnType::Numb), ("kota", ColumnType::Text)])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        for (id, kota) in [(1, "Bandung"), (2, "Banda Aceh"), (3, "Bogor")] {
+            table
+                .add_row(vec![Value::Numb(id), Value::Text(kota.into())])
+                .unwrap();
+        }
+        ReplHelper::from_table(&table)
+    }
+
+    fn complete(helper: &ReplHelper, line: &str) -> (usize, Vec<String>) {
+        let history = DefaultHistory::new();
+        let (start, pairs) = helper
+            .complete(line, line.len(), &Context::new(&history))
+            .unwrap();
+        (start, pairs.into_iter().map(|p| p.replacement).collect())
+    }
+
+    #[test]
+    fn completes_verbs_options_columns_and_values() {
+        let helper = helper();
+        let (start, verbs) = complete(&helper, "set_p");
+        assert_eq!((start, verbs), (0, vec!["set_primary ".to_string()]));
+        assert!(complete(&helper, "ex").1.contains(&"exit ".to_string()));
+        assert_eq!(complete(&helper, "print --whe").1, ["--where "]);
+        assert_eq!(
+            complete(&helper, "print --where kota=Ban").1,
+            ["kota=\"Banda Aceh\" ", "kota=Bandung "]
+        );
+        // add_row: nilai mengikuti posisi kolom
+        assert_eq!(complete(&helper, "add_row 1 Bo").1, ["Bogor "]);
+        let (start, found) = complete(&helper, "remove_row ko");
+        assert_eq!((start, found), (11, vec!["kota ".to_string()]));
+    }
+
+    #[test]
+    fn quoted_values_split_back_unchanged() {
+        for value in ["Banda Aceh", "a\"b", "c:\\d", ""] {
+            let line = format!("x {}", quote_if_needed(value));
+            assert_eq!(cli::split_args(&line).unwrap(), ["x", value]);
+        }
+        assert_eq!(quote_if_needed("Bogor"), "Bogor");
+    }
+}
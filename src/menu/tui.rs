@@ -0,0 +1,539 @@
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    DefaultTerminal, Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+};
+use table::TableError;
+use table::config::{self, HeaderColor};
+use table::handlers::{pager, service::DataTable};
+use table::models::RowTable;
+use table::tr;
+use table::value::{ColumnType, Value};
+
+// Lebar maksimum sel di grid
+const MAX_CELL_WIDTH: usize = 30;
+
+enum Mode {
+    Browse,
+    Edit(String),               // buffer nilai baru untuk sel aktif
+    Insert(Vec<Value>, String), // nilai yang sudah diisi + buffer kolom berikutnya
+    ConfirmDelete,
+}
+
+struct App<'a> {
+    table: &'a mut DataTable,
+    row: usize,        // baris aktif
+    col: usize,        // kolom aktif (index di table.column)
+    top: usize,        // baris pertama yang tampil
+    col_offset: usize, // kolom pertama yang tampil (selain primary)
+    mode: Mode,
+    status: Result<String, String>,
+    show_types: bool,
+    quit: bool,
+}
+
+// Gaya header grid mengikuti `header_color` di config
+fn header_style() -> Style {
+    let style = Style::new().add_modifier(Modifier::BOLD);
+    match config::current().header_color {
+        HeaderColor::None => style,
+        HeaderColor::Red => style.fg(Color::Red),
+        HeaderColor::Green => style.fg(Color::Green),
+        HeaderColor::Yellow => style.fg(Color::Yellow),
+        HeaderColor::Blue => style.fg(Color::Blue),
+        HeaderColor::Magenta => style.fg(Color::Magenta),
+        HeaderColor::Cyan => style.fg(Color::Cyan),
+        HeaderColor::White => style.fg(Color::White),
+    }
+}
+
+// Jalankan browser layar penuh sampai user keluar
+pub fn run_tui(table: &mut DataTable) -> Result<(), String> {
+    let mut terminal = ratatui::try_init().map_err(|e| tr!("tui.open_failed", e))?;
+    let mut app = App {
+        table,
+        row: 0,
+        col: 0,
+        top: 0,
+        col_offset: 0,
+        mode: Mode::Browse,
+        status: Ok(tr!("tui.hint")),
+        show_types: false,
+        quit: false,
+    };
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl App<'_> {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<(), String> {
+        while !self.quit {
+            terminal
+                .draw(|frame| self.draw(frame))
+                .map_err(|e| format!("TUI: {}", e))?;
+            if let Event::Key(key) = event::read().map_err(|e| format!("TUI: {}", e))?
+                && key.kind == KeyEventKind::Press
+            {
+                self.handle_key(key);
+            }
+        }
+        Ok(())
+    }
+
+    // --- Input ---
+    fn handle_key(&mut self, key: KeyEvent) {
+        match std::mem::replace(&mut self.mode, Mode::Browse) {
+            Mode::Browse => self.handle_browse(key.code),
+            Mode::Edit(buffer) => self.handle_edit(key.code, buffer),
+            Mode::Insert(values, buffer) => self.handle_insert(key.code, values, buffer),
+            Mode::ConfirmDelete => {
+                if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                    self.delete_row();
+                } else {
+                    self.status = Ok(tr!("tui.delete_cancelled"));
+                }
+            }
+        }
+    }
+
+    fn handle_browse(&mut self, code: KeyCode) {
+        let rows = self.table.row.len();
+        let cols = self.table.column.len();
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.row = self.row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if self.row + 1 < rows => self.row += 1,
+            KeyCode::Left | KeyCode::Char('h') => self.col = self.col.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') if self.col + 1 < cols => self.col += 1,
+            KeyCode::PageUp => self.row = self.row.saturating_sub(10),
+            KeyCode::PageDown => self.row = (self.row + 10).min(rows.saturating_sub(1)),
+            KeyCode::Home | KeyCode::Char('g') => self.row = 0,
+            KeyCode::End | KeyCode::Char('G') => self.row = rows.saturating_sub(1),
+            KeyCode::Char('t') => self.show_types = !self.show_types,
+            KeyCode::Enter | KeyCode::Char('e') => {
+                if let Some(val) = self.current_value() {
+                    let text = match val {
+                        Value::Empty => String::new(),
+                        v => v.to_string(),
+                    };
+                    self.mode = Mode::Edit(text);
+                }
+            }
+            KeyCode::Char('a') => {
+                if self.table.column.is_empty() {
+                    self.status = Err(tr!("msg.no_columns_yet"));
+                } else {
+                    self.continue_insert(Vec::new());
+                }
+            }
+            KeyCode::Char('d') if rows > 0 => self.mode = Mode::ConfirmDelete,
+            KeyCode::Char('?') => self.status = Ok(tr!("tui.help")),
+            _ => {}
+        }
+    }
+
+    fn handle_edit(&mut self, code: KeyCode, mut buffer: String) {
+        match code {
+            KeyCode::Esc => self.status = Ok(tr!("tui.edit_cancelled")),
+            KeyCode::Enter => match self.commit_edit(&buffer) {
+                Ok(_) => self.status = Ok(tr!("msg.value_updated")),
+                Err(e) => {
+                    self.status = Err(e.to_string());
+                    self.mode = Mode::Edit(buffer);
+                }
+            },
+            other => {
+                edit_buffer(&mut buffer, other);
+                self.mode = Mode::Edit(buffer);
+            }
+        }
+    }
+
+    fn handle_insert(&mut self, code: KeyCode, mut values: Vec<Value>, mut buffer: String) {
+        match code {
+            KeyCode::Esc => self.status = Ok(tr!("tui.insert_cancelled")),
+            KeyCode::Enter => {
+                match self.table._parse_input_to_value_type(values.len(), &buffer) {
+                    Ok(val) => values.push(val),
+                    Err(e) => {
+                        self.status = Err(tr!("msg.retry", e));
+                        self.mode = Mode::Insert(values, buffer);
+                        return;
+                    }
+                }
+                self.status = Ok(String::new());
+                self.continue_insert(values);
+            }
+            other => {
+                edit_buffer(&mut buffer, other);
+                self.mode = Mode::Insert(values, buffer);
+            }
+        }
+    }
+
+    // Minta nilai kolom berikutnya; kolom hitung dilewati (diisi otomatis).
+    // Semua kolom terisi → simpan baris
+    fn continue_insert(&mut self, mut values: Vec<Value>) {
+        while let Some(col) = self.table.column.get(values.len()) {
+            if col.expr.is_none() {
+                self.mode = Mode::Insert(values, String::new());
+                return;
+            }
+            values.push(Value::Empty);
+        }
+        match self.table.add_row(values) {
+            Ok(_) => {
+                self.row = self.table.row.len() - 1;
+                self.status = Ok(tr!("msg.row_added"));
+            }
+            Err(e) => self.status = Err(e.to_string()),
+        }
+    }
+
+    // --- Aksi ke DataTable ---
+    fn current_value(&self) -> Option<&Value> {
+        self.table.row.get(self.row)?.value.get(self.col)
+    }
+
+    // Nilai primary baris aktif (dipakai sebagai kondisi update/hapus)
+    fn current_primary(&self) -> Result<(String, Value), TableError> {
+        let p_idx = self.table.primary_index.ok_or(TableError::NoPrimary)?;
+        let value = self
+            .table
+            .row
+            .get(self.row)
+            .and_then(|r| r.value.get(p_idx))
+            .ok_or(TableError::NoMatchingRow)?;
+        Ok((self.table.column[p_idx].colname.clone(), value.clone()))
+    }
+
+    fn commit_edit(&mut self, input: &str) -> Result<(), TableError> {
+        let (p_col, p_val) = self.current_primary()?;
+        let new_value = self.table._parse_input_to_value_type(self.col, input)?;
+        let target = self.table.column[self.col].colname.clone();
+        self.table
+            .set_value_where(&p_col, p_val, &target, new_value)
+    }
+
+    fn delete_row(&mut self) {
+        let result = self
+            .current_primary()
+            .and_then(|(p_col, p_val)| self.table.remove_row(&p_col, p_val));
+        match result {
+            Ok(_) => {
+                self.row = self.row.min(self.table.row.len().saturating_sub(1));
+                self.status = Ok(tr!("msg.row_removed"));
+            }
+            Err(e) => self.status = Err(e.to_string()),
+        }
+    }
+
+    // --- Render ---
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status, input] = Layout::vertical([
+            Constraint::Min(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let grid = if self.show_types {
+            let [grid, types] =
+                Layout::horizontal([Constraint::Min(10), Constraint::Length(28)]).areas(main);
+            self.draw_types(frame, types);
+            grid
+        } else {
+            main
+        };
+        self.draw_grid(frame, grid);
+
+        let status_line = match &self.status {
+            Ok(msg) => Paragraph::new(msg.as_str()),
+            Err(e) => Paragraph::new(format!("Error: {}", e)).style(Style::new().fg(Color::Red)),
+        };
+        frame.render_widget(status_line, status);
+        frame.render_widget(Paragraph::new(self.prompt_line()), input);
+    }
+
+    fn prompt_line(&self) -> Line<'_> {
+        match &self.mode {
+            Mode::Browse => Line::from(tr!(
+                "tui.position",
+                (self.row + 1).min(self.table.row.len()),
+                self.table.row.len(),
+                self.table
+                    .column
+                    .get(self.col)
+                    .map(|c| format!("{} ({:?})", c.colname, c.coltype))
+                    .unwrap_or_default()
+            )),
+            Mode::Edit(buffer) => Line::from(tr!(
+                "tui.edit_prompt",
+                self.table.column[self.col].colname,
+                buffer
+            )),
+            Mode::Insert(values, buffer) => {
+                let col = &self.table.column[values.len()];
+                Line::from(format!(
+                    "{}{}▏",
+                    tr!(
+                        "prompt.row_value",
+                        col.colname,
+                        format!("{:?}", col.coltype)
+                    ),
+                    buffer
+                ))
+            }
+            Mode::ConfirmDelete => {
+                Line::from(tr!("tui.confirm_delete")).style(Style::new().fg(Color::Yellow))
+            }
+        }
+    }
+
+    fn draw_grid(&mut self, frame: &mut Frame, area: Rect) {
+        // tinggi isi = area - border atas/bawah - header
+        let height = (area.height as usize).saturating_sub(3).max(1);
+        if self.row < self.top {
+            self.top = self.row;
+        } else if self.row >= self.top + height {
+            self.top = self.row + 1 - height;
+        }
+        let end = (self.top + height).min(self.table.row.len());
+        let visible: Vec<&RowTable> = self.table.row[self.top..end].iter().collect();
+
+        let (columns, widths, col_offset) =
+            self.visible_columns(&visible, (area.width as usize).saturating_sub(2));
+        self.col_offset = col_offset;
+
+        let header = Row::new(columns.iter().map(|&i| {
+            let col = &self.table.column[i];
+            let name = if col.is_primary {
+                format!("*{}", col.colname)
+            } else {
+                col.colname.clone()
+            };
+            Cell::from(name)
+        }))
+        .style(header_style());
+
+        let rows = visible.iter().enumerate().map(|(n, row)| {
+            let row_index = self.top + n;
+            let cells = columns.iter().zip(&widths).map(|(&i, &w)| {
+                let text = row.value.get(i).map(|v| v.to_string()).unwrap_or_default();
+                let cell = Cell::from(pager::truncate(&text, w));
+                if row_index == self.row && i == self.col {
+                    cell.style(Style::new().add_modifier(Modifier::REVERSED))
+                } else {
+                    cell
+                }
+            });
+            let row = Row::new(cells);
+            if row_index == self.row {
+                row.style(Style::new().bg(Color::DarkGray))
+            } else {
+                row
+            }
+        });
+
+        let constraints = widths.iter().map(|w| Constraint::Length(*w as u16));
+        let grid = Table::new(rows, constraints).header(header).block(
+            Block::new()
+                .borders(Borders::ALL)
+                .title(tr!("tui.title_table")),
+        );
+        frame.render_widget(grid, area);
+    }
+
+    // Pilih kolom yang muat; primary dipin di kiri, kolom aktif selalu terlihat
+    // Return: (kolom, lebar, col_offset baru)
+    fn visible_columns(
+        &self,
+        visible: &[&RowTable],
+        width: usize,
+    ) -> (Vec<usize>, Vec<usize>, usize) {
+        let pinned = self.table.primary_index;
+        let mut offset = self.col_offset;
+        if Some(self.col) != pinned && self.col < offset {
+            offset = self.col;
+        }
+
+        loop {
+            let mut order: Vec<usize> = pinned.into_iter().collect();
+            order.extend((offset..self.table.column.len()).filter(|i| Some(*i) != pinned));
+
+            let mut columns = Vec::new();
+            let mut widths = Vec::new();
+            let mut used = 0;
+            for i in order {
+                let w = self.table.natural_width(visible, i).min(MAX_CELL_WIDTH);
+                if !columns.is_empty() && used + w + 1 > width {
+                    break;
+                }
+                used += w + 1; // + spasi antar kolom
+                columns.push(i);
+                widths.push(w);
+            }
+
+            // Geser ke kanan sampai kolom aktif terlihat
+            if columns.contains(&self.col) || offset >= self.col {
+                return (columns, widths, offset);
+            }
+            offset += 1;
+        }
+    }
+
+    fn draw_types(&self, frame: &mut Frame, area: Rect) {
+        let rows = self.table.column.iter().map(|col| {
+            let name = if col.is_primary {
+                format!("*{}", col.colname)
+            } else {
+                col.colname.clone()
+            };
+            let col_type = match col.coltype {
+                ColumnType::Text => "Text",
+                ColumnType::Numb => "Numb",
+                ColumnType::Bool => "Bool",
+                ColumnType::Char => "Char",
+            };
+            // Kolom hitung ditandai `=` (ekspresi lengkap ada di show_column_types)
+            let col_type = match col.expr {
+                Some(_) => format!("={}", col_type),
+                None => col_type.to_string(),
+            };
+            Row::new([Cell::from(name), Cell::from(col_type)])
+        });
+        let panel = Table::new(rows, [Constraint::Min(10), Constraint::Length(5)]).block(
+            Block::new()
+                .borders(Borders::ALL)
+                .title(tr!("tui.title_types")),
+        );
+        frame.render_widget(panel, area);
+    }
+}
+
+// Edit sederhana: ketik karakter / hapus karakter terakhir
+fn edit_buffer(buffer: &mut String, code: KeyCode) {
+    match code {
+        KeyCode::Char(c) => buffer.push(c),
+        KeyCode::Backspace => {
+            buffer.pop();
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // id (primary), nama: 1 a, 2 b
+    fn table() -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![("id", ColumnType::Numb), ("nama", ColumnType::Text)])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        for (id, nama) in [(1, "a"), (2, "b")] {
+            table
+                .add_row(vec![Value::Numb(id), Value::Text(nama.into())])
+                .unwrap();
+        }
+        table
+    }
+
+    fn app(table: &mut DataTable) -> App<'_> {
+        App {
+            table,
+            row: 0,
+            col: 0,
+            top: 0,
+            col_offset: 0,
+            mode: Mode::Browse,
+            status: Ok(String::new()),
+            show_types: false,
+            quit: false,
+        }
+    }
+
+    fn press(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                '\x08' => KeyCode::Backspace,
+                '\x1b' => KeyCode::Esc,
+                c => KeyCode::Char(c),
+            };
+            app.handle_key(KeyEvent::from(code));
+        }
+    }
+
+    #[test]
+    fn cursor_stays_inside_table() {
+        let mut table = table();
+        let mut app = app(&mut table);
+        press(&mut app, "jjjlll");
+        assert_eq!((app.row, app.col), (1, 1));
+        press(&mut app, "kkkhhh");
+        assert_eq!((app.row, app.col), (0, 0));
+        press(&mut app, "G");
+        assert_eq!(app.row, 1);
+        press(&mut app, "q");
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn edit_is_validated_by_column_type() {
+        let mut table = table();
+        let mut app = app(&mut table);
+        // id: Numb → "x" ditolak, tetap di mode edit
+        press(&mut app, "e\x08x\n");
+        assert!(app.status.is_err());
+        assert!(matches!(&app.mode, Mode::Edit(buffer) if buffer == "x"));
+        press(&mut app, "\x1b");
+
+        press(&mut app, "le\x08zz\n");
+        assert!(app.status.is_ok());
+        assert_eq!(app.table.row[0].value[1], Value::Text("zz".into()));
+    }
+
+    #[test]
+    fn insert_and_delete_rows() {
+        let mut table = table();
+        let mut app = app(&mut table);
+        press(&mut app, "ax\n");
+        assert!(matches!(app.mode, Mode::Insert(ref v, _) if v.is_empty()));
+        press(&mut app, "\x083\nc\n");
+        assert_eq!(app.table.row.len(), 3);
+        assert_eq!(app.row, 2);
+
+        press(&mut app, "dn");
+        assert_eq!(app.table.row.len(), 3);
+        press(&mut app, "dy");
+        assert_eq!(app.table.row.len(), 2);
+        assert_eq!(app.row, 1);
+    }
+
+    #[test]
+    fn active_column_is_always_visible() {
+        let mut table = DataTable::default();
+        let names: Vec<String> = (0..6).map(|i| format!("kolom{}", i)).collect();
+        table
+            .add_column(
+                names
+                    .iter()
+                    .map(|n| (n.as_str(), ColumnType::Text))
+                    .collect(),
+            )
+            .unwrap();
+        table.set_primary("kolom0").unwrap();
+        let mut app = app(&mut table);
+        app.col = 5;
+        let (columns, _, offset) = app.visible_columns(&[], 20);
+        assert_eq!(columns, [0, 5]);
+        assert_eq!(offset, 5);
+    }
+}
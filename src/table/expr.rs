@@ -0,0 +1,742 @@
+use crate::error::TableError;
+use crate::table::{
+    models::ColumnTable,
+    value::{ColumnType, Value},
+};
+use crate::tr;
+
+// Ekspresi kolom hitung, mis. `qty * price`, `first + ' ' + last`,
+// `if(stok > 0, 'ada', 'habis')`. Urutan prioritas (rendah → tinggi):
+//   or / ||, and / &&, not / !, == != < <= > >=, + -, * / %, minus unary
+// Nama kolom berspasi ditulis dengan backtick: `harga satuan`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(Value),
+    Column(String),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::And => "and",
+            BinaryOp::Or => "or",
+        }
+    }
+}
+
+impl Expr {
+    pub fn parse(input: &str) -> Result<Expr, TableError> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(TableError::InvalidValue(tr!("expr.empty")));
+        }
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            depth: 0,
+        };
+        let expr = parser.or()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(unexpected(token)),
+        }
+    }
+
+    // Nama kolom yang dipakai ekspresi
+    pub fn columns(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.collect_columns(&mut names);
+        names
+    }
+
+    fn collect_columns<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Expr::Literal(_) => {}
+            Expr::Column(name) => {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+            Expr::Neg(e) | Expr::Not(e) => e.collect_columns(names),
+            Expr::Binary(_, a, b) => {
+                a.collect_columns(names);
+                b.collect_columns(names);
+            }
+            Expr::If(c, a, b) => {
+                c.collect_columns(names);
+                a.collect_columns(names);
+                b.collect_columns(names);
+            }
+        }
+    }
+
+    // Cek tipe terhadap kolom yang ada; return tipe hasil ekspresi
+    pub fn result_type(&self, columns: &[ColumnTable]) -> Result<ColumnType, TableError> {
+        match self {
+            Expr::Literal(value) => Ok(match value {
+                Value::Numb(_) => ColumnType::Numb,
+                Value::Bool(_) => ColumnType::Bool,
+                Value::Char(_) => ColumnType::Char,
+                _ => ColumnType::Text,
+            }),
+            Expr::Column(name) => columns
+                .iter()
+                .find(|c| &c.colname == name)
+                .map(|c| c.coltype.clone())
+                .ok_or_else(|| TableError::ColumnNotFound(name.clone())),
+            Expr::Neg(e) => match e.result_type(columns)? {
+                ColumnType::Numb => Ok(ColumnType::Numb),
+                other => Err(unary_type("-", &other)),
+            },
+            Expr::Not(e) => match e.result_type(columns)? {
+                ColumnType::Bool => Ok(ColumnType::Bool),
+                other => Err(unary_type("not", &other)),
+            },
+            Expr::Binary(op, a, b) => {
+                let (left, right) = (a.result_type(columns)?, b.result_type(columns)?);
+                binary_type(*op, &left, &right).ok_or_else(|| {
+                    TableError::InvalidValue(tr!(
+                        "expr.binary_type",
+                        op.symbol(),
+                        format!("{:?}", left),
+                        format!("{:?}", right)
+                    ))
+                })
+            }
+            Expr::If(cond, a, b) => {
+                let cond = cond.result_type(columns)?;
+                if cond != ColumnType::Bool {
+                    return Err(TableError::InvalidValue(tr!(
+                        "expr.condition",
+                        format!("{:?}", cond)
+                    )));
+                }
+                let (left, right) = (a.result_type(columns)?, b.result_type(columns)?);
+                match (&left, &right) {
+                    (l, r) if l == r => Ok(left),
+                    (l, r) if is_textual(l) && is_textual(r) => Ok(ColumnType::Text),
+                    _ => Err(TableError::InvalidValue(tr!(
+                        "expr.branches",
+                        format!("{:?}", left),
+                        format!("{:?}", right)
+                    ))),
+                }
+            }
+        }
+    }
+
+    // Hitung nilai untuk satu baris. Empty ikut menyebar seperti NULL di SQL,
+    // kecuali pada penggabungan teks (Empty dianggap string kosong).
+    // Overflow / bagi nol → Empty
+    pub fn eval(&self, columns: &[ColumnTable], values: &[Value]) -> Value {
+        match self {
+            Expr::Literal(value) => value.clone(),
+            Expr::Column(name) => columns
+                .iter()
+                .position(|c| &c.colname == name)
+                .and_then(|i| values.get(i))
+                .cloned()
+                .unwrap_or(Value::Empty),
+            Expr::Neg(e) => match e.eval(columns, values) {
+                Value::Numb(n) => n.checked_neg().map_or(Value::Empty, Value::Numb),
+                _ => Value::Empty,
+            },
+            Expr::Not(e) => match e.eval(columns, values) {
+                Value::Bool(b) => Value::Bool(!b),
+                _ => Value::Empty,
+            },
+            // and/or dievaluasi pendek: false and … → false, true or … → true
+            Expr::Binary(BinaryOp::And, a, b) => match a.eval(columns, values) {
+                Value::Bool(false) => Value::Bool(false),
+                Value::Bool(true) => match b.eval(columns, values) {
+                    Value::Bool(b) => Value::Bool(b),
+                    _ => Value::Empty,
+                },
+                _ => Value::Empty,
+            },
+            Expr::Binary(BinaryOp::Or, a, b) => match a.eval(columns, values) {
+                Value::Bool(true) => Value::Bool(true),
+                Value::Bool(false) => match b.eval(columns, values) {
+                    Value::Bool(b) => Value::Bool(b),
+                    _ => Value::Empty,
+                },
+                _ => Value::Empty,
+            },
+            Expr::Binary(op, a, b) => apply(*op, a.eval(columns, values), b.eval(columns, values)),
+            Expr::If(cond, a, b) => match cond.eval(columns, values) {
+                Value::Bool(true) => a.eval(columns, values),
+                _ => b.eval(columns, values),
+            },
+        }
+    }
+}
+
+fn is_textual(t: &ColumnType) -> bool {
+    matches!(t, ColumnType::Text | ColumnType::Char)
+}
+
+fn binary_type(op: BinaryOp, left: &ColumnType, right: &ColumnType) -> Option<ColumnType> {
+    use ColumnType::*;
+    match op {
+        BinaryOp::Add => match (left, right) {
+            (Numb, Numb) => Some(Numb),
+            // Gabung teks; angka ikut diubah jadi teks
+            (l, r) if (is_textual(l) || is_textual(r)) && *l != Bool && *r != Bool => Some(Text),
+            _ => None,
+        },
+        BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => {
+            matches!((left, right), (Numb, Numb)).then_some(Numb)
+        }
+        BinaryOp::Eq | BinaryOp::Ne => {
+            let same = left == right || (is_textual(left) && is_textual(right));
+            same.then_some(Bool)
+        }
+        BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+            let ordered =
+                (*left == Numb && *right == Numb) || (is_textual(left) && is_textual(right));
+            ordered.then_some(Bool)
+        }
+        BinaryOp::And | BinaryOp::Or => matches!((left, right), (Bool, Bool)).then_some(Bool),
+    }
+}
+
+fn apply(op: BinaryOp, left: Value, right: Value) -> Value {
+    use std::cmp::Ordering;
+
+    let textual = |v: &Value| matches!(v, Value::Text(_) | Value::Char(_));
+    match op {
+        BinaryOp::Add if textual(&left) || textual(&right) => {
+            let text = |v: &Value| match v {
+                Value::Empty => String::new(),
+                other => other.to_string(),
+            };
+            Value::Text(text(&left) + &text(&right))
+        }
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => {
+            let (Value::Numb(a), Value::Numb(b)) = (left, right) else {
+                return Value::Empty;
+            };
+            let result = match op {
+                BinaryOp::Add => a.checked_add(b),
+                BinaryOp::Sub => a.checked_sub(b),
+                BinaryOp::Mul => a.checked_mul(b),
+                BinaryOp::Div => a.checked_div(b),
+                _ => a.checked_rem(b),
+            };
+            result.map_or(Value::Empty, Value::Numb)
+        }
+        BinaryOp::Eq | BinaryOp::Ne => {
+            let equal = match (&left, &right) {
+                (l, r) if textual(l) && textual(r) => l.to_string() == r.to_string(),
+                (l, r) => l == r,
+            };
+            Value::Bool(equal == (op == BinaryOp::Eq))
+        }
+        _ => {
+            let ordering = match (&left, &right) {
+                (Value::Numb(a), Value::Numb(b)) => a.cmp(b),
+                (l, r) if textual(l) && textual(r) => l.to_string().cmp(&r.to_string()),
+                _ => return Value::Empty,
+            };
+            Value::Bool(match op {
+                BinaryOp::Lt => ordering == Ordering::Less,
+                BinaryOp::Le => ordering != Ordering::Greater,
+                BinaryOp::Gt => ordering == Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            })
+        }
+    }
+}
+
+fn unary_type(op: &str, t: &ColumnType) -> TableError {
+    TableError::InvalidValue(tr!("expr.unary_type", op, format!("{:?}", t)))
+}
+
+fn unexpected(token: &Token) -> TableError {
+    TableError::InvalidValue(tr!("expr.unexpected_token", token.text()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(i64),
+    Str(String),
+    Name(String),
+    Op(&'static str),
+}
+
+impl Token {
+    fn text(&self) -> String {
+        match self {
+            Token::Number(n) => n.to_string(),
+            Token::Str(s) => format!("'{}'", s),
+            Token::Name(n) => n.clone(),
+            Token::Op(op) => op.to_string(),
+        }
+    }
+
+    fn is_op(&self, op: &str) -> bool {
+        matches!(self, Token::Op(o) if *o == op)
+    }
+
+    fn is_keyword(&self, word: &str) -> bool {
+        matches!(self, Token::Name(n) if n.eq_ignore_ascii_case(word))
+    }
+}
+
+// Operator dua karakter dicek lebih dulu
+const OPERATORS: &[&str] = &[
+    "==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "/", "%", "<", ">", "!", "(", ")", ",",
+];
+
+fn tokenize(input: &str) -> Result<Vec<Token>, TableError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() {
+            let mut digits = String::new();
+            while let Some(&(_, d)) = chars.peek().filter(|(_, d)| d.is_ascii_digit()) {
+                digits.push(d);
+                chars.next();
+            }
+            let n = digits
+                .parse()
+                .map_err(|_| TableError::InvalidValue(tr!("expr.number", digits)))?;
+            tokens.push(Token::Number(n));
+        } else if c == '\'' || c == '"' {
+            chars.next();
+            let mut text = String::new();
+            // Masih di dalam string saat input habis (termasuk `\` di akhir) → error
+            let mut escaped = false;
+            loop {
+                match chars.next() {
+                    Some((_, other)) if escaped => {
+                        text.push(other);
+                        escaped = false;
+                    }
+                    Some((_, '\\')) => escaped = true,
+                    Some((_, q)) if q == c => break,
+                    Some((_, other)) => text.push(other),
+                    None => return Err(TableError::InvalidValue(tr!("expr.unclosed_string"))),
+                }
+            }
+            tokens.push(Token::Str(text));
+        } else if c == '`' {
+            chars.next();
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '`')) => break,
+                    Some((_, other)) => name.push(other),
+                    None => return Err(TableError::InvalidValue(tr!("expr.unclosed_name"))),
+                }
+            }
+            tokens.push(Token::Name(name));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut name = String::new();
+            while let Some(&(_, d)) = chars
+                .peek()
+                .filter(|(_, d)| d.is_alphanumeric() || *d == '_')
+            {
+                name.push(d);
+                chars.next();
+            }
+            tokens.push(Token::Name(name));
+        } else {
+            let rest = &input[start..];
+            let op = OPERATORS
+                .iter()
+                .find(|op| rest.starts_with(**op))
+                .ok_or_else(|| TableError::InvalidValue(tr!("expr.unexpected_char", c)))?;
+            for _ in 0..op.len() {
+                chars.next();
+            }
+            tokens.push(Token::Op(op));
+        }
+    }
+    Ok(tokens)
+}
+
+// Batas kedalaman ekspresi (kurung, operator berantai, not/minus berulang);
+// parser & eval rekursif, jadi ekspresi yang terlalu dalam bisa menghabiskan stack
+const MAX_DEPTH: usize = 64;
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    // Satu tingkat lebih dalam; dipulihkan oleh `level` yang membungkusnya
+    fn deeper(&mut self) -> Result<(), TableError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(TableError::InvalidValue(tr!("expr.too_deep", MAX_DEPTH)));
+        }
+        Ok(())
+    }
+
+    fn level(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<Expr, TableError>,
+    ) -> Result<Expr, TableError> {
+        let depth = self.depth;
+        let expr = parse(self)?;
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, TableError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| TableError::InvalidValue(tr!("expr.unexpected_end")))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    // Ambil token berikutnya jika cocok
+    fn eat(&mut self, matches: impl Fn(&Token) -> bool) -> bool {
+        if self.peek().is_some_and(matches) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_op(&mut self, op: &str) -> Result<(), TableError> {
+        let token = self.next()?;
+        if token.is_op(op) {
+            Ok(())
+        } else {
+            Err(unexpected(&token))
+        }
+    }
+
+    // Titik masuk rekursi (ekspresi utuh, isi kurung, argumen if)
+    fn or(&mut self) -> Result<Expr, TableError> {
+        self.level(|p| {
+            p.deeper()?;
+            let mut left = p.and()?;
+            while p.eat(|t| t.is_keyword("or") || t.is_op("||")) {
+                p.deeper()?;
+                left = Expr::Binary(BinaryOp::Or, Box::new(left), Box::new(p.and()?));
+            }
+            Ok(left)
+        })
+    }
+
+    fn and(&mut self) -> Result<Expr, TableError> {
+        self.level(|p| {
+            let mut left = p.not()?;
+            while p.eat(|t| t.is_keyword("and") || t.is_op("&&")) {
+                p.deeper()?;
+                left = Expr::Binary(BinaryOp::And, Box::new(left), Box::new(p.not()?));
+            }
+            Ok(left)
+        })
+    }
+
+    fn not(&mut self) -> Result<Expr, TableError> {
+        if self.eat(|t| t.is_keyword("not") || t.is_op("!")) {
+            self.deeper()?;
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, TableError> {
+        self.level(|p| {
+            let left = p.additive()?;
+            let op = match p.peek() {
+                Some(Token::Op("==")) => BinaryOp::Eq,
+                Some(Token::Op("!=")) => BinaryOp::Ne,
+                Some(Token::Op("<")) => BinaryOp::Lt,
+                Some(Token::Op("<=")) => BinaryOp::Le,
+                Some(Token::Op(">")) => BinaryOp::Gt,
+                Some(Token::Op(">=")) => BinaryOp::Ge,
+                _ => return Ok(left),
+            };
+            p.pos += 1;
+            p.deeper()?;
+            Ok(Expr::Binary(op, Box::new(left), Box::new(p.additive()?)))
+        })
+    }
+
+    fn additive(&mut self) -> Result<Expr, TableError> {
+        self.level(|p| {
+            let mut left = p.multiplicative()?;
+            loop {
+                let op = match p.peek() {
+                    Some(Token::Op("+")) => BinaryOp::Add,
+                    Some(Token::Op("-")) => BinaryOp::Sub,
+                    _ => return Ok(left),
+                };
+                p.pos += 1;
+                p.deeper()?;
+                left = Expr::Binary(op, Box::new(left), Box::new(p.multiplicative()?));
+            }
+        })
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr, TableError> {
+        self.level(|p| {
+            let mut left = p.unary()?;
+            loop {
+                let op = match p.peek() {
+                    Some(Token::Op("*")) => BinaryOp::Mul,
+                    Some(Token::Op("/")) => BinaryOp::Div,
+                    Some(Token::Op("%")) => BinaryOp::Rem,
+                    _ => return Ok(left),
+                };
+                p.pos += 1;
+                p.deeper()?;
+                left = Expr::Binary(op, Box::new(left), Box::new(p.unary()?));
+            }
+        })
+    }
+
+    fn unary(&mut self) -> Result<Expr, TableError> {
+        if self.eat(|t| t.is_op("-")) {
+            self.deeper()?;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, TableError> {
+        match self.next()? {
+            Token::Number(n) => Ok(Expr::Literal(Value::Numb(n))),
+            Token::Str(s) => Ok(Expr::Literal(Value::Text(s))),
+            Token::Op("(") => {
+                let expr = self.or()?;
+                self.expect_op(")")?;
+                Ok(expr)
+            }
+            Token::Name(name) if self.peek().is_some_and(|t| t.is_op("(")) => self.call(&name),
+            Token::Name(name) if name.eq_ignore_ascii_case("true") => {
+                Ok(Expr::Literal(Value::Bool(true)))
+            }
+            Token::Name(name) if name.eq_ignore_ascii_case("false") => {
+                Ok(Expr::Literal(Value::Bool(false)))
+            }
+            Token::Name(name) => Ok(Expr::Column(name)),
+            token => Err(unexpected(&token)),
+        }
+    }
+
+    fn call(&mut self, name: &str) -> Result<Expr, TableError> {
+        if !name.eq_ignore_ascii_case("if") {
+            return Err(TableError::InvalidValue(tr!("expr.unknown_function", name)));
+        }
+        self.expect_op("(")?;
+        let mut args = vec![self.or()?];
+        while self.eat(|t| t.is_op(",")) {
+            args.push(self.or()?);
+        }
+        self.expect_op(")")?;
+
+        let [cond, when_true, when_false]: [Expr; 3] = args
+            .try_into()
+            .map_err(|_| TableError::InvalidValue(tr!("expr.if_args")))?;
+        Ok(Expr::If(
+            Box::new(cond),
+            Box::new(when_true),
+            Box::new(when_false),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn col(name: &str, coltype: ColumnType) -> ColumnTable {
+        ColumnTable {
+            colname: name.into(),
+            coltype,
+            is_primary: false,
+            _is_auto_increment: false,
+            expr: None,
+        }
+    }
+
+    fn columns() -> Vec<ColumnTable> {
+        vec![
+            col("qty", ColumnType::Numb),
+            col("nama", ColumnType::Text),
+            col("aktif", ColumnType::Bool),
+        ]
+    }
+
+    fn eval(input: &str, values: &[Value]) -> Value {
+        Expr::parse(input).unwrap().eval(&columns(), values)
+    }
+
+    fn eval_const(input: &str) -> Value {
+        eval(input, &[])
+    }
+
+    #[test]
+    fn arithmetic_follows_precedence() {
+        assert_eq!(eval_const("1 + 2 * 3"), Value::Numb(7));
+        assert_eq!(eval_const("(1 + 2) * 3"), Value::Numb(9));
+        assert_eq!(eval_const("10 - 4 - 3"), Value::Numb(3));
+        assert_eq!(eval_const("7 % 4 * 2"), Value::Numb(6));
+        assert_eq!(eval_const("-2 * 3"), Value::Numb(-6));
+    }
+
+    #[test]
+    fn logic_follows_precedence() {
+        // and lebih kuat dari or; not lebih kuat dari and
+        assert_eq!(eval_const("true or false and false"), Value::Bool(true));
+        assert_eq!(eval_const("not false and false"), Value::Bool(false));
+        assert_eq!(eval_const("1 + 1 == 2 && 3 > 2"), Value::Bool(true));
+    }
+
+    #[test]
+    fn columns_and_if() {
+        let row = [
+            Value::Numb(3),
+            Value::Text("Budi".into()),
+            Value::Bool(true),
+        ];
+        assert_eq!(eval("qty * 2", &row), Value::Numb(6));
+        assert_eq!(eval("nama + ' ' + qty", &row), Value::Text("Budi 3".into()));
+        assert_eq!(
+            eval("if(aktif, 'ya', 'tidak')", &row),
+            Value::Text("ya".into())
+        );
+        let parsed = Expr::parse("if(qty > 0, nama, `nama`)").unwrap();
+        assert_eq!(parsed.columns(), vec!["qty", "nama"]);
+    }
+
+    #[test]
+    fn overflow_and_division_by_zero_give_empty() {
+        assert_eq!(eval_const("9223372036854775807 + 1"), Value::Empty);
+        assert_eq!(eval_const("9223372036854775807 * 2"), Value::Empty);
+        assert_eq!(eval_const("-(0 - 9223372036854775807 - 1)"), Value::Empty);
+        assert_eq!(eval_const("1 / 0"), Value::Empty);
+        assert_eq!(eval_const("1 % 0"), Value::Empty);
+    }
+
+    #[test]
+    fn empty_propagates_except_in_text_concat() {
+        let row = [Value::Empty, Value::Empty, Value::Empty];
+        assert_eq!(eval("qty + 1", &row), Value::Empty);
+        assert_eq!(eval("'x' + nama", &row), Value::Text("x".into()));
+        assert_eq!(eval("aktif and true", &row), Value::Empty);
+        assert_eq!(eval("false and aktif", &row), Value::Bool(false));
+    }
+
+    #[test]
+    fn result_type_checks_operands() {
+        let columns = columns();
+        let result = |input: &str| Expr::parse(input).unwrap().result_type(&columns);
+        assert_eq!(result("qty * 2").unwrap(), ColumnType::Numb);
+        assert_eq!(result("nama + qty").unwrap(), ColumnType::Text);
+        assert_eq!(result("qty > 1 or aktif").unwrap(), ColumnType::Bool);
+        assert!(matches!(
+            result("qty - nama"),
+            Err(TableError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            result("aktif + 1"),
+            Err(TableError::InvalidValue(_))
+        ));
+        assert!(matches!(result("-nama"), Err(TableError::InvalidValue(_))));
+        assert!(matches!(
+            result("not qty"),
+            Err(TableError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            result("if(qty, 1, 2)"),
+            Err(TableError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            result("harga * 2"),
+            Err(TableError::ColumnNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn syntax_errors() {
+        for input in [
+            "", "1 +", "(1 + 2", "1 2", "'abc", "foo(1)", "if(1, 2)", "1 $ 2",
+        ] {
+            assert!(
+                matches!(Expr::parse(input), Err(TableError::InvalidValue(_))),
+                "`{}` seharusnya ditolak",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn unclosed_string_is_rejected_at_end_of_input() {
+        for input in ["'a' + 'b\\", "'a' + \"b", "'\\'"] {
+            assert!(
+                matches!(tokenize(input), Err(TableError::InvalidValue(_))),
+                "`{}` seharusnya ditolak",
+                input
+            );
+        }
+        assert_eq!(
+            eval_const(r"'it\'s' + 'a\\b'"),
+            Value::Text(r"it'sa\b".into())
+        );
+    }
+
+    #[test]
+    fn nesting_depth_is_limited() {
+        let nested = |n: usize| format!("{}1{}", "(".repeat(n), ")".repeat(n));
+        assert!(Expr::parse(&nested(MAX_DEPTH - 1)).is_ok());
+        assert!(matches!(
+            Expr::parse(&nested(20_000)),
+            Err(TableError::InvalidValue(_))
+        ));
+        assert!(Expr::parse(&"-".repeat(20_000)).is_err());
+        assert!(Expr::parse(&vec!["1"; MAX_DEPTH * 2].join(" + ")).is_err());
+    }
+}
@@ -0,0 +1,478 @@
+use crate::error::TableError;
+use crate::table::handlers::{
+    crypto, hooks::RowChange, json_rows::value_to_json, service::DataTable,
+};
+use crate::table::models::{HookEvent, HookTiming};
+use crate::table::value::Value;
+use crate::tr;
+use chrono::{
+    DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as Json};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::{env, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl AuditOp {
+    pub fn name(self) -> &'static str {
+        match self {
+            AuditOp::Insert => "insert",
+            AuditOp::Update => "update",
+            AuditOp::Delete => "delete",
+        }
+    }
+}
+
+// Satu perubahan baris. Update hanya menyimpan kolom yang berubah;
+// insert/delete menyimpan seluruh baris
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub time: DateTime<FixedOffset>,
+    pub user: String,
+    pub op: AuditOp,
+    pub key: Json, // nilai primary (tanpa primary → null)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old: Option<Map<String, Json>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new: Option<Map<String, Json>>,
+}
+
+// Catatan yang belum ditulis ke file; dibagi dengan hook yang mencatatnya.
+// Default → audit tidak aktif
+#[derive(Clone, Default)]
+pub struct AuditLog {
+    state: Option<Arc<Mutex<AuditState>>>,
+}
+
+#[derive(Default)]
+struct AuditState {
+    pending: Vec<AuditEntry>,
+    // Passphrase file audit di disk; beda dengan tabel (encrypt/decrypt/rekey)
+    // → file ditulis ulang dengan passphrase tabel saat flush
+    file_passphrase: Option<String>,
+}
+
+impl fmt::Debug for AuditLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditLog")
+            .field("enabled", &self.is_enabled())
+            .field("pending", &self.len())
+            .finish()
+    }
+}
+
+impl AuditLog {
+    pub fn is_enabled(&self) -> bool {
+        self.state.is_some()
+    }
+
+    // Jumlah catatan yang belum ditulis
+    pub fn len(&self) -> usize {
+        self.with(|state| state.pending.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Buang catatan setelah posisi `len` (perubahan yang dibatalkan)
+    pub fn truncate(&self, len: usize) {
+        self.with(|state| state.pending.truncate(len));
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&mut AuditState) -> T) -> Option<T> {
+        let mut state = self.state.as_ref()?.lock().ok()?;
+        Some(f(&mut state))
+    }
+}
+
+// File audit di samping file tabel
+pub fn audit_path(storage_path: &str) -> String {
+    format!("{}.audit", storage_path)
+}
+
+impl DataTable {
+    // Catat setiap add_row / set_value_where / remove_row yang berhasil.
+    // Catatan ditulis ke file lewat `flush_audit` (dipanggil saat tabel disimpan)
+    pub fn enable_audit(&mut self) {
+        if self.audit.is_enabled() {
+            return;
+        }
+        let state = AuditState {
+            pending: Vec::new(),
+            file_passphrase: self.passphrase.clone(),
+        };
+        self.audit = AuditLog {
+            state: Some(Arc::new(Mutex::new(state))),
+        };
+        for event in [
+            HookEvent::AddRow,
+            HookEvent::SetValueWhere,
+            HookEvent::RemoveRow,
+        ] {
+            let log = self.audit.clone();
+            self.add_hook(HookTiming::After, event, move |ctx| {
+                let primary = ctx.columns.iter().position(|c| c.is_primary);
+                let row_json = |values: &[Value]| {
+                    ctx.columns
+                        .iter()
+                        .zip(values)
+                        .map(|(c, v)| (c.colname.clone(), value_to_json(v)))
+                        .collect::<Map<String, Json>>()
+                };
+                let entries: Vec<AuditEntry> = ctx
+                    .changes
+                    .iter()
+                    .filter_map(|change| audit_entry(change, primary, &row_json))
+                    .collect();
+                log.with(|state| state.pending.extend(entries));
+                Ok(())
+            });
+        }
+    }
+
+    // Tambahkan catatan yang tertunda ke file audit (terenkripsi jika tabel terenkripsi).
+    // Return jumlah catatan yang ditulis
+    pub fn flush_audit(&self, path: &str) -> Result<usize, TableError> {
+        let Some(state) = &self.audit.state else {
+            return Ok(0);
+        };
+        let mut state = state.lock().map_err(|e| TableError::Parse(e.to_string()))?;
+        let rekeyed = state.file_passphrase != self.passphrase;
+        if state.pending.is_empty() && !(rekeyed && Path::new(path).exists()) {
+            return Ok(0);
+        }
+
+        let mut text = match Path::new(path).exists() {
+            true => read_text(path, state.file_passphrase.as_deref())?,
+            false => String::new(),
+        };
+        for entry in &state.pending {
+            text.push_str(&serde_json::to_string(entry)?);
+            text.push('\n');
+        }
+        let data = match &self.passphrase {
+            Some(p) => crypto::encrypt_bytes(text.as_bytes(), p)?,
+            None => text.into_bytes(),
+        };
+        fs::write(path, data).map_err(TableError::io(path))?;
+
+        state.file_passphrase = self.passphrase.clone();
+        Ok(std::mem::take(&mut state.pending).len())
+    }
+
+    // Salin tabel hasil load/import ke sini; hook & audit yang terpasang tetap aktif
+    pub fn replace_keeping_hooks(&mut self, fresh: DataTable) {
+        let hooks = std::mem::take(&mut self.hooks);
+        let audit = std::mem::take(&mut self.audit);
+        *self = fresh;
+        self.hooks = hooks;
+        self.audit = audit;
+    }
+}
+
+fn audit_entry(
+    change: &RowChange,
+    primary: Option<usize>,
+    row_json: &dyn Fn(&[Value]) -> Map<String, Json>,
+) -> Option<AuditEntry> {
+    let key = primary
+        .and_then(|p| change.row()?.get(p))
+        .map(value_to_json)
+        .unwrap_or(Json::Null);
+    let (op, old, new) = match (&change.old, &change.new) {
+        (None, Some(new)) => (AuditOp::Insert, None, Some(row_json(new))),
+        (Some(old), None) => (AuditOp::Delete, Some(row_json(old)), None),
+        (Some(old), Some(new)) => {
+            let (old, new) = (row_json(old), row_json(new));
+            // Hanya kolom yang berubah
+            let changed: Vec<&String> = new.keys().filter(|k| old.get(*k) != new.get(*k)).collect();
+            if changed.is_empty() {
+                return None;
+            }
+            let pick = |row: &Map<String, Json>| {
+                changed
+                    .iter()
+                    .map(|k| ((*k).clone(), row[k.as_str()].clone()))
+                    .collect()
+            };
+            (AuditOp::Update, Some(pick(&old)), Some(pick(&new)))
+        }
+        (None, None) => return None,
+    };
+    Some(AuditEntry {
+        time: chrono::Local::now().fixed_offset(),
+        user: current_user(),
+        op,
+        key,
+        old,
+        new,
+    })
+}
+
+// Nama pengguna OS: $USER / $LOGNAME, selain itu uid
+fn current_user() -> String {
+    ["USER", "LOGNAME", "USERNAME"]
+        .iter()
+        .filter_map(|name| env::var(name).ok().filter(|v| !v.is_empty()))
+        .next()
+        .unwrap_or_else(|| {
+            // SAFETY: getuid tidak pernah gagal dan tidak menyentuh memori
+            format!("uid:{}", unsafe { libc::getuid() })
+        })
+}
+
+// Baca semua catatan audit; file belum ada → kosong
+pub fn read_audit(path: &str, passphrase: Option<&str>) -> Result<Vec<AuditEntry>, TableError> {
+    if !Path::new(path).exists() {
+        return Ok(Vec::new());
+    }
+    read_text(path, passphrase)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(TableError::from))
+        .collect()
+}
+
+fn read_text(path: &str, passphrase: Option<&str>) -> Result<String, TableError> {
+    let data = fs::read(path).map_err(TableError::io(path))?;
+    let plain = match crypto::is_encrypted(&data) {
+        true => crypto::decrypt_bytes(&data, passphrase.ok_or(TableError::PassphraseRequired)?)?,
+        false => data,
+    };
+    String::from_utf8(plain).map_err(|e| TableError::Parse(e.to_string()))
+}
+
+// Catatan satu baris (berdasarkan nilai primary), urut waktu
+pub fn row_history<'a>(entries: &'a [AuditEntry], key: &Json) -> Vec<&'a AuditEntry> {
+    entries.iter().filter(|e| &e.key == key).collect()
+}
+
+// Catatan sejak waktu tertentu (urut waktu, seperti di file)
+pub fn entries_since(
+    entries: &[AuditEntry],
+    since: Option<DateTime<FixedOffset>>,
+) -> Vec<&AuditEntry> {
+    entries
+        .iter()
+        .filter(|e| since.is_none_or(|s| e.time >= s))
+        .collect()
+}
+
+// Waktu untuk `log --since`: RFC 3339, `YYYY-MM-DD`, `YYYY-MM-DD HH:MM[:SS]` (waktu lokal),
+// atau relatif terhadap sekarang: `30m`, `2h`, `7d`
+pub fn parse_since(input: &str) -> Result<DateTime<FixedOffset>, TableError> {
+    let input = input.trim();
+    let invalid = || TableError::InvalidValue(tr!("audit.since", input));
+    if let Ok(time) = DateTime::parse_from_rfc3339(input) {
+        return Ok(time);
+    }
+
+    let local = |naive: NaiveDateTime| {
+        Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|t| t.fixed_offset())
+            .ok_or_else(invalid)
+    };
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return local(date.and_time(NaiveTime::MIN));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(input, format) {
+            return local(naive);
+        }
+    }
+
+    let split = input.len().saturating_sub(1);
+    let amount: i64 = input
+        .get(..split)
+        .and_then(|n| n.parse().ok())
+        .filter(|n| *n >= 0)
+        .ok_or_else(invalid)?;
+    let span = match &input[split..] {
+        "s" => Duration::try_seconds(amount),
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+    Ok((Local::now() - span).fixed_offset())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::value::ColumnType;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn failed_after_hook_drops_audit_entries() {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![("id", ColumnType::Numb), ("nama", ColumnType::Text)])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        table.enable_audit();
+        table
+            .add_row(vec![Value::Numb(1), Value::Text("a".into())])
+            .unwrap();
+        assert_eq!(table.audit.len(), 1);
+
+        // Hook sesudah audit yang gagal hanya saat diaktifkan
+        let fail = Arc::new(AtomicBool::new(true));
+        for event in [
+            HookEvent::AddRow,
+            HookEvent::SetValueWhere,
+            HookEvent::RemoveRow,
+        ] {
+            let fail = fail.clone();
+            table.add_hook(HookTiming::After, event, move |_| {
+                match fail.load(Ordering::SeqCst) {
+                    true => Err(TableError::InvalidValue("tolak".into())),
+                    false => Ok(()),
+                }
+            });
+        }
+
+        assert!(
+            table
+                .add_row(vec![Value::Numb(2), Value::Text("b".into())])
+                .is_err()
+        );
+        assert!(
+            table
+                .set_value_where("id", Value::Numb(1), "nama", Value::Text("x".into()))
+                .is_err()
+        );
+        assert!(table.remove_row("id", Value::Numb(1)).is_err());
+        assert_eq!(table.row.len(), 1);
+        assert_eq!(table.row[0].value[1], Value::Text("a".into()));
+        assert_eq!(
+            table.audit.len(),
+            1,
+            "catatan dari perubahan batal harus dibuang"
+        );
+
+        fail.store(false, Ordering::SeqCst);
+        table.remove_row("id", Value::Numb(1)).unwrap();
+        assert_eq!(table.audit.len(), 2);
+    }
+
+    fn audited(dir: &str) -> (DataTable, String) {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![
+                ("id", ColumnType::Numb),
+                ("nama", ColumnType::Text),
+                ("stok", ColumnType::Numb),
+            ])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        table.enable_audit();
+        let path = crate::table::handlers::test_dir(dir).join("t.json.audit");
+        (table, path.display().to_string())
+    }
+
+    #[test]
+    fn flush_menambah_catatan_dan_riwayat_per_baris() {
+        let (mut table, path) = audited("audit-flush");
+        for id in [1, 2] {
+            table
+                .add_row(vec![
+                    Value::Numb(id),
+                    Value::Text("a".into()),
+                    Value::Numb(0),
+                ])
+                .unwrap();
+        }
+        assert_eq!(table.flush_audit(&path).unwrap(), 2);
+        assert_eq!(table.flush_audit(&path).unwrap(), 0);
+
+        table
+            .set_value_where("id", Value::Numb(1), "stok", Value::Numb(5))
+            .unwrap();
+        // Nilai sama → tidak ada catatan
+        table
+            .set_value_where("id", Value::Numb(2), "nama", Value::Text("a".into()))
+            .unwrap();
+        table.remove_row("id", Value::Numb(2)).unwrap();
+        assert_eq!(table.flush_audit(&path).unwrap(), 2);
+
+        let entries = read_audit(&path, None).unwrap();
+        let ops: Vec<AuditOp> = entries.iter().map(|e| e.op).collect();
+        assert_eq!(
+            ops,
+            [
+                AuditOp::Insert,
+                AuditOp::Insert,
+                AuditOp::Update,
+                AuditOp::Delete
+            ]
+        );
+        let update = &entries[2];
+        assert_eq!(
+            update.old,
+            Some(Map::from_iter([("stok".into(), Json::from(0))]))
+        );
+        assert_eq!(
+            update.new,
+            Some(Map::from_iter([("stok".into(), Json::from(5))]))
+        );
+
+        let history = row_history(&entries, &Json::from(2));
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].op, AuditOp::Delete);
+        assert!(history[1].new.is_none());
+        let future = parse_since("2999-01-01").unwrap();
+        assert!(entries_since(&entries, Some(future)).is_empty());
+        assert_eq!(entries_since(&entries, None).len(), 4);
+    }
+
+    #[test]
+    fn file_audit_ikut_dienkripsi_saat_passphrase_berubah() {
+        let (mut table, path) = audited("audit-encrypt");
+        table
+            .add_row(vec![Value::Numb(1), Value::Text("rahasia".into())])
+            .unwrap();
+        table.flush_audit(&path).unwrap();
+
+        // Tanpa catatan baru, file lama tetap ditulis ulang dengan kunci tabel
+        table.passphrase = Some("kunci".into());
+        assert_eq!(table.flush_audit(&path).unwrap(), 0);
+        let data = fs::read(&path).unwrap();
+        assert!(crypto::is_encrypted(&data));
+        assert!(!String::from_utf8_lossy(&data).contains("rahasia"));
+        assert!(matches!(
+            read_audit(&path, None),
+            Err(TableError::PassphraseRequired)
+        ));
+        assert_eq!(read_audit(&path, Some("kunci")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn format_since() {
+        let date = parse_since("2024-03-01 10:30").unwrap();
+        assert_eq!(date.naive_local().to_string(), "2024-03-01 10:30:00");
+        let rfc = parse_since("2024-03-01T10:30:00+07:00").unwrap();
+        assert_eq!(rfc.offset().local_minus_utc(), 7 * 3600);
+        let hour_ago = parse_since("1h").unwrap();
+        let elapsed = Local::now().fixed_offset() - hour_ago;
+        assert!((Duration::minutes(59)..Duration::minutes(61)).contains(&elapsed));
+        for input in ["", "-1d", "5y", "kemarin"] {
+            assert!(parse_since(input).is_err(), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::error::TableError;
+use crate::table::handlers::service::DataTable;
+use crate::table::{
+    expr::Expr,
+    models::ColumnTable,
+    value::{ColumnType, Value},
+};
+
+impl DataTable {
+    // Tambah kolom hitung; tipe diambil dari hasil ekspresi dan semua baris langsung dihitung
+    pub fn add_computed_column(&mut self, colname: &str, expr: &str) -> Result<(), TableError> {
+        if self.column.iter().any(|c| c.colname == colname) {
+            return Err(TableError::ColumnExists(colname.into()));
+        }
+        let parsed = Expr::parse(expr)?;
+        let coltype = parsed.result_type(&self.column)?;
+
+        self.column.push(ColumnTable {
+            colname: colname.into(),
+            coltype,
+            is_primary: false,
+            _is_auto_increment: false,
+            expr: Some(expr.trim().into()),
+        });
+        let col_len = self.column.len();
+        for row in &mut self.row {
+            row.value.resize(col_len, Value::Empty);
+        }
+        self.recompute_all();
+        Ok(())
+    }
+
+    // Hitung ulang kolom hitung di semua baris
+    pub fn recompute_all(&mut self) {
+        let exprs = parse_computed(&self.column);
+        for row in &mut self.row {
+            compute_row(&self.column, &exprs, &mut row.value);
+        }
+    }
+
+    // Kolom hitung pertama yang memakai `colname`
+    pub(crate) fn computed_dependent(&self, colname: &str) -> Option<&ColumnTable> {
+        self.column.iter().find(|c| {
+            c.colname != colname
+                && c.expr
+                    .as_deref()
+                    .and_then(|e| Expr::parse(e).ok())
+                    .is_some_and(|e| e.columns().contains(&colname))
+        })
+    }
+}
+
+// Ekspresi tiap kolom, di-parse sekali untuk banyak baris.
+// None → kolom biasa, atau definisi rusak (file diedit manual)
+pub(crate) fn parse_computed(columns: &[ColumnTable]) -> Vec<Option<Expr>> {
+    columns
+        .iter()
+        .map(|c| c.expr.as_deref().and_then(|e| Expr::parse(e).ok()))
+        .collect()
+}
+
+// Isi kolom hitung satu baris sesuai urutan kolom; kolom hitung hanya boleh memakai
+// kolom sebelumnya, jadi satu putaran cukup. `exprs` dari `parse_computed(columns)`
+pub(crate) fn compute_row(columns: &[ColumnTable], exprs: &[Option<Expr>], values: &mut [Value]) {
+    for (i, col) in columns.iter().enumerate() {
+        if col.expr.is_none() {
+            continue;
+        }
+        // Definisi rusak → Empty
+        let value = match &exprs[i] {
+            Some(expr) => expr.eval(columns, values),
+            None => Value::Empty,
+        };
+        values[i] = match (value, &col.coltype) {
+            (Value::Char(c), ColumnType::Text) => Value::Text(c.to_string()),
+            (value, coltype) if DataTable::_validate_type_column_and_row(coltype, &value) => value,
+            _ => Value::Empty,
+        };
+    }
+}
@@ -0,0 +1,190 @@
+use crate::error::TableError;
+use crate::table::{handlers::service::DataTable, models::TriggerAction};
+use crate::tr;
+use argon2::Argon2;
+use chacha20poly1305::{
+    ChaCha20Poly1305, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, rand_core::RngCore},
+};
+use std::env;
+
+// Nama env var untuk passphrase (dipakai jika tidak ada prompt)
+pub const PASSPHRASE_ENV: &str = "MYTABEL_PASSPHRASE";
+// Nama env var untuk passphrase baru saat rekey
+pub const NEW_PASSPHRASE_ENV: &str = "MYTABEL_NEW_PASSPHRASE";
+
+// Format file terenkripsi: MAGIC | salt (16) | nonce (12) | ciphertext+tag
+const MAGIC: &[u8] = b"MYTABEL-ENC1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+// Cek apakah isi file adalah tabel terenkripsi
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+// Ambil passphrase dari env var, jika ada dan tidak kosong
+pub fn passphrase_from_env(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|p| !p.is_empty())
+}
+
+// Turunkan kunci 256-bit dari passphrase + salt (Argon2id)
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, TableError> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| TableError::Crypto(tr!("crypto.derive", e)))?;
+    Ok(Key::from(key))
+}
+
+pub fn encrypt_bytes(plain: &[u8], passphrase: &str) -> Result<Vec<u8>, TableError> {
+    if passphrase.is_empty() {
+        return Err(TableError::Crypto(tr!("crypto.empty")));
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(passphrase, &salt)?;
+
+    let cipher = ChaCha20Poly1305::new(&key);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plain)
+        .map_err(|_| TableError::Crypto(tr!("crypto.encrypt_failed")))?;
+
+    let mut out = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+pub fn decrypt_bytes(data: &[u8], passphrase: &str) -> Result<Vec<u8>, TableError> {
+    if !is_encrypted(data) || data.len() < MAGIC.len() + SALT_LEN + NONCE_LEN {
+        return Err(TableError::Crypto(tr!("crypto.bad_format")));
+    }
+
+    let rest = &data[MAGIC.len()..];
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let key = derive_key(passphrase, salt)?;
+    let cipher = ChaCha20Poly1305::new(&key);
+
+    // Tag autentikasi gagal → passphrase salah atau file diubah
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| TableError::WrongPassphrase)
+}
+
+impl DataTable {
+    // Aktifkan enkripsi; berlaku saat save berikutnya
+    pub fn encrypt(&mut self, passphrase: &str) -> Result<(), TableError> {
+        if self.passphrase.is_some() {
+            return Err(TableError::Crypto(tr!("crypto.already")));
+        }
+        if passphrase.is_empty() {
+            return Err(TableError::Crypto(tr!("crypto.empty")));
+        }
+        if let Some(trigger) = self
+            .triggers
+            .iter()
+            .find(|t| matches!(t.action, TriggerAction::Log { .. }))
+        {
+            return Err(TableError::Crypto(tr!(
+                "trigger.log_encrypted",
+                trigger.name
+            )));
+        }
+        self.passphrase = Some(passphrase.to_string());
+        Ok(())
+    }
+
+    // Nonaktifkan enkripsi; save berikutnya menulis JSON biasa
+    pub fn decrypt(&mut self) -> Result<(), TableError> {
+        if self.passphrase.take().is_none() {
+            return Err(TableError::Crypto(tr!("crypto.not_encrypted")));
+        }
+        Ok(())
+    }
+
+    // Ganti passphrase
+    pub fn rekey(&mut self, new_passphrase: &str) -> Result<(), TableError> {
+        if self.passphrase.is_none() {
+            return Err(TableError::Crypto(tr!("crypto.not_encrypted_rekey")));
+        }
+        if new_passphrase.is_empty() {
+            return Err(TableError::Crypto(tr!("crypto.empty")));
+        }
+        self.passphrase = Some(new_passphrase.to_string());
+        Ok(())
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.passphrase.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_then_decrypt_returns_original() {
+        let plain = br#"{"column":[],"row":[]}"#;
+        let data = encrypt_bytes(plain, "rahasia").unwrap();
+        assert!(is_encrypted(&data));
+        assert!(!data.windows(plain.len()).any(|w| w == plain));
+        assert_eq!(decrypt_bytes(&data, "rahasia").unwrap(), plain);
+    }
+
+    #[test]
+    fn same_input_gives_different_ciphertext() {
+        let a = encrypt_bytes(b"isi", "rahasia").unwrap();
+        let b = encrypt_bytes(b"isi", "rahasia").unwrap();
+        assert_ne!(a, b, "salt & nonce harus acak");
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let data = encrypt_bytes(b"isi", "rahasia").unwrap();
+        assert!(matches!(
+            decrypt_bytes(&data, "salah"),
+            Err(TableError::WrongPassphrase)
+        ));
+    }
+
+    #[test]
+    fn tampered_data_is_rejected() {
+        let mut data = encrypt_bytes(b"isi", "rahasia").unwrap();
+        *data.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            decrypt_bytes(&data, "rahasia"),
+            Err(TableError::WrongPassphrase)
+        ));
+    }
+
+    #[test]
+    fn plain_or_truncated_data_is_bad_format() {
+        assert!(matches!(
+            decrypt_bytes(b"{}", "rahasia"),
+            Err(TableError::Crypto(_))
+        ));
+        assert!(matches!(
+            decrypt_bytes(MAGIC, "rahasia"),
+            Err(TableError::Crypto(_))
+        ));
+    }
+
+    #[test]
+    fn empty_passphrase_is_rejected() {
+        assert!(matches!(
+            encrypt_bytes(b"isi", ""),
+            Err(TableError::Crypto(_))
+        ));
+        let mut table = DataTable::default();
+        assert!(table.encrypt("").is_err());
+        assert!(!table.is_encrypted());
+    }
+}
@@ -0,0 +1,431 @@
+use crate::error::TableError;
+use crate::table::{
+    handlers::service::DataTable,
+    models::{ColumnTable, RowTable},
+    value::{ColumnType, Value},
+};
+use std::collections::{HashMap, HashSet};
+
+// Ringkasan baris yang berubah antara dua versi tabel; baris dikenali lewat
+// nilai primary (tanpa primary → nomor baris)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangeSummary {
+    pub added: Vec<Value>,
+    pub removed: Vec<Value>,
+    pub modified: Vec<Value>,
+}
+
+impl ChangeSummary {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+// Perbedaan lengkap dua versi tabel: skema lalu baris per sel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableDiff {
+    pub schema: Vec<SchemaChange>,
+    pub added: Vec<KeyedRow>,
+    pub removed: Vec<KeyedRow>,
+    pub modified: Vec<RowDiff>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaChange {
+    ColumnAdded {
+        column: String,
+        coltype: ColumnType,
+    },
+    ColumnRemoved {
+        column: String,
+        coltype: ColumnType,
+    },
+    TypeChanged {
+        column: String,
+        old: ColumnType,
+        new: ColumnType,
+    },
+    // Ekspresi kolom hitung (None → kolom biasa)
+    ExprChanged {
+        column: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    PrimaryChanged {
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+// Isi satu baris per nama kolom (kolom Empty dilewati)
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyedRow {
+    pub key: Value,
+    pub cells: Vec<(String, Value)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowDiff {
+    pub key: Value,
+    pub cells: Vec<CellChange>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellChange {
+    pub column: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+impl TableDiff {
+    pub fn is_empty(&self) -> bool {
+        self.schema.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+    }
+}
+
+impl DataTable {
+    // Bandingkan tabel ini (versi baru) dengan `before`
+    pub fn change_summary(&self, before: &DataTable) -> Result<ChangeSummary, TableError> {
+        let diff = self.diff(before)?;
+        let keys = |rows: Vec<KeyedRow>| rows.into_iter().map(|r| r.key).collect();
+        Ok(ChangeSummary {
+            added: keys(diff.added),
+            removed: keys(diff.removed),
+            modified: diff.modified.into_iter().map(|r| r.key).collect(),
+        })
+    }
+
+    // Perbedaan tabel ini (versi baru) terhadap `before`. Sel dicocokkan lewat nama
+    // kolom; kolom yang tidak ada di salah satu versi dianggap Empty, jadi kolom baru
+    // yang masih kosong tidak membuat semua baris dianggap berubah
+    pub fn diff(&self, before: &DataTable) -> Result<TableDiff, TableError> {
+        let new = self.keyed_rows();
+        let old = before.keyed_rows();
+        check_keys(new.iter().map(|r| &r.key))?;
+        check_keys(old.iter().map(|r| &r.key))?;
+        let old_rows: HashMap<&Value, &KeyedRow> = old.iter().map(|r| (&r.key, r)).collect();
+        let new_keys: HashSet<&Value> = new.iter().map(|r| &r.key).collect();
+
+        let mut diff = TableDiff {
+            schema: schema_changes(&before.column, &self.column),
+            ..TableDiff::default()
+        };
+        for row in &new {
+            match old_rows.get(&row.key) {
+                None => diff.added.push(row.clone()),
+                Some(old_row) => {
+                    let cells = cell_changes(old_row, row);
+                    if !cells.is_empty() {
+                        diff.modified.push(RowDiff {
+                            key: row.key.clone(),
+                            cells,
+                        });
+                    }
+                }
+            }
+        }
+        diff.removed = old
+            .into_iter()
+            .filter(|row| !new_keys.contains(&row.key))
+            .collect();
+        Ok(diff)
+    }
+
+    fn keyed_rows(&self) -> Vec<KeyedRow> {
+        self.row
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let key = match self.primary_index.and_then(|p| row.value.get(p)) {
+                    Some(value) => value.clone(),
+                    None => Value::Numb(i as i64 + 1),
+                };
+                KeyedRow {
+                    key,
+                    cells: self.non_empty_cells(row),
+                }
+            })
+            .collect()
+    }
+
+    fn non_empty_cells(&self, row: &RowTable) -> Vec<(String, Value)> {
+        self.column
+            .iter()
+            .zip(&row.value)
+            .filter(|(_, value)| !matches!(value, Value::Empty))
+            .map(|(col, value)| (col.colname.clone(), value.clone()))
+            .collect()
+    }
+}
+
+// Kunci baris harus unik & tidak Empty; kalau tidak, baris bisa tertukar diam-diam
+pub(crate) fn check_keys<'a>(keys: impl IntoIterator<Item = &'a Value>) -> Result<(), TableError> {
+    let mut seen = HashSet::new();
+    for key in keys {
+        if matches!(key, Value::Empty) {
+            return Err(TableError::EmptyPrimary);
+        }
+        if !seen.insert(key) {
+            return Err(TableError::DuplicatePrimary);
+        }
+    }
+    Ok(())
+}
+
+fn schema_changes(old: &[ColumnTable], new: &[ColumnTable]) -> Vec<SchemaChange> {
+    let find = |columns: &'_ [ColumnTable], name: &str| {
+        columns.iter().find(|c| c.colname == name).cloned()
+    };
+    let mut changes = Vec::new();
+    for col in new {
+        match find(old, &col.colname) {
+            None => changes.push(SchemaChange::ColumnAdded {
+                column: col.colname.clone(),
+                coltype: col.coltype.clone(),
+            }),
+            Some(prev) => {
+                if prev.coltype != col.coltype {
+                    changes.push(SchemaChange::TypeChanged {
+                        column: col.colname.clone(),
+                        old: prev.coltype,
+                        new: col.coltype.clone(),
+                    });
+                }
+                if prev.expr != col.expr {
+                    changes.push(SchemaChange::ExprChanged {
+                        column: col.colname.clone(),
+                        old: prev.expr,
+                        new: col.expr.clone(),
+                    });
+                }
+            }
+        }
+    }
+    for col in old {
+        if find(new, &col.colname).is_none() {
+            changes.push(SchemaChange::ColumnRemoved {
+                column: col.colname.clone(),
+                coltype: col.coltype.clone(),
+            });
+        }
+    }
+
+    let primary = |columns: &[ColumnTable]| {
+        columns
+            .iter()
+            .find(|c| c.is_primary)
+            .map(|c| c.colname.clone())
+    };
+    let (old_primary, new_primary) = (primary(old), primary(new));
+    if old_primary != new_primary {
+        changes.push(SchemaChange::PrimaryChanged {
+            old: old_primary,
+            new: new_primary,
+        });
+    }
+    changes
+}
+
+// Sel yang berbeda, urut kolom versi baru lalu kolom yang hanya ada di versi lama
+fn cell_changes(old: &KeyedRow, new: &KeyedRow) -> Vec<CellChange> {
+    let get = |row: &KeyedRow, column: &str| {
+        row.cells
+            .iter()
+            .find(|(c, _)| c == column)
+            .map(|(_, v)| v.clone())
+            .unwrap_or(Value::Empty)
+    };
+    let mut columns: Vec<&String> = new.cells.iter().map(|(c, _)| c).collect();
+    for (c, _) in &old.cells {
+        if !columns.contains(&c) {
+            columns.push(c);
+        }
+    }
+    columns
+        .into_iter()
+        .filter_map(|column| {
+            let (before, after) = (get(old, column), get(new, column));
+            (before != after).then(|| CellChange {
+                column: column.clone(),
+                old: before,
+                new: after,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numb(n: i64) -> Value {
+        Value::Numb(n)
+    }
+
+    fn text(s: &str) -> Value {
+        Value::Text(s.into())
+    }
+
+    // Tabel dengan kolom pertama sebagai primary
+    fn table(columns: &[(&str, ColumnType)], rows: &[Vec<Value>]) -> DataTable {
+        let mut table = DataTable::default();
+        table.add_column(columns.to_vec()).unwrap();
+        table.set_primary(columns[0].0).unwrap();
+        for row in rows {
+            table.add_row(row.clone()).unwrap();
+        }
+        table
+    }
+
+    fn id_nama(rows: &[(i64, &str)]) -> DataTable {
+        let rows: Vec<Vec<Value>> = rows
+            .iter()
+            .map(|(id, n)| vec![numb(*id), text(n)])
+            .collect();
+        table(
+            &[("id", ColumnType::Numb), ("nama", ColumnType::Text)],
+            &rows,
+        )
+    }
+
+    #[test]
+    fn same_table_has_no_diff() {
+        let t = id_nama(&[(1, "a"), (2, "b")]);
+        assert!(t.diff(&t).unwrap().is_empty());
+        assert!(t.change_summary(&t).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rows_are_matched_by_primary() {
+        let before = id_nama(&[(1, "a"), (2, "b"), (3, "c")]);
+        let after = id_nama(&[(3, "c"), (1, "A"), (4, "d")]);
+        let diff = after.diff(&before).unwrap();
+
+        assert!(diff.schema.is_empty());
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].key, numb(4));
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].key, numb(2));
+        assert_eq!(
+            diff.modified,
+            vec![RowDiff {
+                key: numb(1),
+                cells: vec![CellChange {
+                    column: "nama".into(),
+                    old: text("a"),
+                    new: text("A"),
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn added_column_only_changes_filled_cells() {
+        let before = id_nama(&[(1, "a"), (2, "b")]);
+        let after = table(
+            &[
+                ("id", ColumnType::Numb),
+                ("nama", ColumnType::Text),
+                ("stok", ColumnType::Numb),
+            ],
+            &[
+                vec![numb(1), text("a"), numb(5)],
+                vec![numb(2), text("b"), Value::Empty],
+            ],
+        );
+        let diff = after.diff(&before).unwrap();
+
+        assert_eq!(
+            diff.schema,
+            vec![SchemaChange::ColumnAdded {
+                column: "stok".into(),
+                coltype: ColumnType::Numb,
+            }]
+        );
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].key, numb(1));
+        assert_eq!(
+            diff.modified[0].cells,
+            vec![CellChange {
+                column: "stok".into(),
+                old: Value::Empty,
+                new: numb(5),
+            }]
+        );
+    }
+
+    #[test]
+    fn removed_column_shows_old_values() {
+        let before = id_nama(&[(1, "a")]);
+        let after = table(&[("id", ColumnType::Numb)], &[vec![numb(1)]]);
+        let diff = after.diff(&before).unwrap();
+
+        assert_eq!(
+            diff.schema,
+            vec![SchemaChange::ColumnRemoved {
+                column: "nama".into(),
+                coltype: ColumnType::Text,
+            }]
+        );
+        assert_eq!(
+            diff.modified[0].cells,
+            vec![CellChange {
+                column: "nama".into(),
+                old: text("a"),
+                new: Value::Empty,
+            }]
+        );
+    }
+
+    #[test]
+    fn type_and_primary_changes_are_reported() {
+        let before = id_nama(&[]);
+        let after = table(&[("nama", ColumnType::Text), ("id", ColumnType::Text)], &[]);
+        let diff = after.diff(&before).unwrap();
+        assert!(diff.schema.contains(&SchemaChange::TypeChanged {
+            column: "id".into(),
+            old: ColumnType::Numb,
+            new: ColumnType::Text,
+        }));
+        assert!(diff.schema.contains(&SchemaChange::PrimaryChanged {
+            old: Some("id".into()),
+            new: Some("nama".into()),
+        }));
+    }
+
+    #[test]
+    fn without_primary_rows_are_matched_by_position() {
+        let mut before = DataTable::default();
+        before.add_column(vec![("nama", ColumnType::Text)]).unwrap();
+        let mut after = before.clone();
+        before.row.push(RowTable {
+            value: vec![text("a")],
+        });
+        after.row.push(RowTable {
+            value: vec![text("b")],
+        });
+        let diff = after.diff(&before).unwrap();
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].key, numb(1));
+    }
+
+    #[test]
+    fn duplicate_or_empty_keys_are_rejected() {
+        let good = id_nama(&[(1, "a")]);
+        let mut dup = good.clone();
+        dup.row.push(RowTable {
+            value: vec![numb(1), text("dup")],
+        });
+        assert!(matches!(dup.diff(&good), Err(TableError::DuplicatePrimary)));
+        assert!(matches!(good.diff(&dup), Err(TableError::DuplicatePrimary)));
+
+        let mut empty = good.clone();
+        empty.row.push(RowTable {
+            value: vec![Value::Empty, text("x")],
+        });
+        assert!(matches!(empty.diff(&good), Err(TableError::EmptyPrimary)));
+    }
+}
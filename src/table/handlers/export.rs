@@ -0,0 +1,315 @@
+use crate::error::TableError;
+use crate::table::{
+    handlers::service::DataTable,
+    models::RowTable,
+    value::{ColumnType, Value},
+};
+use crate::tr;
+use std::fs;
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Table,    // box characters (seperti show_data_table)
+    Markdown, // GitHub Markdown
+    Html,     // dokumen HTML mandiri
+    Plain,    // teks rata kolom tanpa border
+}
+
+impl ExportFormat {
+    pub fn parse(input: &str) -> Result<Self, TableError> {
+        match input.to_lowercase().as_str() {
+            "table" | "box" => Ok(ExportFormat::Table),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "html" | "htm" => Ok(ExportFormat::Html),
+            "text" | "txt" | "plain" => Ok(ExportFormat::Plain),
+            _ => Err(TableError::InvalidOption(tr!("opt.export_format", input))),
+        }
+    }
+}
+
+impl DataTable {
+    // Render sebagian baris (mis. hasil filter)
+    pub fn render_rows(&self, rows: &[&RowTable], format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Table => self.pretty_table(rows).to_string(),
+            ExportFormat::Markdown => self.render_markdown(rows),
+            ExportFormat::Html => self.render_html(rows),
+            ExportFormat::Plain => self.render_plain(rows),
+        }
+    }
+
+    // Tulis ke file, atau stdout jika path None
+    pub fn export_rows(
+        &self,
+        rows: &[&RowTable],
+        format: ExportFormat,
+        path: Option<&str>,
+    ) -> Result<(), TableError> {
+        match path {
+            Some(path) => {
+                fs::write(path, self.render_rows(rows, format)).map_err(TableError::io(path))
+            }
+            None if format == ExportFormat::Table => {
+                // stdout: pakai printstd agar warna header tetap tampil
+                self.pretty_table(rows).printstd();
+                Ok(())
+            }
+            None => {
+                print!("{}", self.render_rows(rows, format));
+                Ok(())
+            }
+        }
+    }
+
+    fn render_markdown(&self, rows: &[&RowTable]) -> String {
+        // `|` memecah sel, `<` bisa dibaca sebagai tag HTML oleh GitHub
+        let escape = |s: &str| {
+            s.replace('|', "\\|")
+                .replace('<', "&lt;")
+                .replace('\n', "<br>")
+        };
+
+        let header: Vec<String> = self
+            .column
+            .iter()
+            .map(|col| {
+                if col.is_primary {
+                    format!("**{}**", escape(&col.colname))
+                } else {
+                    escape(&col.colname)
+                }
+            })
+            .collect();
+
+        // Numb rata kanan, lainnya rata kiri
+        let align: Vec<&str> = self
+            .column
+            .iter()
+            .map(|col| match col.coltype {
+                ColumnType::Numb => "---:",
+                _ => "---",
+            })
+            .collect();
+
+        let mut out = String::new();
+        out.push_str(&format!("| {} |\n", header.join(" | ")));
+        out.push_str(&format!("| {} |\n", align.join(" | ")));
+        for row in rows {
+            let cells: Vec<String> = row.value.iter().map(|v| escape(&v.to_string())).collect();
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        out
+    }
+
+    fn render_html(&self, rows: &[&RowTable]) -> String {
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!(
+            "<title>{}</title>\n<style>\n",
+            html_escape(&tr!("export.html_title"))
+        ));
+        out.push_str("table { border-collapse: collapse; font-family: sans-serif; }\n");
+        out.push_str("th, td { border: 1px solid #999; padding: 4px 8px; }\n");
+        out.push_str("th { background: #eee; }\n");
+        out.push_str(".primary { background: #fff3b0; font-weight: bold; }\n");
+        out.push_str(".numb { text-align: right; }\n");
+        out.push_str(".empty { color: #999; }\n");
+        out.push_str("</style>\n</head>\n<body>\n<table>\n<thead>\n<tr>");
+
+        for col in &self.column {
+            let class = if col.is_primary {
+                " class=\"primary\""
+            } else {
+                ""
+            };
+            out.push_str(&format!("<th{}>{}</th>", class, html_escape(&col.colname)));
+        }
+        out.push_str("</tr>\n</thead>\n<tbody>\n");
+
+        for row in rows {
+            out.push_str("<tr>");
+            for (i, val) in row.value.iter().enumerate() {
+                let mut classes = Vec::new();
+                if self.primary_index == Some(i) {
+                    classes.push("primary");
+                }
+                if matches!(val, Value::Numb(_)) {
+                    classes.push("numb");
+                }
+                if matches!(val, Value::Empty) {
+                    classes.push("empty");
+                }
+                let class = if classes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" class=\"{}\"", classes.join(" "))
+                };
+                out.push_str(&format!(
+                    "<td{}>{}</td>",
+                    class,
+                    html_escape(&val.to_string())
+                ));
+            }
+            out.push_str("</tr>\n");
+        }
+
+        out.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+        out
+    }
+
+    fn render_plain(&self, rows: &[&RowTable]) -> String {
+        let header: Vec<String> = self
+            .column
+            .iter()
+            .map(|col| {
+                if col.is_primary {
+                    format!("*{}", col.colname)
+                } else {
+                    col.colname.clone()
+                }
+            })
+            .collect();
+        let body: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| {
+                row.value
+                    .iter()
+                    .map(|v| v.to_string().replace('\n', " "))
+                    .collect()
+            })
+            .collect();
+
+        // Lebar kolom = sel terlebar (header ikut dihitung)
+        let mut widths: Vec<usize> = header.iter().map(|h| h.width()).collect();
+        for cells in &body {
+            for (i, cell) in cells.iter().enumerate() {
+                if i < widths.len() {
+                    widths[i] = widths[i].max(cell.width());
+                }
+            }
+        }
+
+        let line = |cells: &[String]| -> String {
+            let padded: Vec<String> = cells
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    let pad = widths
+                        .get(i)
+                        .copied()
+                        .unwrap_or(0)
+                        .saturating_sub(cell.width());
+                    let numb = matches!(
+                        self.column.get(i).map(|c| &c.coltype),
+                        Some(ColumnType::Numb)
+                    );
+                    if numb {
+                        format!("{}{}", " ".repeat(pad), cell)
+                    } else {
+                        format!("{}{}", cell, " ".repeat(pad))
+                    }
+                })
+                .collect();
+            format!("{}\n", padded.join("  ").trim_end())
+        };
+
+        let mut out = line(&header);
+        let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        out.push_str(&line(&separator));
+        for cells in &body {
+            out.push_str(&line(cells));
+        }
+        out
+    }
+}
+
+fn html_escape(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // id (primary), nama, stok
+    fn table() -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![
+                ("id", ColumnType::Numb),
+                ("nama", ColumnType::Text),
+                ("stok", ColumnType::Numb),
+            ])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        for (id, nama, stok) in [(1, "a|b <i>", 5), (20, "kopi\nsusu", 120)] {
+            table
+                .add_row(vec![
+                    Value::Numb(id),
+                    Value::Text(nama.into()),
+                    Value::Numb(stok),
+                ])
+                .unwrap();
+        }
+        table
+    }
+
+    fn render(table: &DataTable, format: ExportFormat) -> String {
+        let rows: Vec<&RowTable> = table.row.iter().collect();
+        table.render_rows(&rows, format)
+    }
+
+    #[test]
+    fn markdown_escapes_cells_and_aligns_numbers() {
+        let out = render(&table(), ExportFormat::Markdown);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "| **id** | nama | stok |");
+        assert_eq!(lines[1], "| ---: | --- | ---: |");
+        assert_eq!(lines[2], "| 1 | a\\|b &lt;i> | 5 |");
+        assert_eq!(lines[3], "| 20 | kopi<br>susu | 120 |");
+    }
+
+    #[test]
+    fn html_escapes_and_marks_primary() {
+        let out = render(&table(), ExportFormat::Html);
+        assert!(out.starts_with("<!DOCTYPE html>"));
+        assert!(out.contains("<th class=\"primary\">id</th><th>nama</th>"));
+        assert!(out.contains("<td class=\"primary numb\">1</td><td>a|b &lt;i&gt;</td>"));
+        assert!(!out.contains("<i>"));
+        assert_eq!(html_escape("\"'&"), "&quot;&#39;&amp;");
+    }
+
+    #[test]
+    fn plain_pads_by_display_width() {
+        let mut table = table();
+        table.row[0].value[1] = Value::Text("日本".into());
+        let out = render(&table, ExportFormat::Plain);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "*id  nama       stok");
+        assert_eq!(lines[1], "---  ---------  ----");
+        assert_eq!(lines[2], "  1  日本          5");
+        assert_eq!(lines[3], " 20  kopi susu   120");
+    }
+
+    #[test]
+    fn format_names() {
+        assert_eq!(ExportFormat::parse("MD").unwrap(), ExportFormat::Markdown);
+        assert_eq!(ExportFormat::parse("txt").unwrap(), ExportFormat::Plain);
+        assert!(matches!(
+            ExportFormat::parse("pdf"),
+            Err(TableError::InvalidOption(_))
+        ));
+    }
+}
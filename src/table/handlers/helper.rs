@@ -1,28 +1,132 @@
+use crate::error::TableError;
 use crate::table::{
-    handlers::service::DataTable,
+    handlers::{
+        crypto::{self, PASSPHRASE_ENV},
+        service::DataTable,
+    },
+    models::RowTable,
     value::{ColumnType, Value},
 };
+use crate::tr;
+use std::cell::RefCell;
 use std::collections::HashSet;
 use std::fs;
+use std::path::Path;
+
+// Hapus semua `path.bak.N`
+fn remove_backups(path: &str) -> Result<(), TableError> {
+    let file = Path::new(path);
+    let dir = match file.parent() {
+        Some(d) if !d.as_os_str().is_empty() => d,
+        _ => Path::new("."),
+    };
+    let Some(prefix) = file
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| format!("{}.bak.", n))
+    else {
+        return Ok(());
+    };
+    for entry in fs::read_dir(dir).map_err(TableError::io(path))? {
+        let backup = entry.map_err(TableError::io(path))?.path();
+        let is_backup = backup
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_prefix(&prefix))
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        if is_backup {
+            fs::remove_file(&backup).map_err(TableError::io(&backup.display().to_string()))?;
+        }
+    }
+    Ok(())
+}
 
 impl DataTable {
-    pub fn save(&self, path: &str) -> Result<(), String> {
-        let json =
-            serde_json::to_string_pretty(self).map_err(|e| format!("Gagal serialisasi: {}", e))?;
-        fs::write(path, json).map_err(|e| format!("Gagal tulis file: {}", e))
+    pub fn save(&self, path: &str) -> Result<(), TableError> {
+        let json = serde_json::to_string_pretty(self)?;
+        // Enkripsi jika passphrase aktif
+        let data = match &self.passphrase {
+            Some(p) => crypto::encrypt_bytes(json.as_bytes(), p)?,
+            None => json.into_bytes(),
+        };
+        fs::write(path, data).map_err(TableError::io(path))
     }
 
-    pub fn load(path: &str) -> Result<Self, String> {
-        let data = fs::read_to_string(path).map_err(|e| format!("Gagal baca file: {}", e))?;
-        let table: DataTable =
-            serde_json::from_str(&data).map_err(|e| format!("Gagal parse JSON: {}", e))?;
+    // Simpan dengan rotasi backup: `path.bak.1` (terbaru) … `path.bak.N`.
+    // Isi tidak berubah → file tidak ditulis ulang agar backup tidak tergeser sia-sia.
+    // Status enkripsi berubah (encrypt/decrypt/rekey) → semua backup lama dihapus, agar
+    // salinan plaintext tidak tertinggal di samping tabel terenkripsi
+    pub fn save_with_backups(&self, path: &str, keep: usize) -> Result<(), TableError> {
+        if !Path::new(path).exists() {
+            return self.save(path);
+        }
+        let Some(on_disk) = self.read_same_key(path) else {
+            remove_backups(path)?;
+            return self.save(path);
+        };
+        if keep == 0 {
+            return self.save(path);
+        }
+        if on_disk == serde_json::to_string_pretty(self)?.as_bytes() {
+            return Ok(());
+        }
+
+        let backup = |n: usize| format!("{}.bak.{}", path, n);
+        for n in (1..keep).rev() {
+            let from = backup(n);
+            if Path::new(&from).exists() {
+                fs::rename(&from, backup(n + 1)).map_err(TableError::io(&from))?;
+            }
+        }
+        fs::copy(path, backup(1)).map_err(TableError::io(path))?;
+        self.save(path)
+    }
+
+    // Isi file di disk (plaintext) jika ditulis dengan status enkripsi & passphrase
+    // yang sama dengan tabel ini; None → berbeda atau tidak bisa dibaca
+    fn read_same_key(&self, path: &str) -> Option<Vec<u8>> {
+        let data = fs::read(path).ok()?;
+        match (&self.passphrase, crypto::is_encrypted(&data)) {
+            (None, false) => Some(data),
+            (Some(p), true) => crypto::decrypt_bytes(&data, p).ok(),
+            _ => None,
+        }
+    }
+
+    // Load dengan passphrase dari env var (jika file terenkripsi)
+    pub fn load(path: &str) -> Result<Self, TableError> {
+        let passphrase = crypto::passphrase_from_env(PASSPHRASE_ENV);
+        Self::load_with_passphrase(path, passphrase.as_deref())
+    }
+
+    pub fn load_with_passphrase(path: &str, passphrase: Option<&str>) -> Result<Self, TableError> {
+        let data = fs::read(path).map_err(TableError::io(path))?;
+
+        if !crypto::is_encrypted(&data) {
+            let table: DataTable = serde_json::from_slice(&data)?;
+            return Ok(table);
+        }
+
+        // File terenkripsi: jangan pernah jatuh ke tabel kosong
+        let passphrase = passphrase.ok_or(TableError::PassphraseRequired)?;
+        let plain = crypto::decrypt_bytes(&data, passphrase)?;
+        let mut table: DataTable = serde_json::from_slice(&plain)?;
+        table.passphrase = Some(passphrase.to_string());
+        table.snapshot_passphrase = RefCell::new(table.passphrase.clone());
         Ok(table)
     }
+
+    // Cek apakah file tabel di disk terenkripsi
+    pub fn is_encrypted_file(path: &str) -> bool {
+        fs::read(path)
+            .map(|data| crypto::is_encrypted(&data))
+            .unwrap_or(false)
+    }
     pub fn _parse_input_to_value_type(
         &self,
         col_index: usize,
         input: &str,
-    ) -> Result<Value, String> {
+    ) -> Result<Value, TableError> {
         let col_type = &self.column[col_index].coltype;
         match col_type {
             ColumnType::Text => Ok(Value::Text(input.into())),
@@ -30,27 +134,27 @@ impl DataTable {
                 if input.len() == 1 {
                     Ok(Value::Char(input.chars().next().unwrap()))
                 } else {
-                    Err("Char harus 1 karakter".into())
+                    Err(TableError::InvalidValue(tr!("val.char_len")))
                 }
             }
             ColumnType::Numb => input
                 .parse::<i64>()
                 .map(Value::Numb)
-                .map_err(|_| format!("Numb harus angka: '{}'", input)),
+                .map_err(|_| TableError::InvalidValue(tr!("val.numb", input))),
             ColumnType::Bool => match input.to_lowercase().as_str() {
                 "true" | "1" => Ok(Value::Bool(true)),
                 "false" | "0" => Ok(Value::Bool(false)),
-                _ => Err("Bool harus true/false atau 1/0".into()),
+                _ => Err(TableError::InvalidValue(tr!("val.bool"))),
             },
         }
     }
 
     // Validasi kandidat primary
-    pub fn _validate_primary_candidate(&self, col_index: usize) -> Result<(), String> {
+    pub fn _validate_primary_candidate(&self, col_index: usize) -> Result<(), TableError> {
         // 1. Tidak boleh Empty
         for row in &self.row {
             if matches!(row.value[col_index], Value::Empty) {
-                return Err("PRIMARY: ada nilai Empty".into());
+                return Err(TableError::EmptyPrimary);
             }
         }
 
@@ -58,7 +162,7 @@ impl DataTable {
         let mut set: HashSet<Value> = HashSet::new();
         for row in &self.row {
             if !set.insert(row.value[col_index].clone()) {
-                return Err("PRIMARY: nilai duplikat".into());
+                return Err(TableError::DuplicatePrimary);
             }
         }
 
@@ -68,7 +172,9 @@ impl DataTable {
                 &self.column[col_index].coltype,
                 &row.value[col_index],
             ) {
-                return Err("PRIMARY: tipe tidak cocok".into());
+                return Err(TableError::TypeMismatch(
+                    self.column[col_index].colname.clone(),
+                ));
             }
         }
 
@@ -96,13 +202,94 @@ impl DataTable {
     pub fn _get_column_is_primary_active(&self) -> Option<&str> {
         self.column
             .iter()
-            .find_map(|c| c.is_primary.then(|| c.colname.as_str()))
+            .find_map(|c| c.is_primary.then_some(c.colname.as_str()))
     }
 
     // Ambil kolom auto_increment
     pub fn _get_column_is_increment_active(&self) -> Option<&str> {
         self.column
             .iter()
-            .find_map(|c| c._is_auto_increment.then(|| c.colname.as_str()))
+            .find_map(|c| c._is_auto_increment.then_some(c.colname.as_str()))
+    }
+
+    // Ambil baris yang nilai kolomnya sama dengan `value`
+    pub fn rows_where(&self, colname: &str, value: &Value) -> Result<Vec<&RowTable>, TableError> {
+        let index = self
+            .column
+            .iter()
+            .position(|c| c.colname == colname)
+            .ok_or_else(|| TableError::ColumnNotFound(colname.into()))?;
+
+        Ok(self
+            .row
+            .iter()
+            .filter(|row| row.value.get(index) == Some(value))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::handlers::test_dir;
+
+    fn table(name: &str) -> DataTable {
+        let mut table = DataTable::default();
+        table.add_column(vec![("nama", ColumnType::Text)]).unwrap();
+        table.row.push(RowTable {
+            value: vec![Value::Text(name.into())],
+        });
+        table
+    }
+
+    fn backups(path: &str) -> Vec<Vec<u8>> {
+        (1..10)
+            .map_while(|n| fs::read(format!("{}.bak.{}", path, n)).ok())
+            .collect()
+    }
+
+    #[test]
+    fn backups_rotate_and_unchanged_table_is_not_rewritten() {
+        let dir = test_dir("backup-rotate");
+        let path = dir.join("t.json").display().to_string();
+        table("a").save_with_backups(&path, 2).unwrap();
+        table("b").save_with_backups(&path, 2).unwrap();
+        table("b").save_with_backups(&path, 2).unwrap();
+        table("c").save_with_backups(&path, 2).unwrap();
+        let names: Vec<String> = backups(&path)
+            .iter()
+            .map(|b| serde_json::from_slice::<DataTable>(b).unwrap().row[0].value[0].to_string())
+            .collect();
+        assert_eq!(names, vec!["b", "a"]);
+    }
+
+    #[test]
+    fn encrypt_purges_plaintext_backups() {
+        let dir = test_dir("backup-encrypt");
+        let path = dir.join("t.json").display().to_string();
+        table("a").save_with_backups(&path, 3).unwrap();
+        table("rahasia").save_with_backups(&path, 3).unwrap();
+        assert_eq!(backups(&path).len(), 1);
+
+        let mut encrypted = table("rahasia");
+        encrypted.encrypt("pw").unwrap();
+        encrypted.save_with_backups(&path, 3).unwrap();
+        assert!(backups(&path).is_empty(), "backup plaintext harus dihapus");
+        let data = fs::read(&path).unwrap();
+        assert!(crypto::is_encrypted(&data));
+
+        // Simpan berikutnya dengan kunci sama → backup terenkripsi
+        let mut next = encrypted.clone();
+        next.row[0].value[0] = Value::Text("baru".into());
+        next.save_with_backups(&path, 3).unwrap();
+        let saved = backups(&path);
+        assert_eq!(saved.len(), 1);
+        assert!(crypto::is_encrypted(&saved[0]));
+
+        // Rekey → backup dengan kunci lama dibuang
+        next.rekey("pw2").unwrap();
+        next.save_with_backups(&path, 3).unwrap();
+        assert!(backups(&path).is_empty());
+        assert!(DataTable::load_with_passphrase(&path, Some("pw2")).is_ok());
     }
 }
@@ -0,0 +1,509 @@
+use crate::error::TableError;
+use crate::table::{
+    expr::Expr,
+    handlers::{computed::compute_row, service::DataTable},
+    models::{ColumnTable, HookEvent, HookTiming, RowTable, Trigger, TriggerAction},
+    value::{ColumnType, Value},
+};
+use crate::tr;
+use serde_json::json;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::Arc;
+
+// Satu baris yang berubah: old None → baris baru, new None → baris dihapus
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowChange {
+    pub old: Option<Vec<Value>>,
+    pub new: Option<Vec<Value>>,
+}
+
+impl HookTiming {
+    pub fn parse(input: &str) -> Result<Self, TableError> {
+        match input.to_lowercase().as_str() {
+            "before" => Ok(HookTiming::Before),
+            "after" => Ok(HookTiming::After),
+            _ => Err(TableError::InvalidOption(tr!("trigger.timing_name", input))),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HookTiming::Before => "before",
+            HookTiming::After => "after",
+        }
+    }
+}
+
+impl HookEvent {
+    // Nama sama dengan perintah CLI-nya
+    pub fn parse(input: &str) -> Result<Self, TableError> {
+        match input.to_lowercase().as_str() {
+            "add_row" => Ok(HookEvent::AddRow),
+            "set_value_where" => Ok(HookEvent::SetValueWhere),
+            "remove_row" => Ok(HookEvent::RemoveRow),
+            _ => Err(TableError::InvalidOption(tr!("trigger.event_name", input))),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::AddRow => "add_row",
+            HookEvent::SetValueWhere => "set_value_where",
+            HookEvent::RemoveRow => "remove_row",
+        }
+    }
+}
+
+impl RowChange {
+    // Nilai baris yang relevan: baru jika ada, selain itu nilai lama
+    pub fn row(&self) -> Option<&[Value]> {
+        self.new.as_deref().or(self.old.as_deref())
+    }
+}
+
+// Data yang diterima hook. Saat before, `new` boleh diubah (dicek ulang setelahnya)
+// dan `new = None` membatalkan baris itu tanpa error
+pub struct HookContext<'a> {
+    pub event: HookEvent,
+    pub timing: HookTiming,
+    pub columns: &'a [ColumnTable],
+    pub changes: &'a mut [RowChange],
+}
+
+pub type HookFn = Arc<dyn Fn(&mut HookContext) -> Result<(), TableError> + Send + Sync>;
+
+// Callback Rust yang didaftarkan lewat `DataTable::add_hook`; tidak ikut disimpan
+#[derive(Clone, Default)]
+pub struct Hooks {
+    entries: Vec<(HookTiming, HookEvent, HookFn)>,
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field("count", &self.entries.len())
+            .finish()
+    }
+}
+
+impl DataTable {
+    // Daftarkan callback; before-hook yang return Err membatalkan perubahan
+    pub fn add_hook<F>(&mut self, timing: HookTiming, event: HookEvent, hook: F)
+    where
+        F: Fn(&mut HookContext) -> Result<(), TableError> + Send + Sync + 'static,
+    {
+        self.hooks.entries.push((timing, event, Arc::new(hook)));
+    }
+
+    pub fn clear_hooks(&mut self) {
+        self.hooks.entries.clear();
+    }
+
+    // Tambah trigger deklaratif; kolom & ekspresinya dicek terhadap skema saat ini
+    pub fn add_trigger(&mut self, trigger: Trigger) -> Result<(), TableError> {
+        if self.triggers.iter().any(|t| t.name == trigger.name) {
+            return Err(TableError::TriggerExists(trigger.name));
+        }
+        self.check_trigger(&trigger)?;
+        self.triggers.push(trigger);
+        Ok(())
+    }
+
+    pub fn remove_trigger(&mut self, name: &str) -> Result<Trigger, TableError> {
+        let index = self
+            .triggers
+            .iter()
+            .position(|t| t.name == name)
+            .ok_or_else(|| TableError::TriggerNotFound(name.into()))?;
+        Ok(self.triggers.remove(index))
+    }
+
+    fn check_trigger(&self, trigger: &Trigger) -> Result<(), TableError> {
+        let writes = matches!(
+            trigger.action,
+            TriggerAction::Touch { .. } | TriggerAction::Set { .. }
+        );
+        let vetoes = matches!(trigger.action, TriggerAction::Reject { .. });
+        // Ubah nilai hanya masuk akal sebelum baris ditulis; batal hanya sebelum perubahan
+        if (writes
+            && (trigger.timing == HookTiming::After || trigger.event == HookEvent::RemoveRow))
+            || (vetoes && trigger.timing == HookTiming::After)
+        {
+            return Err(TableError::InvalidValue(tr!(
+                "trigger.timing",
+                action_name(&trigger.action)
+            )));
+        }
+
+        match &trigger.action {
+            TriggerAction::Touch { column } => {
+                let col = self.writable_column(column)?;
+                if !matches!(col.coltype, ColumnType::Text | ColumnType::Numb) {
+                    return Err(TableError::TypeMismatch(column.clone()));
+                }
+            }
+            TriggerAction::Set { column, expr } => {
+                let col = self.writable_column(column)?;
+                let result = Expr::parse(expr)?.result_type(&self.column)?;
+                let fits = result == col.coltype
+                    || (result == ColumnType::Char && col.coltype == ColumnType::Text);
+                if !fits {
+                    return Err(TableError::TypeMismatch(column.clone()));
+                }
+            }
+            TriggerAction::Reject { condition, .. } => {
+                let result = Expr::parse(condition)?.result_type(&self.column)?;
+                if result != ColumnType::Bool {
+                    return Err(TableError::InvalidValue(tr!(
+                        "expr.condition",
+                        format!("{:?}", result)
+                    )));
+                }
+            }
+            // File log ditulis apa adanya → bocorkan isi tabel terenkripsi
+            TriggerAction::Log { .. } if self.is_encrypted() => {
+                return Err(TableError::Crypto(tr!(
+                    "trigger.log_encrypted",
+                    trigger.name
+                )));
+            }
+            TriggerAction::Log { .. } => {}
+        }
+        Ok(())
+    }
+
+    // Kolom target touch/set: bukan primary & bukan kolom hitung
+    fn writable_column(&self, colname: &str) -> Result<&ColumnTable, TableError> {
+        let col = &self.column[self.column_position(colname)?];
+        if col.is_primary {
+            return Err(TableError::PrimaryReadOnly(colname.into()));
+        }
+        if col.expr.is_some() {
+            return Err(TableError::ComputedReadOnly(colname.into()));
+        }
+        Ok(col)
+    }
+
+    // Trigger pertama yang memakai `colname`
+    pub(crate) fn trigger_using(&self, colname: &str) -> Option<&Trigger> {
+        let in_expr = |e: &str| Expr::parse(e).is_ok_and(|e| e.columns().contains(&colname));
+        self.triggers.iter().find(|t| match &t.action {
+            TriggerAction::Touch { column } => column == colname,
+            TriggerAction::Set { column, expr } => column == colname || in_expr(expr),
+            TriggerAction::Reject { condition, .. } => in_expr(condition),
+            TriggerAction::Log { .. } => false,
+        })
+    }
+
+    // Jalankan trigger lalu callback yang cocok, berurutan; error pertama menghentikan
+    pub(crate) fn run_hooks(
+        &self,
+        timing: HookTiming,
+        event: HookEvent,
+        changes: &mut [RowChange],
+    ) -> Result<(), TableError> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        for trigger in &self.triggers {
+            if trigger.timing == timing && trigger.event == event {
+                self.run_trigger(trigger, changes)?;
+            }
+        }
+        for (t, e, hook) in &self.hooks.entries {
+            if *t == timing && *e == event {
+                hook(&mut HookContext {
+                    event,
+                    timing,
+                    columns: &self.column,
+                    changes,
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn run_trigger(&self, trigger: &Trigger, changes: &mut [RowChange]) -> Result<(), TableError> {
+        match &trigger.action {
+            TriggerAction::Touch { column } => {
+                let index = self.column_position(column)?;
+                let now = match self.column[index].coltype {
+                    ColumnType::Numb => Value::Numb(chrono::Utc::now().timestamp()),
+                    _ => Value::Text(chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()),
+                };
+                for new in changes.iter_mut().filter_map(|c| c.new.as_mut()) {
+                    new[index] = now.clone();
+                }
+            }
+            TriggerAction::Set { column, expr } => {
+                let index = self.column_position(column)?;
+                let expr = Expr::parse(expr)?;
+                for new in changes.iter_mut().filter_map(|c| c.new.as_mut()) {
+                    new[index] = match expr.eval(&self.column, new) {
+                        Value::Char(c) if self.column[index].coltype == ColumnType::Text => {
+                            Value::Text(c.to_string())
+                        }
+                        value => value,
+                    };
+                }
+            }
+            TriggerAction::Reject { condition, message } => {
+                let expr = Expr::parse(condition)?;
+                let rejected = changes
+                    .iter()
+                    .filter_map(RowChange::row)
+                    .any(|row| expr.eval(&self.column, row) == Value::Bool(true));
+                if rejected {
+                    return Err(TableError::TriggerRejected {
+                        trigger: trigger.name.clone(),
+                        message: message.clone().unwrap_or_else(|| condition.clone()),
+                    });
+                }
+            }
+            TriggerAction::Log { .. } if self.is_encrypted() => {
+                return Err(TableError::Crypto(tr!(
+                    "trigger.log_encrypted",
+                    trigger.name
+                )));
+            }
+            TriggerAction::Log { path } => {
+                let to_json = |values: &Option<Vec<Value>>| {
+                    values
+                        .as_ref()
+                        .map(|v| self.row_to_json(&RowTable { value: v.clone() }))
+                };
+                let time = chrono::Local::now().to_rfc3339();
+                let mut lines = String::new();
+                for change in changes.iter() {
+                    let entry = json!({
+                        "time": time,
+                        "trigger": trigger.name,
+                        "timing": trigger.timing,
+                        "event": trigger.event,
+                        "old": to_json(&change.old),
+                        "new": to_json(&change.new),
+                    });
+                    lines.push_str(&format!("{}\n", entry));
+                }
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut file| file.write_all(lines.as_bytes()))
+                    .map_err(TableError::io(path))?;
+            }
+        }
+        Ok(())
+    }
+
+    // Nilai baru setelah before-hook: jumlah & tipe dicek ulang, kolom hitung dihitung ulang
+    // (`exprs` dari `parse_computed`)
+    pub(crate) fn checked_new_values(
+        &self,
+        mut values: Vec<Value>,
+        exprs: &[Option<Expr>],
+    ) -> Result<Vec<Value>, TableError> {
+        let col_len = self.column.len();
+        if values.len() > col_len {
+            return Err(TableError::TooManyValues {
+                given: values.len(),
+                columns: col_len,
+            });
+        }
+        values.resize(col_len, Value::Empty);
+        for (col, val) in self.column.iter().zip(&values) {
+            if !Self::_validate_type_column_and_row(&col.coltype, val) {
+                return Err(TableError::TypeMismatch(col.colname.clone()));
+            }
+        }
+        compute_row(&self.column, exprs, &mut values);
+        Ok(values)
+    }
+}
+
+pub fn action_name(action: &TriggerAction) -> &'static str {
+    match action {
+        TriggerAction::Touch { .. } => "touch",
+        TriggerAction::Set { .. } => "set",
+        TriggerAction::Reject { .. } => "reject",
+        TriggerAction::Log { .. } => "log",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::handlers::test_dir;
+    use std::fs;
+
+    fn table() -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![
+                ("kode", ColumnType::Numb),
+                ("harga", ColumnType::Numb),
+                ("total", ColumnType::Numb),
+            ])
+            .unwrap();
+        table.set_primary("kode").unwrap();
+        table
+    }
+
+    fn trigger(name: &str, timing: HookTiming, event: HookEvent, action: TriggerAction) -> Trigger {
+        Trigger {
+            name: name.into(),
+            timing,
+            event,
+            action,
+        }
+    }
+
+    #[test]
+    fn before_trigger_mengisi_dan_menolak() {
+        let mut table = table();
+        table
+            .add_trigger(trigger(
+                "hitung",
+                HookTiming::Before,
+                HookEvent::AddRow,
+                TriggerAction::Set {
+                    column: "total".into(),
+                    expr: "harga * 2".into(),
+                },
+            ))
+            .unwrap();
+        table
+            .add_trigger(trigger(
+                "tolak_negatif",
+                HookTiming::Before,
+                HookEvent::AddRow,
+                TriggerAction::Reject {
+                    condition: "harga < 0".into(),
+                    message: Some("harga negatif".into()),
+                },
+            ))
+            .unwrap();
+
+        table.add_row(vec![Value::Numb(1), Value::Numb(7)]).unwrap();
+        assert_eq!(table.row[0].value[2], Value::Numb(14));
+        assert!(matches!(
+            table.add_row(vec![Value::Numb(2), Value::Numb(-1)]),
+            Err(TableError::TriggerRejected { trigger, message })
+                if trigger == "tolak_negatif" && message == "harga negatif"
+        ));
+        assert_eq!(table.row.len(), 1);
+
+        // Kolom yang dipakai trigger tidak boleh dihapus
+        assert!(matches!(
+            table.remove_column("harga"),
+            Err(TableError::ColumnInTrigger { .. })
+        ));
+        assert!(matches!(
+            table.add_trigger(trigger(
+                "hitung",
+                HookTiming::Before,
+                HookEvent::RemoveRow,
+                TriggerAction::Log { path: "x".into() },
+            )),
+            Err(TableError::TriggerExists(_))
+        ));
+    }
+
+    #[test]
+    fn trigger_yang_tidak_masuk_akal_ditolak() {
+        let mut table = table();
+        let set = |column: &str, expr: &str| TriggerAction::Set {
+            column: column.into(),
+            expr: expr.into(),
+        };
+        for (timing, event, action) in [
+            (HookTiming::After, HookEvent::AddRow, set("total", "harga")),
+            (
+                HookTiming::Before,
+                HookEvent::RemoveRow,
+                set("total", "harga"),
+            ),
+            (HookTiming::Before, HookEvent::AddRow, set("kode", "harga")),
+            (
+                HookTiming::Before,
+                HookEvent::AddRow,
+                set("total", "harga > 1"),
+            ),
+            (
+                HookTiming::After,
+                HookEvent::AddRow,
+                TriggerAction::Reject {
+                    condition: "harga > 1".into(),
+                    message: None,
+                },
+            ),
+        ] {
+            assert!(
+                table
+                    .add_trigger(trigger("t", timing, event, action))
+                    .is_err()
+            );
+        }
+        assert!(table.triggers.is_empty());
+    }
+
+    #[test]
+    fn hook_before_bisa_membatalkan_baris() {
+        let mut table = table();
+        table.add_hook(HookTiming::Before, HookEvent::AddRow, |ctx| {
+            for change in ctx.changes.iter_mut() {
+                if change.new.as_ref().is_some_and(|v| v[1] == Value::Numb(0)) {
+                    change.new = None;
+                }
+            }
+            Ok(())
+        });
+        table.add_row(vec![Value::Numb(1), Value::Numb(0)]).unwrap();
+        table.add_row(vec![Value::Numb(2), Value::Numb(5)]).unwrap();
+        assert_eq!(table.row.len(), 1);
+        assert_eq!(table.row[0].value[0], Value::Numb(2));
+    }
+
+    #[test]
+    fn log_trigger_menulis_json_dan_ditolak_untuk_tabel_terenkripsi() {
+        let path = test_dir("trigger-log").join("log.jsonl");
+        let log = TriggerAction::Log {
+            path: path.display().to_string(),
+        };
+        let mut table = table();
+        table
+            .add_trigger(trigger(
+                "catat",
+                HookTiming::After,
+                HookEvent::AddRow,
+                log.clone(),
+            ))
+            .unwrap();
+        table.add_row(vec![Value::Numb(1), Value::Numb(3)]).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let entry: serde_json::Value = serde_json::from_str(text.trim()).unwrap();
+        assert_eq!(entry["new"]["harga"], 3);
+        assert!(entry["old"].is_null());
+
+        // Dienkripsi setelah trigger dibuat → baris tidak ditulis ke log maupun tabel
+        table.passphrase = Some("rahasia".into());
+        assert!(matches!(
+            table.add_row(vec![Value::Numb(2), Value::Numb(4)]),
+            Err(TableError::Crypto(_))
+        ));
+        assert_eq!(table.row.len(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), text);
+
+        assert!(matches!(
+            table.add_trigger(trigger(
+                "lagi",
+                HookTiming::After,
+                HookEvent::RemoveRow,
+                log
+            )),
+            Err(TableError::Crypto(_))
+        ));
+    }
+}
@@ -0,0 +1,262 @@
+use crate::error::TableError;
+use crate::table::{
+    handlers::service::DataTable,
+    models::RowTable,
+    value::{ColumnType, Value},
+};
+use crate::tr;
+use serde_json::{Map, Value as Json, value::RawValue};
+use std::collections::HashSet;
+use std::fs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonShape {
+    Array, // [ {..}, {..} ]
+    Lines, // satu objek per baris (JSON Lines)
+}
+
+impl JsonShape {
+    // Tebak bentuk dari ekstensi file
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(".jsonl") || path.ends_with(".ndjson") {
+            JsonShape::Lines
+        } else {
+            JsonShape::Array
+        }
+    }
+}
+
+impl DataTable {
+    // Satu baris → objek JSON dengan key nama kolom
+    pub fn row_to_json(&self, row: &RowTable) -> Map<String, Json> {
+        self.column
+            .iter()
+            .zip(&row.value)
+            .map(|(col, val)| (col.colname.clone(), value_to_json(val)))
+            .collect()
+    }
+
+    pub fn rows_to_json_string(&self, rows: &[&RowTable], shape: JsonShape) -> String {
+        let objects: Vec<Json> = rows
+            .iter()
+            .map(|row| Json::Object(self.row_to_json(row)))
+            .collect();
+
+        match shape {
+            JsonShape::Array => {
+                let mut out = serde_json::to_string_pretty(&objects).unwrap_or_default();
+                out.push('\n');
+                out
+            }
+            JsonShape::Lines => objects.iter().map(|o| format!("{}\n", o)).collect(),
+        }
+    }
+
+    // Export ke file, atau stdout jika path None
+    pub fn export_json(
+        &self,
+        rows: &[&RowTable],
+        shape: JsonShape,
+        path: Option<&str>,
+    ) -> Result<(), TableError> {
+        let out = self.rows_to_json_string(rows, shape);
+        match path {
+            Some(path) => fs::write(path, out).map_err(TableError::io(path)),
+            None => {
+                print!("{}", out);
+                Ok(())
+            }
+        }
+    }
+
+    // Import baris dari JSON; semua record divalidasi dulu, jika ada yang
+    // salah tidak ada satu pun yang ditambahkan. Return: jumlah baris masuk
+    pub fn import_json(&mut self, data: &str, shape: JsonShape) -> Result<usize, TableError> {
+        if self.primary_index.is_none() {
+            return Err(TableError::NoPrimary);
+        }
+
+        // (nomor baris, teks record)
+        let records: Vec<(usize, &str)> = match shape {
+            JsonShape::Lines => data
+                .lines()
+                .enumerate()
+                .map(|(i, line)| (i + 1, line.trim()))
+                .filter(|(_, line)| !line.is_empty())
+                .collect(),
+            JsonShape::Array => {
+                let raws: Vec<&RawValue> = serde_json::from_str(data)?;
+                raws.iter()
+                    .map(|raw| (line_of(data, raw.get()), raw.get()))
+                    .collect()
+            }
+        };
+
+        let mut errors: Vec<String> = Vec::new();
+        let mut rows: Vec<Vec<Value>> = Vec::new();
+        let mut primaries: HashSet<Value> = HashSet::new();
+        let primary_index = self.primary_index.unwrap_or(0);
+
+        for (line, text) in records {
+            match self.json_record_to_values(text) {
+                Ok(values) => {
+                    let primary = &values[primary_index];
+                    let duplicate = !primaries.insert(primary.clone())
+                        || self.row.iter().any(|r| &r.value[primary_index] == primary);
+                    if matches!(primary, Value::Empty) {
+                        errors.push(tr!("val.line", line, tr!("err.empty_primary")));
+                    } else if duplicate {
+                        errors.push(tr!("val.line", line, tr!("err.duplicate_primary")));
+                    } else {
+                        rows.push(values);
+                    }
+                }
+                Err(e) => errors.push(tr!("val.line", line, e)),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(TableError::InvalidRecords(errors));
+        }
+
+        // Hook/trigger bisa menolak satu baris di tengah jalan → batalkan semua
+        // baris & catatan audit dari import ini
+        let (row_mark, audit_mark, increment) = (self.row.len(), self.audit.len(), self._increment);
+        for values in rows {
+            if let Err(e) = self.add_row(values) {
+                self.row.truncate(row_mark);
+                self.audit.truncate(audit_mark);
+                self._increment = increment;
+                return Err(e);
+            }
+        }
+        // Baris yang dibatalkan before-hook tidak dihitung
+        Ok(self.row.len() - row_mark)
+    }
+
+    // Satu objek JSON → nilai baris sesuai urutan kolom
+    fn json_record_to_values(&self, text: &str) -> Result<Vec<Value>, TableError> {
+        let record: Json = serde_json::from_str(text)?;
+        let Json::Object(map) = record else {
+            return Err(TableError::InvalidValue(tr!("val.record_object")));
+        };
+        self.json_object_to_values(&map)
+    }
+
+    // Objek JSON → nilai baris; key tak dikenal → error, kolom tanpa key → Empty
+    pub fn json_object_to_values(&self, map: &Map<String, Json>) -> Result<Vec<Value>, TableError> {
+        if let Some(key) = map
+            .keys()
+            .find(|k| !self.column.iter().any(|c| &c.colname == *k))
+        {
+            return Err(TableError::ColumnNotFound(key.clone()));
+        }
+
+        self.column
+            .iter()
+            .map(|col| {
+                let value = match map.get(&col.colname) {
+                    // Nilai kolom hitung (mis. dari hasil ekspor) diabaikan, dihitung ulang
+                    Some(_) if col.expr.is_some() => Value::Empty,
+                    Some(json) => json_to_value(json, &col.coltype).map_err(|e| {
+                        TableError::InvalidValue(tr!("val.in_column", col.colname, e))
+                    })?,
+                    None => Value::Empty,
+                };
+                if !Self::_validate_type_column_and_row(&col.coltype, &value) {
+                    return Err(TableError::TypeMismatch(col.colname.clone()));
+                }
+                Ok(value)
+            })
+            .collect()
+    }
+}
+
+pub fn value_to_json(value: &Value) -> Json {
+    match value {
+        Value::Text(s) => Json::String(s.clone()),
+        Value::Char(c) => Json::String(c.to_string()),
+        Value::Numb(n) => Json::from(*n),
+        Value::Bool(b) => Json::Bool(*b),
+        Value::Empty => Json::Null,
+    }
+}
+
+// Tipe JSON → Value; string dipetakan ke Char jika kolomnya Char
+fn json_to_value(json: &Json, col_type: &ColumnType) -> Result<Value, String> {
+    match json {
+        Json::Null => Ok(Value::Empty),
+        Json::Bool(b) => Ok(Value::Bool(*b)),
+        Json::Number(n) => n
+            .as_i64()
+            .map(Value::Numb)
+            .ok_or_else(|| tr!("val.json_i64", n)),
+        Json::String(s) => match col_type {
+            ColumnType::Char => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(Value::Char(c)),
+                    _ => Err(tr!("val.char_len")),
+                }
+            }
+            _ => Ok(Value::Text(s.clone())),
+        },
+        Json::Array(_) | Json::Object(_) => Err(tr!("val.json_nested")),
+    }
+}
+
+// Nomor baris (1-based) dari potongan `part` di dalam `data`
+fn line_of(data: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).saturating_sub(data.as_ptr() as usize);
+    data[..offset.min(data.len())].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::models::{HookEvent, HookTiming};
+
+    fn table() -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![("id", ColumnType::Numb), ("nama", ColumnType::Text)])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        table
+            .add_row(vec![Value::Numb(1), Value::Text("a".into())])
+            .unwrap();
+        table
+    }
+
+    #[test]
+    fn invalid_records_add_nothing() {
+        let mut table = table();
+        let data = "{\"id\": 2, \"nama\": \"b\"}\n{\"id\": \"x\"}\n{\"id\": 1}\n";
+        let Err(TableError::InvalidRecords(errors)) = table.import_json(data, JsonShape::Lines)
+        else {
+            panic!("import harus gagal");
+        };
+        assert_eq!(errors.len(), 2);
+        assert_eq!(table.row.len(), 1);
+    }
+
+    #[test]
+    fn rejected_row_rolls_back_whole_import() {
+        let mut table = table();
+        table.enable_audit();
+        table.add_hook(HookTiming::After, HookEvent::AddRow, |ctx| {
+            match ctx.changes[0].new.as_ref().map(|v| &v[0]) {
+                Some(Value::Numb(3)) => Err(TableError::InvalidValue("tolak".into())),
+                _ => Ok(()),
+            }
+        });
+        let data = r#"[{"id": 2, "nama": "b"}, {"id": 3, "nama": "c"}, {"id": 4}]"#;
+        assert!(table.import_json(data, JsonShape::Array).is_err());
+        assert_eq!(table.row.len(), 1);
+        assert_eq!(table.audit.len(), 0);
+
+        let data = r#"[{"id": 2, "nama": "b"}, {"id": 4}]"#;
+        assert_eq!(table.import_json(data, JsonShape::Array).unwrap(), 2);
+        assert_eq!(table.audit.len(), 2);
+    }
+}
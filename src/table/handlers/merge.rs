@@ -0,0 +1,655 @@
+use crate::error::TableError;
+use crate::table::{
+    handlers::{diff::check_keys, service::DataTable},
+    models::{ColumnTable, RowTable},
+    value::{ColumnType, Value},
+};
+use crate::tr;
+use std::collections::{HashMap, HashSet};
+
+// Perubahan yang bentrok antara dua salinan tabel
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeConflict {
+    // Kedua pihak mengubah sel yang sama dengan nilai berbeda
+    Cell {
+        key: Value,
+        column: String,
+        base: Value,
+        ours: Value,
+        theirs: Value,
+    },
+    // Satu pihak menghapus baris, pihak lain mengubahnya (None → dihapus)
+    Row {
+        key: Value,
+        ours: Option<Vec<(String, Value)>>,
+        theirs: Option<Vec<(String, Value)>>,
+    },
+    // Kolom bernama sama dengan tipe berbeda
+    Column {
+        column: String,
+        ours: ColumnType,
+        theirs: ColumnType,
+    },
+    // Nilai hasil merge tidak cocok dengan tipe kolom hasil merge (mis. tipe kolom
+    // diubah satu pihak, pihak lain mengisi nilai tipe lama). Tidak bisa dipilih
+    // ours/theirs → selalu tersisa sebagai konflik
+    Type {
+        key: Value,
+        column: String,
+        coltype: ColumnType,
+        value: Value,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Ours,
+    Theirs,
+}
+
+impl Resolution {
+    pub fn parse(input: &str) -> Result<Self, TableError> {
+        match input.to_lowercase().as_str() {
+            "ours" => Ok(Resolution::Ours),
+            "theirs" => Ok(Resolution::Theirs),
+            _ => Err(TableError::InvalidOption(tr!("merge.prefer", input))),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Resolution::Ours => "ours",
+            Resolution::Theirs => "theirs",
+        }
+    }
+}
+
+// Hasil merge; konflik yang tidak terselesaikan memakai versi ours (sel dengan
+// konflik tipe dikosongkan)
+#[derive(Debug, Clone)]
+pub struct MergeOutcome {
+    pub table: DataTable,
+    pub resolved: Vec<(MergeConflict, Resolution)>,
+    pub unresolved: Vec<MergeConflict>,
+}
+
+type Cells = HashMap<String, Value>;
+
+impl DataTable {
+    // Merge tiga arah per nilai primary. Perubahan yang hanya dibuat satu pihak
+    // (sel, baris, kolom baru/dihapus) diambil otomatis; konflik diserahkan ke
+    // `resolve` (None → tetap konflik). View, trigger & passphrase ikut `ours`
+    pub fn merge3<F>(
+        base: &DataTable,
+        ours: &DataTable,
+        theirs: &DataTable,
+        mut resolve: F,
+    ) -> Result<MergeOutcome, TableError>
+    where
+        F: FnMut(&MergeConflict) -> Option<Resolution>,
+    {
+        let key_col = merge_key(ours, theirs)?;
+        let mut resolved = Vec::new();
+        let mut unresolved = Vec::new();
+        let mut decide = |conflict: MergeConflict| match resolve(&conflict) {
+            Some(choice) => {
+                resolved.push((conflict, choice));
+                choice
+            }
+            None => {
+                unresolved.push(conflict);
+                Resolution::Ours
+            }
+        };
+
+        let columns = merge_columns(&base.column, &ours.column, &theirs.column, &mut decide);
+        let base_rows = keyed_cells(base, &key_col);
+        let our_rows = keyed_cells(ours, &key_col);
+        let their_rows = keyed_cells(theirs, &key_col);
+        for rows in [&base_rows, &our_rows, &their_rows] {
+            check_keys(rows.iter().map(|(k, _)| k))?;
+        }
+        let base_map: HashMap<&Value, &Cells> = base_rows.iter().map(|(k, c)| (k, c)).collect();
+        let our_map: HashMap<&Value, &Cells> = our_rows.iter().map(|(k, c)| (k, c)).collect();
+        let their_map: HashMap<&Value, &Cells> = their_rows.iter().map(|(k, c)| (k, c)).collect();
+
+        // Urutan: baris ours, lalu baris yang hanya ada di theirs
+        let mut keys: Vec<&Value> = our_rows.iter().map(|(k, _)| k).collect();
+        let known: HashSet<&Value> = keys.iter().copied().collect();
+        keys.extend(
+            their_rows
+                .iter()
+                .map(|(k, _)| k)
+                .filter(|k| !known.contains(k)),
+        );
+
+        let empty = Cells::new();
+        let mut rows = Vec::new();
+        let mut mismatched = Vec::new();
+        for key in keys {
+            let (b, o, t) = (
+                base_map.get(key).copied(),
+                our_map.get(key).copied(),
+                their_map.get(key).copied(),
+            );
+            let merged = match (o, t) {
+                (Some(o), Some(t)) => Some(merge_cells(
+                    key,
+                    &columns,
+                    b.unwrap_or(&empty),
+                    o,
+                    t,
+                    &mut decide,
+                )),
+                // Dihapus satu pihak: konflik jika pihak lain sempat mengubahnya
+                (Some(kept), None) | (None, Some(kept)) => match b {
+                    None => Some(kept.clone()),
+                    Some(b) if same_cells(&columns, b, kept) => None,
+                    Some(_) => {
+                        let row = Some(ordered(&columns, kept));
+                        let ours_kept = o.is_some();
+                        let conflict = MergeConflict::Row {
+                            key: key.clone(),
+                            ours: if ours_kept { row.clone() } else { None },
+                            theirs: if ours_kept { None } else { row },
+                        };
+                        let keep = match decide(conflict) {
+                            Resolution::Ours => ours_kept,
+                            Resolution::Theirs => !ours_kept,
+                        };
+                        keep.then(|| kept.clone())
+                    }
+                },
+                (None, None) => None,
+            };
+            if let Some(cells) = merged {
+                rows.push(to_row(key, &columns, &cells, &mut mismatched));
+            }
+        }
+        unresolved.extend(mismatched);
+
+        let mut table = ours.clone();
+        table.primary_index = columns.iter().position(|c| c.is_primary);
+        table.column = columns;
+        table.row = rows;
+        table._increment = ours._increment.max(theirs._increment);
+        table.recompute_all();
+        Ok(MergeOutcome {
+            table,
+            resolved,
+            unresolved,
+        })
+    }
+}
+
+// Kolom primary ours & theirs harus sama; base tanpa kolom itu → dianggap kosong
+fn merge_key(ours: &DataTable, theirs: &DataTable) -> Result<String, TableError> {
+    let primary = |t: &DataTable| t._get_column_is_primary_active().map(String::from);
+    match (primary(ours), primary(theirs)) {
+        (Some(a), Some(b)) if a == b => Ok(a),
+        (Some(a), Some(b)) => Err(TableError::InvalidValue(tr!("merge.primary_differs", a, b))),
+        _ => Err(TableError::NoPrimary),
+    }
+}
+
+fn merge_columns(
+    base: &[ColumnTable],
+    ours: &[ColumnTable],
+    theirs: &[ColumnTable],
+    decide: &mut impl FnMut(MergeConflict) -> Resolution,
+) -> Vec<ColumnTable> {
+    let find =
+        |columns: &[ColumnTable], name: &str| columns.iter().find(|c| c.colname == name).cloned();
+    let mut merged = Vec::new();
+    for col in ours {
+        let (b, t) = (find(base, &col.colname), find(theirs, &col.colname));
+        match (b, t) {
+            // Dihapus theirs
+            (Some(_), None) => {}
+            (b, Some(t)) => {
+                let mut col = col.clone();
+                let base_type = b.as_ref().map(|b| &b.coltype);
+                if col.coltype != t.coltype {
+                    col.coltype = if base_type == Some(&col.coltype) {
+                        t.coltype.clone()
+                    } else if base_type == Some(&t.coltype) {
+                        col.coltype
+                    } else {
+                        let conflict = MergeConflict::Column {
+                            column: col.colname.clone(),
+                            ours: col.coltype.clone(),
+                            theirs: t.coltype.clone(),
+                        };
+                        match decide(conflict) {
+                            Resolution::Ours => col.coltype,
+                            Resolution::Theirs => t.coltype.clone(),
+                        }
+                    };
+                }
+                // Ekspresi kolom hitung: ours jika ours mengubahnya, selain itu theirs
+                if b.is_some_and(|b| b.expr == col.expr) {
+                    col.expr = t.expr.clone();
+                }
+                merged.push(col);
+            }
+            // Kolom baru di ours
+            (None, None) => merged.push(col.clone()),
+        }
+    }
+    // Kolom baru di theirs (kolom yang dihapus ours tidak dikembalikan)
+    for col in theirs {
+        if find(ours, &col.colname).is_none() && find(base, &col.colname).is_none() {
+            let mut col = col.clone();
+            col.is_primary = false;
+            merged.push(col);
+        }
+    }
+    merged
+}
+
+// Isi baris per nama kolom, dikunci nilai `key_col`
+fn keyed_cells(table: &DataTable, key_col: &str) -> Vec<(Value, Cells)> {
+    let Some(index) = table.column.iter().position(|c| c.colname == key_col) else {
+        return Vec::new();
+    };
+    table
+        .row
+        .iter()
+        .map(|row| {
+            let cells = table
+                .column
+                .iter()
+                .zip(&row.value)
+                .map(|(c, v)| (c.colname.clone(), v.clone()))
+                .collect();
+            (row.value[index].clone(), cells)
+        })
+        .collect()
+}
+
+fn cell(cells: &Cells, column: &str) -> Value {
+    cells.get(column).cloned().unwrap_or(Value::Empty)
+}
+
+// Isi baris urut kolom hasil merge (untuk laporan konflik)
+fn ordered(columns: &[ColumnTable], cells: &Cells) -> Vec<(String, Value)> {
+    columns
+        .iter()
+        .map(|c| (c.colname.clone(), cell(cells, &c.colname)))
+        .collect()
+}
+
+fn same_cells(columns: &[ColumnTable], a: &Cells, b: &Cells) -> bool {
+    columns
+        .iter()
+        .all(|c| cell(a, &c.colname) == cell(b, &c.colname))
+}
+
+fn merge_cells(
+    key: &Value,
+    columns: &[ColumnTable],
+    base: &Cells,
+    ours: &Cells,
+    theirs: &Cells,
+    decide: &mut impl FnMut(MergeConflict) -> Resolution,
+) -> Cells {
+    let mut merged = Cells::new();
+    for col in columns {
+        let name = &col.colname;
+        let (b, o, t) = (cell(base, name), cell(ours, name), cell(theirs, name));
+        // Kolom hitung dihitung ulang setelah merge
+        let value = if col.expr.is_some() || o == t || t == b {
+            o
+        } else if o == b {
+            t
+        } else {
+            let conflict = MergeConflict::Cell {
+                key: key.clone(),
+                column: name.clone(),
+                base: b,
+                ours: o.clone(),
+                theirs: t.clone(),
+            };
+            match decide(conflict) {
+                Resolution::Ours => o,
+                Resolution::Theirs => t,
+            }
+        };
+        merged.insert(name.clone(), value);
+    }
+    merged
+}
+
+// Nilai yang tidak cocok dengan tipe kolom hasil merge → konflik `Type`
+fn to_row(
+    key: &Value,
+    columns: &[ColumnTable],
+    cells: &Cells,
+    conflicts: &mut Vec<MergeConflict>,
+) -> RowTable {
+    RowTable {
+        value: columns
+            .iter()
+            .map(|c| {
+                let value = cell(cells, &c.colname);
+                if DataTable::_validate_type_column_and_row(&c.coltype, &value) {
+                    return value;
+                }
+                conflicts.push(MergeConflict::Type {
+                    key: key.clone(),
+                    column: c.colname.clone(),
+                    coltype: c.coltype.clone(),
+                    value,
+                });
+                Value::Empty
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numb(n: i64) -> Value {
+        Value::Numb(n)
+    }
+
+    fn text(s: &str) -> Value {
+        Value::Text(s.into())
+    }
+
+    // id (primary), nama, stok: 1 a 10, 2 b 20, 3 c 30
+    fn base() -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![
+                ("id", ColumnType::Numb),
+                ("nama", ColumnType::Text),
+                ("stok", ColumnType::Numb),
+            ])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        for (id, nama, stok) in [(1, "a", 10), (2, "b", 20), (3, "c", 30)] {
+            table
+                .add_row(vec![numb(id), text(nama), numb(stok)])
+                .unwrap();
+        }
+        table
+    }
+
+    fn set(table: &mut DataTable, id: i64, column: &str, value: Value) {
+        table
+            .set_value_where("id", numb(id), column, value)
+            .unwrap();
+    }
+
+    fn row(table: &DataTable, id: i64) -> Option<Vec<Value>> {
+        table
+            .row
+            .iter()
+            .find(|r| r.value[0] == numb(id))
+            .map(|r| r.value.clone())
+    }
+
+    fn merge(
+        base: &DataTable,
+        ours: &DataTable,
+        theirs: &DataTable,
+        choice: Option<Resolution>,
+    ) -> MergeOutcome {
+        DataTable::merge3(base, ours, theirs, |_| choice).unwrap()
+    }
+
+    #[test]
+    fn one_sided_changes_merge_cleanly() {
+        let base = base();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        set(&mut ours, 1, "nama", text("A"));
+        set(&mut theirs, 1, "stok", numb(11));
+        set(&mut theirs, 2, "nama", text("B"));
+        // Perubahan sama di kedua pihak bukan konflik
+        set(&mut ours, 3, "stok", numb(33));
+        set(&mut theirs, 3, "stok", numb(33));
+
+        let outcome = merge(&base, &ours, &theirs, None);
+        assert!(outcome.unresolved.is_empty());
+        assert!(outcome.resolved.is_empty());
+        let t = &outcome.table;
+        assert_eq!(row(t, 1), Some(vec![numb(1), text("A"), numb(11)]));
+        assert_eq!(row(t, 2), Some(vec![numb(2), text("B"), numb(20)]));
+        assert_eq!(row(t, 3), Some(vec![numb(3), text("c"), numb(33)]));
+    }
+
+    #[test]
+    fn same_cell_changed_differently_conflicts() {
+        let base = base();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        set(&mut ours, 1, "stok", numb(11));
+        set(&mut theirs, 1, "stok", numb(12));
+        let expected = MergeConflict::Cell {
+            key: numb(1),
+            column: "stok".into(),
+            base: numb(10),
+            ours: numb(11),
+            theirs: numb(12),
+        };
+
+        // Tidak diselesaikan → tetap konflik, nilai ours dipakai sementara
+        let outcome = merge(&base, &ours, &theirs, None);
+        assert_eq!(outcome.unresolved, vec![expected.clone()]);
+        assert_eq!(row(&outcome.table, 1).unwrap()[2], numb(11));
+
+        let outcome = merge(&base, &ours, &theirs, Some(Resolution::Theirs));
+        assert!(outcome.unresolved.is_empty());
+        assert_eq!(outcome.resolved, vec![(expected, Resolution::Theirs)]);
+        assert_eq!(row(&outcome.table, 1).unwrap()[2], numb(12));
+    }
+
+    #[test]
+    fn delete_against_unchanged_row_deletes() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.remove_row("id", numb(2)).unwrap();
+        let theirs = base.clone();
+
+        let outcome = merge(&base, &ours, &theirs, None);
+        assert!(outcome.unresolved.is_empty());
+        assert_eq!(row(&outcome.table, 2), None);
+        // Arah sebaliknya juga
+        let outcome = merge(&base, &theirs, &ours, None);
+        assert_eq!(row(&outcome.table, 2), None);
+    }
+
+    #[test]
+    fn delete_against_modify_conflicts() {
+        let base = base();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        ours.remove_row("id", numb(2)).unwrap();
+        set(&mut theirs, 2, "stok", numb(21));
+        let expected = MergeConflict::Row {
+            key: numb(2),
+            ours: None,
+            theirs: Some(vec![
+                ("id".into(), numb(2)),
+                ("nama".into(), text("b")),
+                ("stok".into(), numb(21)),
+            ]),
+        };
+
+        let outcome = merge(&base, &ours, &theirs, None);
+        assert_eq!(outcome.unresolved, vec![expected]);
+        assert_eq!(row(&outcome.table, 2), None, "tanpa pilihan → versi ours");
+
+        let outcome = merge(&base, &ours, &theirs, Some(Resolution::Theirs));
+        assert_eq!(
+            row(&outcome.table, 2),
+            Some(vec![numb(2), text("b"), numb(21)])
+        );
+        let outcome = merge(&base, &ours, &theirs, Some(Resolution::Ours));
+        assert_eq!(row(&outcome.table, 2), None);
+    }
+
+    #[test]
+    fn modify_against_delete_conflicts() {
+        let base = base();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        set(&mut ours, 3, "nama", text("C"));
+        theirs.remove_row("id", numb(3)).unwrap();
+
+        let outcome = merge(&base, &ours, &theirs, None);
+        assert!(matches!(
+            outcome.unresolved.as_slice(),
+            [MergeConflict::Row {
+                ours: Some(_),
+                theirs: None,
+                ..
+            }]
+        ));
+        assert_eq!(
+            row(&outcome.table, 3),
+            Some(vec![numb(3), text("C"), numb(30)])
+        );
+
+        let outcome = merge(&base, &ours, &theirs, Some(Resolution::Theirs));
+        assert_eq!(row(&outcome.table, 3), None);
+    }
+
+    #[test]
+    fn rows_added_on_both_sides_are_kept() {
+        let base = base();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        ours.add_row(vec![numb(4), text("d"), numb(40)]).unwrap();
+        theirs.add_row(vec![numb(5), text("e"), numb(50)]).unwrap();
+
+        let outcome = merge(&base, &ours, &theirs, None);
+        assert!(outcome.unresolved.is_empty());
+        let keys: Vec<Value> = outcome
+            .table
+            .row
+            .iter()
+            .map(|r| r.value[0].clone())
+            .collect();
+        assert_eq!(keys, vec![numb(1), numb(2), numb(3), numb(4), numb(5)]);
+    }
+
+    #[test]
+    fn column_added_by_theirs_is_merged() {
+        let base = base();
+        let ours = base.clone();
+        let mut theirs = base.clone();
+        theirs
+            .add_column(vec![("aktif", ColumnType::Bool)])
+            .unwrap();
+        set(&mut theirs, 1, "aktif", Value::Bool(true));
+
+        let outcome = merge(&base, &ours, &theirs, None);
+        assert!(outcome.unresolved.is_empty());
+        assert_eq!(outcome.table.column.len(), 4);
+        assert_eq!(row(&outcome.table, 1).unwrap()[3], Value::Bool(true));
+        assert_eq!(row(&outcome.table, 2).unwrap()[3], Value::Empty);
+    }
+
+    #[test]
+    fn column_type_changed_differently_conflicts() {
+        let base = base();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        ours.column[2].coltype = ColumnType::Text;
+        theirs.column[2].coltype = ColumnType::Bool;
+
+        let outcome = merge(&base, &ours, &theirs, None);
+        assert_eq!(
+            outcome.unresolved[0],
+            MergeConflict::Column {
+                column: "stok".into(),
+                ours: ColumnType::Text,
+                theirs: ColumnType::Bool,
+            }
+        );
+        // Nilai angka lama tidak cocok dengan tipe hasil (Text)
+        assert_eq!(outcome.unresolved.len(), 4);
+        assert!(outcome.unresolved[1..].iter().all(|c| matches!(
+            c,
+            MergeConflict::Type {
+                coltype: ColumnType::Text,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn value_of_old_column_type_conflicts() {
+        let base = base();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        ours.add_row(vec![numb(4), text("d"), numb(40)]).unwrap();
+        // theirs mengubah `stok` jadi Text beserta isinya
+        theirs.column[2].coltype = ColumnType::Text;
+        for row in &mut theirs.row {
+            row.value[2] = text(&row.value[2].to_string());
+        }
+
+        let outcome = merge(&base, &ours, &theirs, Some(Resolution::Theirs));
+        assert_eq!(
+            outcome.unresolved,
+            vec![MergeConflict::Type {
+                key: numb(4),
+                column: "stok".into(),
+                coltype: ColumnType::Text,
+                value: numb(40),
+            }]
+        );
+        assert_eq!(row(&outcome.table, 1).unwrap()[2], text("10"));
+        assert_eq!(row(&outcome.table, 4).unwrap()[2], Value::Empty);
+    }
+
+    #[test]
+    fn different_or_missing_primary_is_an_error() {
+        let base = base();
+        let mut theirs = base.clone();
+        theirs.set_primary("nama").unwrap();
+        assert!(matches!(
+            DataTable::merge3(&base, &base, &theirs, |_| None),
+            Err(TableError::InvalidValue(_))
+        ));
+        let no_primary = DataTable::default();
+        assert!(matches!(
+            DataTable::merge3(&base, &no_primary, &no_primary, |_| None),
+            Err(TableError::NoPrimary)
+        ));
+    }
+
+    #[test]
+    fn duplicate_or_empty_keys_are_rejected() {
+        let base = base();
+        let mut dup = base.clone();
+        dup.row.push(RowTable {
+            value: vec![numb(1), text("dup"), numb(0)],
+        });
+        for (b, o, t) in [
+            (&dup, &base, &base),
+            (&base, &dup, &base),
+            (&base, &base, &dup),
+        ] {
+            assert!(matches!(
+                DataTable::merge3(b, o, t, |_| None),
+                Err(TableError::DuplicatePrimary)
+            ));
+        }
+
+        let mut empty = base.clone();
+        empty.row.push(RowTable {
+            value: vec![Value::Empty, text("x"), numb(0)],
+        });
+        assert!(matches!(
+            DataTable::merge3(&base, &empty, &base, |_| None),
+            Err(TableError::EmptyPrimary)
+        ));
+    }
+}
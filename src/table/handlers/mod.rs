@@ -1,2 +1,27 @@
+pub mod audit;
+pub mod computed;
+pub mod crypto;
+pub mod diff;
+pub mod export;
 pub mod helper;
+pub mod hooks;
+pub mod json_rows;
+pub mod merge;
+pub mod pager;
+pub mod row;
+pub mod search;
 pub mod service;
+pub mod snapshot;
+pub mod sql_dump;
+pub mod sqlite;
+pub mod typed;
+pub mod view;
+
+// Folder kosong sementara untuk test yang menulis file
+#[cfg(test)]
+pub(crate) fn test_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("mytabel-test-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,352 @@
+use crate::config;
+use crate::table::{
+    handlers::{search::SearchQuery, service::DataTable},
+    models::RowTable,
+};
+use crate::tr;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use unicode_width::UnicodeWidthChar;
+use unicode_width::UnicodeWidthStr;
+
+// Jumlah baris per halaman (default)
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+// Lebar maksimum satu sel (default), sisanya dipotong/dibungkus
+pub const DEFAULT_MAX_CELL_WIDTH: usize = 30;
+// Penanda teks yang cocok dengan pencarian: tebal + kuning
+const HIGHLIGHT_START: &str = "\x1b[1;33m";
+const HIGHLIGHT_END: &str = "\x1b[0m";
+
+#[derive(Debug, Clone)]
+pub struct ViewOptions {
+    pub page: usize,                    // 0-based
+    pub limit: usize,                   // baris per halaman
+    pub col_offset: usize,              // kolom pertama yang tampil (scroll horizontal)
+    pub max_cell_width: usize,          // batas lebar sel
+    pub term_width: Option<usize>,      // None → tanpa batas lebar layar
+    pub wrap: bool,                     // bungkus teks panjang, bukan potong
+    pub pin_primary: bool,              // primary selalu tampil di kiri
+    pub highlight: Option<SearchQuery>, // tandai teks yang cocok (hasil `search`)
+}
+
+impl Default for ViewOptions {
+    fn default() -> Self {
+        Self {
+            page: 0,
+            limit: config::current().page_size,
+            col_offset: 0,
+            max_cell_width: DEFAULT_MAX_CELL_WIDTH,
+            term_width: terminal_width(),
+            wrap: false,
+            pin_primary: true,
+            highlight: None,
+        }
+    }
+}
+
+// Lebar layar yang dipaksa dari luar (mis. lebar terminal klien daemon).
+// usize::MAX → tidak dipaksa, 0 → tanpa batas lebar
+static WIDTH_OVERRIDE: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+pub fn override_terminal_width(width: Option<usize>) {
+    WIDTH_OVERRIDE.store(width.unwrap_or(0), Ordering::Relaxed);
+}
+
+// Lebar terminal: override, lalu ukuran asli tty, lalu $COLUMNS, selain itu None
+pub fn terminal_width() -> Option<usize> {
+    match WIDTH_OVERRIDE.load(Ordering::Relaxed) {
+        usize::MAX => {}
+        0 => return None,
+        width => return Some(width),
+    }
+    if let Some((terminal_size::Width(w), _)) = terminal_size::terminal_size() {
+        return Some(w as usize);
+    }
+    env::var("COLUMNS").ok().and_then(|c| c.parse().ok())
+}
+
+// Hasil pemilihan kolom & baris untuk satu layar
+pub struct PageView<'a> {
+    pub rows: &'a [&'a RowTable],
+    pub columns: Vec<usize>,
+    pub widths: Vec<usize>,
+    pub page_count: usize,
+    pub first_row: usize,
+    pub total_rows: usize,
+    pub hidden_right: bool,
+}
+
+impl DataTable {
+    // Pilih baris halaman aktif + kolom yang muat di layar
+    pub fn page_view<'a>(&self, rows: &'a [&'a RowTable], opts: &ViewOptions) -> PageView<'a> {
+        let limit = opts.limit.max(1);
+        let page_count = rows.len().div_ceil(limit).max(1);
+        let page = opts.page.min(page_count - 1);
+        let first_row = (page * limit).min(rows.len());
+        let page_rows = &rows[first_row..(first_row + limit).min(rows.len())];
+
+        // Urutan kandidat kolom: primary (jika dipin) lalu mulai dari col_offset
+        let pinned = self.primary_index.filter(|_| opts.pin_primary);
+        let mut order: Vec<usize> = pinned.into_iter().collect();
+        order.extend((opts.col_offset..self.column.len()).filter(|i| Some(*i) != pinned));
+
+        let mut columns = Vec::new();
+        let mut widths = Vec::new();
+        let mut used = 1; // border kiri
+        for &i in &order {
+            let natural = self.natural_width(page_rows, i);
+            let width = natural.min(opts.max_cell_width.max(1));
+            // tiap kolom: isi + 2 padding + 1 border
+            if let Some(term) = opts.term_width
+                && !columns.is_empty()
+                && used + width + 3 > term
+            {
+                break;
+            }
+            used += width + 3;
+            columns.push(i);
+            widths.push(width);
+        }
+        let hidden_right = order.last().is_some_and(|last| !columns.contains(last));
+
+        PageView {
+            rows: page_rows,
+            columns,
+            widths,
+            page_count,
+            first_row,
+            total_rows: rows.len(),
+            hidden_right,
+        }
+    }
+
+    // Lebar terlebar di kolom (header ikut dihitung, + tanda `*` primary)
+    pub fn natural_width(&self, rows: &[&RowTable], index: usize) -> usize {
+        let col = &self.column[index];
+        let header = col.colname.width() + usize::from(col.is_primary);
+        rows.iter()
+            .filter_map(|r| r.value.get(index))
+            .map(|v| v.to_string().width())
+            .fold(header, usize::max)
+    }
+
+    pub fn pretty_table_view(&self, view: &PageView, opts: &ViewOptions) -> prettytable::Table {
+        let mut table = prettytable::Table::new();
+        table.set_format(config::current().table_format());
+
+        let fit = |s: &str, width: usize| {
+            if opts.wrap {
+                wrap(s, width)
+            } else {
+                truncate(s, width)
+            }
+        };
+
+        let header_cells: Vec<prettytable::Cell> = view
+            .columns
+            .iter()
+            .zip(&view.widths)
+            .map(|(&i, &w)| {
+                let col = &self.column[i];
+                let name = if col.is_primary {
+                    format!("*{}", col.colname)
+                } else {
+                    col.colname.clone()
+                };
+                config::current().style_header(prettytable::Cell::new(&fit(&name, w)))
+            })
+            .collect();
+        table.add_row(prettytable::Row::new(header_cells));
+
+        // Kode warna hanya untuk terminal; output yang dialihkan tetap teks biasa
+        let highlight = opts
+            .highlight
+            .as_ref()
+            .filter(|_| io::stdout().is_terminal());
+        for row in view.rows {
+            let cells: Vec<prettytable::Cell> = view
+                .columns
+                .iter()
+                .zip(&view.widths)
+                .map(|(&i, &w)| {
+                    let s = row.value.get(i).map(|v| v.to_string()).unwrap_or_default();
+                    let text = fit(&s, w);
+                    match &highlight {
+                        Some(query) => prettytable::Cell::new(&query.highlight(
+                            &text,
+                            HIGHLIGHT_START,
+                            HIGHLIGHT_END,
+                        )),
+                        None => prettytable::Cell::new(&text),
+                    }
+                })
+                .collect();
+            table.add_row(prettytable::Row::new(cells));
+        }
+
+        table
+    }
+
+    // Print tabel rapi: satu halaman, muat di layar, + baris status
+    pub fn show_data_table(&self, rows: &[&RowTable], opts: &ViewOptions) {
+        let view = self.page_view(rows, opts);
+        self.pretty_table_view(&view, opts).printstd();
+        println!("{}", self.page_status(&view, opts));
+    }
+
+    pub fn page_status(&self, view: &PageView, opts: &ViewOptions) -> String {
+        let page = opts.page.min(view.page_count - 1) + 1;
+        let last_row = view.first_row + view.rows.len();
+        let mut status = tr!(
+            "pager.status",
+            page,
+            view.page_count,
+            if view.rows.is_empty() {
+                0
+            } else {
+                view.first_row + 1
+            },
+            last_row,
+            view.total_rows
+        );
+        let hidden = self.column.len() - view.columns.len();
+        if hidden > 0 {
+            status.push_str(&tr!("pager.hidden", hidden));
+            if view.hidden_right {
+                status.push_str(" →");
+            }
+        }
+        status
+    }
+}
+
+// Potong teks agar muat di `width`, tambahkan elipsis
+pub fn truncate(s: &str, width: usize) -> String {
+    let s = s.replace('\n', " ");
+    if s.width() <= width {
+        return s;
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push('…');
+    out
+}
+
+// Bungkus teks menjadi beberapa baris selebar `width`
+pub fn wrap(s: &str, width: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for part in s.split('\n') {
+        let mut line = String::new();
+        let mut used = 0;
+        for c in part.chars() {
+            let w = c.width().unwrap_or(0);
+            if used + w > width && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+            line.push(c);
+            used += w;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::value::{ColumnType, Value};
+
+    fn opts(page: usize, limit: usize, term_width: Option<usize>) -> ViewOptions {
+        ViewOptions {
+            page,
+            limit,
+            col_offset: 0,
+            max_cell_width: 10,
+            term_width,
+            wrap: false,
+            pin_primary: true,
+            highlight: None,
+        }
+    }
+
+    // nama, id (primary), ket: 5 baris
+    fn table() -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![
+                ("nama", ColumnType::Text),
+                ("id", ColumnType::Numb),
+                ("ket", ColumnType::Text),
+            ])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        for id in 1..=5 {
+            table
+                .add_row(vec![
+                    Value::Text(format!("barang {}", id)),
+                    Value::Numb(id),
+                    Value::Text("keterangan yang panjang sekali".into()),
+                ])
+                .unwrap();
+        }
+        table
+    }
+
+    #[test]
+    fn pages_are_clamped() {
+        let table = table();
+        let rows: Vec<&RowTable> = table.row.iter().collect();
+        let view = table.page_view(&rows, &opts(1, 2, None));
+        assert_eq!(
+            (view.page_count, view.first_row, view.rows.len()),
+            (3, 2, 2)
+        );
+        let view = table.page_view(&rows, &opts(9, 2, None));
+        assert_eq!((view.first_row, view.rows.len()), (4, 1));
+        let empty = table.page_view(&[], &opts(0, 2, None));
+        assert_eq!((empty.page_count, empty.rows.len()), (1, 0));
+    }
+
+    #[test]
+    fn columns_fit_terminal_with_primary_pinned() {
+        let table = table();
+        let rows: Vec<&RowTable> = table.row.iter().collect();
+        let view = table.page_view(&rows, &opts(0, 5, None));
+        assert_eq!(view.columns, [1, 0, 2]);
+        assert_eq!(view.widths, [3, 8, 10]);
+
+        // │ id │ nama     │ = 1 + (3+3) + (8+3)
+        let view = table.page_view(&rows, &opts(0, 5, Some(18)));
+        assert_eq!(view.columns, [1, 0]);
+        assert!(view.hidden_right);
+        let status = table.page_status(&view, &opts(0, 5, Some(18)));
+        assert!(status.ends_with(" →"), "{}", status);
+
+        let scrolled = ViewOptions {
+            col_offset: 2,
+            ..opts(0, 5, None)
+        };
+        assert_eq!(table.page_view(&rows, &scrolled).columns, [1, 2]);
+    }
+
+    #[test]
+    fn truncate_and_wrap_use_display_width() {
+        assert_eq!(truncate("abcdef", 6), "abcdef");
+        assert_eq!(truncate("abcdefg", 6), "abcde…");
+        assert_eq!(truncate("日本語テキスト", 6), "日本…");
+        assert_eq!(truncate("a\nb", 5), "a b");
+        assert_eq!(wrap("abcdefg", 3), "abc\ndef\ng");
+        assert_eq!(wrap("日本語", 4), "日本\n語");
+        assert_eq!(wrap("ab\ncd", 5), "ab\ncd");
+    }
+}
@@ -0,0 +1,402 @@
+use crate::error::TableError;
+use crate::table::{
+    handlers::service::DataTable,
+    models::{ColumnTable, RowTable},
+    value::Value,
+};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+// Posisi kolom berdasarkan nama; dibangun sekali per iterasi lalu dibagi ke semua Row
+type ColumnPositions = Rc<HashMap<String, usize>>;
+
+// Perubahan dari `iter_mut` yang belum diterapkan: posisi baris → nilai baru
+type PendingEdits = Rc<RefCell<BTreeMap<usize, Vec<Value>>>>;
+
+// Tampilan baca satu baris; akses nilai lewat nama kolom
+#[derive(Clone)]
+pub struct Row<'a> {
+    position: usize,
+    row: &'a RowTable,
+    positions: ColumnPositions,
+}
+
+impl<'a> Row<'a> {
+    // Urutan baris di dalam tabel (0-based)
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn get(&self, colname: &str) -> Option<&'a Value> {
+        let index = *self.positions.get(colname)?;
+        self.row.value.get(index)
+    }
+
+    pub fn get_text(&self, colname: &str) -> Option<&'a str> {
+        match self.get(colname)? {
+            Value::Text(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn get_numb(&self, colname: &str) -> Option<i64> {
+        match self.get(colname)? {
+            Value::Numb(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn get_bool(&self, colname: &str) -> Option<bool> {
+        match self.get(colname)? {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn get_char(&self, colname: &str) -> Option<char> {
+        match self.get(colname)? {
+            Value::Char(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    pub fn is_empty(&self, colname: &str) -> bool {
+        matches!(self.get(colname), Some(Value::Empty))
+    }
+
+    // Semua nilai sesuai urutan kolom
+    pub fn values(&self) -> &'a [Value] {
+        &self.row.value
+    }
+}
+
+// Tampilan ubah satu baris; tipe divalidasi, kolom primary tidak bisa diubah di sini.
+// Perubahan lewat pipeline set_value_where (trigger, hook, audit), jadi memegang tabel
+pub struct RowMut<'a> {
+    position: usize,
+    table: &'a mut DataTable,
+    positions: ColumnPositions,
+}
+
+impl RowMut<'_> {
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn get(&self, colname: &str) -> Option<&Value> {
+        let index = *self.positions.get(colname)?;
+        self.values().get(index)
+    }
+
+    // Sama seperti set_value_where untuk baris ini: before-hook boleh mengubah/membatalkan,
+    // reject & after-hook yang gagal membatalkan perubahan
+    pub fn set(&mut self, colname: &str, value: Value) -> Result<(), TableError> {
+        let index = settable(&self.table.column, &self.positions, colname, &value)?;
+        let mut new = self.values().to_vec();
+        new[index] = value;
+        self.table.update_rows(vec![(self.position, new)], false)
+    }
+
+    pub fn values(&self) -> &[Value] {
+        &self.table.row[self.position].value
+    }
+}
+
+// Iterator dari `iter_mut`: tiap baris jadi RowEdit. Perubahan dikumpulkan lalu
+// diterapkan sekaligus oleh `apply` (satu kali pipeline set_value_where);
+// iterator dibuang tanpa `apply` → semua perubahan batal
+#[must_use = "perubahan baru masuk ke tabel setelah `apply`"]
+pub struct RowsMut<'a> {
+    table: &'a mut DataTable,
+    next: usize,
+    columns: Rc<Vec<ColumnTable>>,
+    positions: ColumnPositions,
+    pending: PendingEdits,
+}
+
+impl Iterator for RowsMut<'_> {
+    type Item = RowEdit;
+
+    fn next(&mut self) -> Option<RowEdit> {
+        let row = self.table.row.get(self.next)?;
+        let position = self.next;
+        self.next += 1;
+        // Baris yang sudah diubah lewat RowEdit sebelumnya → tampilkan nilai barunya
+        let values = self
+            .pending
+            .borrow()
+            .get(&position)
+            .cloned()
+            .unwrap_or_else(|| row.value.clone());
+        Some(RowEdit {
+            position,
+            values,
+            columns: Rc::clone(&self.columns),
+            positions: Rc::clone(&self.positions),
+            pending: Rc::clone(&self.pending),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.table.row.len() - self.next;
+        (left, Some(left))
+    }
+}
+
+impl RowsMut<'_> {
+    // Terapkan semua perubahan: before-hook, validasi, after-hook & audit seperti
+    // set_value_where; gagal → tidak ada yang berubah
+    pub fn apply(self) -> Result<(), TableError> {
+        let edits: Vec<(usize, Vec<Value>)> = self.pending.take().into_iter().collect();
+        if edits.is_empty() {
+            return Ok(());
+        }
+        self.table.update_rows(edits, false)
+    }
+}
+
+// Salinan satu baris dari `iter_mut`; `set` divalidasi seperti RowMut
+pub struct RowEdit {
+    position: usize,
+    values: Vec<Value>,
+    columns: Rc<Vec<ColumnTable>>,
+    positions: ColumnPositions,
+    pending: PendingEdits,
+}
+
+impl RowEdit {
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    // Nilai terbaru, termasuk yang sudah di-`set` tapi belum diterapkan
+    pub fn get(&self, colname: &str) -> Option<&Value> {
+        self.values.get(*self.positions.get(colname)?)
+    }
+
+    pub fn set(&mut self, colname: &str, value: Value) -> Result<(), TableError> {
+        let index = settable(&self.columns, &self.positions, colname, &value)?;
+        self.values[index] = value;
+        self.pending
+            .borrow_mut()
+            .insert(self.position, self.values.clone());
+        Ok(())
+    }
+
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+}
+
+// Indeks kolom yang boleh diisi `value` lewat RowMut/RowEdit
+fn settable(
+    columns: &[ColumnTable],
+    positions: &HashMap<String, usize>,
+    colname: &str,
+    value: &Value,
+) -> Result<usize, TableError> {
+    let index = *positions
+        .get(colname)
+        .ok_or_else(|| TableError::ColumnNotFound(colname.into()))?;
+    let col = &columns[index];
+
+    // Ubah primary harus lewat set_value_where agar keunikan tetap dicek
+    if col.is_primary {
+        return Err(TableError::PrimaryReadOnly(colname.into()));
+    }
+    if col.expr.is_some() {
+        return Err(TableError::ComputedReadOnly(colname.into()));
+    }
+    if !DataTable::_validate_type_column_and_row(&col.coltype, value) {
+        return Err(TableError::TypeMismatch(colname.into()));
+    }
+    Ok(index)
+}
+
+impl DataTable {
+    pub fn column_position(&self, colname: &str) -> Result<usize, TableError> {
+        self.column
+            .iter()
+            .position(|c| c.colname == colname)
+            .ok_or_else(|| TableError::ColumnNotFound(colname.into()))
+    }
+
+    // Parse input teks sesuai tipe kolom bernama `colname`
+    pub fn parse_value(&self, colname: &str, input: &str) -> Result<Value, TableError> {
+        self._parse_input_to_value_type(self.column_position(colname)?, input)
+    }
+
+    fn column_positions(&self) -> ColumnPositions {
+        Rc::new(
+            self.column
+                .iter()
+                .enumerate()
+                .map(|(i, c)| (c.colname.clone(), i))
+                .collect(),
+        )
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Row<'_>> + '_ {
+        let positions = self.column_positions();
+        self.row.iter().enumerate().map(move |(position, row)| Row {
+            position,
+            row,
+            positions: Rc::clone(&positions),
+        })
+    }
+
+    // Ubah banyak baris lewat Iterator biasa; perubahan masuk setelah `apply`:
+    //     let mut rows = table.iter_mut();
+    //     for mut row in rows.by_ref() { row.set("stok", Value::Numb(0))?; }
+    //     rows.apply()?;
+    pub fn iter_mut(&mut self) -> RowsMut<'_> {
+        RowsMut {
+            next: 0,
+            columns: Rc::new(self.column.clone()),
+            positions: self.column_positions(),
+            pending: PendingEdits::default(),
+            table: self,
+        }
+    }
+
+    pub fn row_mut(&mut self, position: usize) -> Option<RowMut<'_>> {
+        (position < self.row.len()).then(|| RowMut {
+            position,
+            positions: self.column_positions(),
+            table: self,
+        })
+    }
+
+    // Ubah baris satu per satu, tiap `set` langsung diterapkan (beda dengan
+    // `iter_mut` yang menerapkan sekaligus); error pertama menghentikan iterasi
+    pub fn for_each_mut<F>(&mut self, mut f: F) -> Result<(), TableError>
+    where
+        F: FnMut(&mut RowMut<'_>) -> Result<(), TableError>,
+    {
+        let positions = self.column_positions();
+        for position in 0..self.row.len() {
+            f(&mut RowMut {
+                position,
+                table: self,
+                positions: Rc::clone(&positions),
+            })?;
+        }
+        Ok(())
+    }
+
+    // Baris yang memenuhi predikat
+    pub fn filter<F>(&self, mut predicate: F) -> Vec<Row<'_>>
+    where
+        F: FnMut(&Row<'_>) -> bool,
+    {
+        self.iter().filter(|row| predicate(row)).collect()
+    }
+
+    // Cari baris berdasarkan nilai primary
+    pub fn find_by_primary(&self, value: &Value) -> Result<Option<Row<'_>>, TableError> {
+        let index = self.primary_index.ok_or(TableError::NoPrimary)?;
+        Ok(self
+            .iter()
+            .find(|row| row.values().get(index) == Some(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::models::{HookEvent, HookTiming};
+    use crate::table::value::ColumnType;
+
+    // id (primary), nama, stok: 1 a 0, 2 b 5, 3 c 0
+    fn table() -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![
+                ("id", ColumnType::Numb),
+                ("nama", ColumnType::Text),
+                ("stok", ColumnType::Numb),
+            ])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        for (id, nama, stok) in [(1, "a", 0), (2, "b", 5), (3, "c", 0)] {
+            table
+                .add_row(vec![
+                    Value::Numb(id),
+                    Value::Text(nama.into()),
+                    Value::Numb(stok),
+                ])
+                .unwrap();
+        }
+        table
+    }
+
+    fn names(table: &DataTable) -> Vec<&str> {
+        table.iter().filter_map(|r| r.get_text("nama")).collect()
+    }
+
+    #[test]
+    fn row_views_read_by_column_name() {
+        let table = table();
+        let empty = table.filter(|r| r.get_numb("stok") == Some(0));
+        assert_eq!(
+            empty.iter().map(|r| r.position()).collect::<Vec<_>>(),
+            [0, 2]
+        );
+        let row = table.find_by_primary(&Value::Numb(2)).unwrap().unwrap();
+        assert_eq!(row.get_text("nama"), Some("b"));
+        assert_eq!(row.get("tidak_ada"), None);
+        assert!(matches!(
+            DataTable::default().find_by_primary(&Value::Numb(1)),
+            Err(TableError::NoPrimary)
+        ));
+    }
+
+    #[test]
+    fn iter_mut_applies_all_edits_through_hooks() {
+        let mut table = table();
+        table.enable_audit();
+        let mut rows = table.iter_mut();
+        for mut row in rows.by_ref() {
+            if row.get("stok") == Some(&Value::Numb(0)) {
+                row.set("nama", Value::Text("habis".into())).unwrap();
+            }
+        }
+        rows.apply().unwrap();
+        assert_eq!(names(&table), ["habis", "b", "habis"]);
+        assert_eq!(table.audit.len(), 2);
+    }
+
+    #[test]
+    fn iter_mut_checks_types_and_drops_unapplied_edits() {
+        let mut table = table();
+        let mut rows = table.iter_mut();
+        let mut row = rows.next().unwrap();
+        assert!(matches!(
+            row.set("stok", Value::Text("x".into())),
+            Err(TableError::TypeMismatch(_))
+        ));
+        assert!(matches!(
+            row.set("id", Value::Numb(9)),
+            Err(TableError::PrimaryReadOnly(_))
+        ));
+        row.set("nama", Value::Text("z".into())).unwrap();
+        assert_eq!(row.get("nama"), Some(&Value::Text("z".into())));
+        drop(rows);
+        assert_eq!(names(&table), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn iter_mut_rejected_by_hook_changes_nothing() {
+        let mut table = table();
+        table.add_hook(HookTiming::After, HookEvent::SetValueWhere, |_| {
+            Err(TableError::InvalidValue("tolak".into()))
+        });
+        let mut rows = table.iter_mut();
+        for mut row in rows.by_ref() {
+            row.set("stok", Value::Numb(1)).unwrap();
+        }
+        assert!(rows.apply().is_err());
+        assert!(table.iter().all(|r| r.get_numb("stok") != Some(1)));
+    }
+}
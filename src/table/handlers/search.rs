@@ -0,0 +1,577 @@
+use crate::error::TableError;
+use crate::table::{handlers::service::DataTable, models::RowTable, value::Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+
+// Skor satu kata kueri; baris diurutkan dari jumlah skor tertinggi
+const SCORE_EXACT: u32 = 100; // sama dengan satu kata
+const SCORE_PREFIX: u32 = 80; // awal kata
+const SCORE_SUBSTRING: u32 = 60; // bagian teks
+const SCORE_FUZZY: u32 = 40; // salah ketik, dikurangi 10 per huruf beda
+const SCORE_FUZZY_PREFIX: u32 = 30; // salah ketik di awal kata
+
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    pub fuzzy: bool,          // toleransi salah ketik
+    pub columns: Vec<String>, // kosong → semua kolom
+    pub limit: Option<usize>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            fuzzy: true,
+            columns: Vec::new(),
+            limit: None,
+        }
+    }
+}
+
+// Kueri yang sudah dipecah per kata (huruf kecil); semua kata harus cocok
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    terms: Vec<Vec<char>>,
+    fuzzy: bool,
+}
+
+// Satu baris hasil: skor & kolom yang cocok
+#[derive(Debug, Clone)]
+pub struct SearchHit<'a> {
+    pub row: &'a RowTable,
+    pub score: u32,
+    pub columns: Vec<usize>,
+}
+
+// Indeks kata → baris, untuk tabel besar yang dicari berulang kali
+// (mis. menu cari). Harus dibangun ulang setelah tabel berubah
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    columns: Vec<usize>,
+    // Kata unik (huruf kecil) + baris yang memuatnya
+    words: Vec<(Vec<char>, Vec<usize>)>,
+    // Setiap akhiran kata → indeks di `words`; kata yang sama/diawali/memuat
+    // kata kueri cukup dicari lewat rentang awalan
+    suffixes: BTreeMap<String, Vec<usize>>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str, fuzzy: bool) -> Self {
+        Self {
+            terms: input.split_whitespace().map(lower_chars).collect(),
+            fuzzy,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    // Skor terbaik satu kata kueri di `text` + posisi (indeks char) yang cocok
+    fn match_term(&self, term: &[char], text: &[char]) -> Option<(u32, Range<usize>)> {
+        let words = words(text);
+        if let Some(w) = words.iter().find(|&w| text[w.clone()] == *term) {
+            return Some((SCORE_EXACT, w.clone()));
+        }
+        if let Some(w) = words.iter().find(|&w| text[w.clone()].starts_with(term)) {
+            return Some((SCORE_PREFIX, w.start..w.start + term.len()));
+        }
+        if let Some(start) = find(text, term) {
+            return Some((SCORE_SUBSTRING, start..start + term.len()));
+        }
+
+        let typos = self.max_typos(term);
+        if typos == 0 {
+            return None;
+        }
+        words
+            .iter()
+            .filter_map(|w| fuzzy_score(term, &text[w.clone()], typos).map(|(s, len)| (s, w, len)))
+            .max_by_key(|(score, _, _)| *score)
+            .map(|(score, w, len)| (score, w.start..w.start + len))
+    }
+
+    // Kata pendek harus tepat; 3-5 huruf boleh 1 salah, lebih panjang boleh 2
+    fn max_typos(&self, term: &[char]) -> usize {
+        match (self.fuzzy, term.len()) {
+            (false, _) | (_, 0..=2) => 0,
+            (_, 3..=5) => 1,
+            _ => 2,
+        }
+    }
+
+    // Skor baris (jumlah skor tiap kata) & kolom yang cocok; None jika ada kata yang tidak cocok
+    fn score_row(&self, row: &RowTable, columns: &[usize]) -> Option<(u32, Vec<usize>)> {
+        let cells: Vec<(usize, Vec<char>)> = columns
+            .iter()
+            .filter_map(|&i| searchable_text(row.value.get(i)?).map(|t| (i, t)))
+            .collect();
+        let mut total = 0;
+        let mut matched = Vec::new();
+        for term in &self.terms {
+            let (score, column) = cells
+                .iter()
+                .filter_map(|(i, text)| Some((self.match_term(term, text)?.0, *i)))
+                .max_by_key(|(score, _)| *score)?;
+            total += score;
+            if !matched.contains(&column) {
+                matched.push(column);
+            }
+        }
+        matched.sort_unstable();
+        Some((total, matched))
+    }
+
+    // Posisi (indeks char) yang cocok di `text`, urut & digabung (untuk highlight)
+    pub fn ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let lowered = lower_chars(text);
+        let mut ranges: Vec<Range<usize>> = self
+            .terms
+            .iter()
+            .filter_map(|term| self.match_term(term, &lowered).map(|(_, r)| r))
+            .collect();
+        ranges.sort_by_key(|r| r.start);
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+
+    // Bungkus bagian yang cocok dengan `start`/`end` (mis. kode warna ANSI)
+    pub fn highlight(&self, text: &str, start: &str, end: &str) -> String {
+        let ranges = self.ranges(text);
+        if ranges.is_empty() {
+            return text.to_string();
+        }
+        let mut out = String::new();
+        for (i, c) in text.chars().enumerate() {
+            if ranges.iter().any(|r| r.start == i) {
+                out.push_str(start);
+            }
+            out.push(c);
+            if ranges.iter().any(|r| r.end == i + 1) {
+                out.push_str(end);
+            }
+        }
+        out
+    }
+}
+
+impl SearchIndex {
+    pub fn build(table: &DataTable, opts: &SearchOptions) -> Result<Self, TableError> {
+        let columns = table.search_columns(opts)?;
+        let mut ids: HashMap<Vec<char>, usize> = HashMap::new();
+        let mut words_rows: Vec<(Vec<char>, Vec<usize>)> = Vec::new();
+        for (index, row) in table.row.iter().enumerate() {
+            for text in columns
+                .iter()
+                .filter_map(|&i| searchable_text(row.value.get(i)?))
+            {
+                for w in words(&text) {
+                    let word = text[w].to_vec();
+                    let id = *ids.entry(word.clone()).or_insert_with(|| {
+                        words_rows.push((word, Vec::new()));
+                        words_rows.len() - 1
+                    });
+                    let rows = &mut words_rows[id].1;
+                    if rows.last() != Some(&index) {
+                        rows.push(index);
+                    }
+                }
+            }
+        }
+
+        let mut suffixes: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (id, (word, _)) in words_rows.iter().enumerate() {
+            for start in 0..word.len() {
+                suffixes
+                    .entry(word[start..].iter().collect())
+                    .or_default()
+                    .push(id);
+            }
+        }
+        Ok(Self {
+            columns,
+            words: words_rows,
+            suffixes,
+        })
+    }
+
+    // Baris yang mungkin cocok dengan semua kata; None → semua baris perlu dicek
+    // (kata berisi tanda baca bisa cocok melewati batas kata)
+    fn candidates(&self, query: &SearchQuery) -> Option<HashSet<usize>> {
+        let mut result: Option<HashSet<usize>> = None;
+        for term in &query.terms {
+            if !term.iter().all(|c| c.is_alphanumeric()) {
+                continue;
+            }
+            let mut found: HashSet<usize> = self.containing(term).collect();
+            // Salah ketik: k edit merusak paling banyak 2k potongan kata kueri
+            // (tukar huruf bisa mengenai dua), jadi dari 2k+1 potongan minimal satu
+            // tetap utuh di kata yang cocok. Kandidat dari potongan dicek ulang
+            let typos = query.max_typos(term);
+            if typos > 0 {
+                for piece in pieces(term, 2 * typos + 1) {
+                    let extra: Vec<usize> = self
+                        .containing(piece)
+                        .filter(|id| !found.contains(id))
+                        .filter(|&id| query.match_term(term, &self.words[id].0).is_some())
+                        .collect();
+                    found.extend(extra);
+                }
+            }
+            let rows: HashSet<usize> = found
+                .into_iter()
+                .flat_map(|id| self.words[id].1.iter().copied())
+                .collect();
+            result = Some(match result {
+                Some(prev) => prev.intersection(&rows).copied().collect(),
+                None => rows,
+            });
+        }
+        result
+    }
+
+    // Kata (indeks di `words`) yang memuat `part`; bisa berulang
+    fn containing(&self, part: &[char]) -> impl Iterator<Item = usize> + '_ {
+        let key: String = part.iter().collect();
+        self.suffixes
+            .range(key.clone()..)
+            .take_while(move |(suffix, _)| suffix.starts_with(&key))
+            .flat_map(|(_, ids)| ids.iter().copied())
+    }
+}
+
+// Bagi `term` jadi `n` potongan berurutan yang panjangnya hampir sama
+fn pieces(term: &[char], n: usize) -> Vec<&[char]> {
+    let n = n.min(term.len()).max(1);
+    (0..n)
+        .map(|i| &term[i * term.len() / n..(i + 1) * term.len() / n])
+        .collect()
+}
+
+impl DataTable {
+    // Cari baris yang memuat semua kata kueri (tidak peka huruf besar/kecil), urut skor.
+    // Kolom Text/Char dicari apa adanya, tipe lain sebagai teks; Empty dilewati
+    pub fn search<'a>(
+        &'a self,
+        query: &SearchQuery,
+        opts: &SearchOptions,
+        index: Option<&SearchIndex>,
+    ) -> Result<Vec<SearchHit<'a>>, TableError> {
+        let columns = self.search_columns(opts)?;
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        // Indeks untuk kolom lain tidak bisa dipakai
+        let candidates = index
+            .filter(|index| index.columns == columns)
+            .and_then(|index| index.candidates(query));
+
+        let mut hits: Vec<SearchHit> = self
+            .row
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| candidates.as_ref().is_none_or(|c| c.contains(i)))
+            .filter_map(|(_, row)| {
+                let (score, columns) = query.score_row(row, &columns)?;
+                Some(SearchHit {
+                    row,
+                    score,
+                    columns,
+                })
+            })
+            .collect();
+        // sort stabil: skor sama → urutan asli
+        hits.sort_by_key(|h| std::cmp::Reverse(h.score));
+        if let Some(limit) = opts.limit {
+            hits.truncate(limit);
+        }
+        Ok(hits)
+    }
+
+    fn search_columns(&self, opts: &SearchOptions) -> Result<Vec<usize>, TableError> {
+        if opts.columns.is_empty() {
+            return Ok((0..self.column.len()).collect());
+        }
+        opts.columns
+            .iter()
+            .map(|c| self.column_position(c))
+            .collect()
+    }
+}
+
+// Teks yang dicari dari satu nilai; Empty → None
+fn searchable_text(value: &Value) -> Option<Vec<char>> {
+    match value {
+        Value::Empty => None,
+        other => Some(lower_chars(&other.to_string())),
+    }
+}
+
+// Huruf kecil per char (jumlah char tetap sama agar posisi highlight cocok)
+fn lower_chars(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+// Posisi kata (deret huruf/angka) di teks
+fn words(text: &[char]) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text.iter().enumerate() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push(s..text.len());
+    }
+    words
+}
+
+fn find(text: &[char], term: &[char]) -> Option<usize> {
+    if term.is_empty() || term.len() > text.len() {
+        return None;
+    }
+    text.windows(term.len()).position(|w| w == term)
+}
+
+// Skor salah ketik terhadap satu kata (utuh atau awalnya) + panjang bagian yang cocok
+fn fuzzy_score(term: &[char], word: &[char], typos: usize) -> Option<(u32, usize)> {
+    let whole = edit_distance(term, word).filter(|d| *d <= typos);
+    if let Some(d) = whole {
+        return Some((SCORE_FUZZY - 10 * d as u32, word.len()));
+    }
+    if word.len() > term.len() {
+        let prefix = &word[..term.len()];
+        if let Some(d) = edit_distance(term, prefix).filter(|d| *d <= typos) {
+            return Some((SCORE_FUZZY_PREFIX - 10 * d as u32, term.len()));
+        }
+    }
+    None
+}
+
+// Jarak edit (sisip/hapus/ganti/tukar dua huruf bersebelahan)
+fn edit_distance(a: &[char], b: &[char]) -> Option<usize> {
+    // Beda panjang saja sudah melewati batas maksimum (2) → tidak perlu dihitung
+    if a.len().abs_diff(b.len()) > 2 {
+        return None;
+    }
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    Some(d[a.len()][b.len()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::value::ColumnType;
+
+    fn table(rows: &[(&str, &str)]) -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![
+                ("id", ColumnType::Numb),
+                ("nama", ColumnType::Text),
+                ("kota", ColumnType::Text),
+            ])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        for (i, (nama, kota)) in rows.iter().enumerate() {
+            table
+                .add_row(vec![
+                    Value::Numb(i as i64 + 1),
+                    Value::Text(nama.to_string()),
+                    Value::Text(kota.to_string()),
+                ])
+                .unwrap();
+        }
+        table
+    }
+
+    // Nama baris hasil, urut skor
+    fn names(table: &DataTable, query: &str, opts: &SearchOptions) -> Vec<String> {
+        let query = SearchQuery::parse(query, opts.fuzzy);
+        table
+            .search(&query, opts, None)
+            .unwrap()
+            .iter()
+            .map(|hit| hit.row.value[1].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn exact_beats_prefix_beats_substring_beats_typo() {
+        let t = table(&[
+            ("kopi susu", "-"), // kata utuh
+            ("kopiah", "-"),    // awal kata
+            ("es kopi", "-"),   // kata utuh
+            ("makopi", "-"),    // bagian kata
+            ("kopu", "-"),      // salah ketik satu huruf
+            ("teh manis", "-"), // tidak cocok
+        ]);
+        assert_eq!(
+            names(&t, "kopi", &SearchOptions::default()),
+            vec!["kopi susu", "es kopi", "kopiah", "makopi", "kopu"]
+        );
+    }
+
+    #[test]
+    fn every_term_must_match_and_scores_add_up() {
+        let t = table(&[
+            ("budi", "bandung"),
+            ("budi", "jakarta"),
+            ("budiman", "bandung"),
+        ]);
+        let opts = SearchOptions::default();
+        assert_eq!(names(&t, "budi bandung", &opts), vec!["budi", "budiman"]);
+        assert_eq!(names(&t, "BUDI Jakarta", &opts), vec!["budi"]);
+        assert!(names(&t, "budi surabaya", &opts).is_empty());
+    }
+
+    #[test]
+    fn typo_tolerance_depends_on_word_length() {
+        let t = table(&[("ani", "-"), ("bandung", "-"), ("surabaya", "-")]);
+        let opts = SearchOptions::default();
+        // ≤ 2 huruf harus tepat, 3-5 boleh 1 salah, lebih panjang boleh 2
+        assert!(names(&t, "ano", &opts).contains(&"ani".to_string()));
+        assert_eq!(names(&t, "bnadung", &opts), vec!["bandung"]); // tukar huruf
+        assert_eq!(names(&t, "surbaia", &opts), vec!["surabaya"]);
+        assert!(names(&t, "srbaia", &opts).is_empty());
+
+        let exact = SearchOptions {
+            fuzzy: false,
+            ..SearchOptions::default()
+        };
+        assert!(names(&t, "bnadung", &exact).is_empty());
+    }
+
+    #[test]
+    fn columns_and_limit() {
+        let t = table(&[("bandung", "jakarta"), ("ani", "bandung"), ("bandung", "-")]);
+        let opts = SearchOptions {
+            columns: vec!["kota".into()],
+            ..SearchOptions::default()
+        };
+        assert_eq!(names(&t, "bandung", &opts), vec!["ani"]);
+
+        let opts = SearchOptions {
+            limit: Some(1),
+            ..SearchOptions::default()
+        };
+        assert_eq!(names(&t, "bandung", &opts).len(), 1);
+
+        let opts = SearchOptions {
+            columns: vec!["harga".into()],
+            ..SearchOptions::default()
+        };
+        let query = SearchQuery::parse("x", true);
+        assert!(matches!(
+            t.search(&query, &opts, None),
+            Err(TableError::ColumnNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn hit_reports_matching_columns() {
+        let t = table(&[("budi", "bandung")]);
+        let query = SearchQuery::parse("bandung budi", true);
+        let hits = t.search(&query, &SearchOptions::default(), None).unwrap();
+        assert_eq!(hits[0].columns, vec![1, 2]);
+        assert_eq!(hits[0].score, 2 * SCORE_EXACT);
+    }
+
+    #[test]
+    fn index_gives_same_results() {
+        let t = table(&[
+            ("kopi susu", "bandung"),
+            ("es kopi", "jakarta"),
+            ("kopu", "bandung"),
+            ("teh", "bogor"),
+        ]);
+        let opts = SearchOptions::default();
+        let index = SearchIndex::build(&t, &opts).unwrap();
+        for input in [
+            "kopi",
+            "kopi bandung",
+            "bndung",
+            "bnadung",
+            "xandung",
+            "jakrta",
+            "kopo",
+            "band",
+            "teh",
+            "zzz",
+            "pi su",
+        ] {
+            let query = SearchQuery::parse(input, true);
+            let plain: Vec<usize> = t
+                .search(&query, &opts, None)
+                .unwrap()
+                .iter()
+                .map(|h| h.score as usize)
+                .collect();
+            let indexed: Vec<usize> = t
+                .search(&query, &opts, Some(&index))
+                .unwrap()
+                .iter()
+                .map(|h| h.score as usize)
+                .collect();
+            assert_eq!(plain, indexed, "kueri `{}`", input);
+        }
+        // Kata persis/awalan/bagian kata lewat rentang akhiran, bukan semua kata
+        let candidates = |input| index.candidates(&SearchQuery::parse(input, false));
+        assert_eq!(candidates("teh"), Some(HashSet::from([3])));
+        assert_eq!(candidates("ndu"), Some(HashSet::from([0, 2])));
+        assert_eq!(candidates("kop"), Some(HashSet::from([0, 1, 2])));
+    }
+
+    #[test]
+    fn ranges_are_char_positions_sorted_and_merged() {
+        let query = SearchQuery::parse("kopi", true);
+        assert_eq!(query.ranges("Es Kopi"), vec![3..7]);
+        // Posisi dihitung per char, bukan byte
+        assert_eq!(query.ranges("Café kopi"), vec![5..9]);
+
+        let query = SearchQuery::parse("susu kopi", true);
+        assert_eq!(query.ranges("kopi susu"), vec![0..4, 5..9]);
+        // Dua kata yang bertumpuk digabung
+        let query = SearchQuery::parse("kopis opis", true);
+        assert_eq!(query.ranges("kopisusu"), vec![0..5]);
+        assert!(query.ranges("teh").is_empty());
+    }
+
+    #[test]
+    fn highlight_wraps_matches() {
+        let query = SearchQuery::parse("kopi", true);
+        assert_eq!(query.highlight("Es Kopi", "[", "]"), "Es [Kopi]");
+        assert_eq!(query.highlight("kopu", "[", "]"), "[kopu]");
+        assert_eq!(query.highlight("teh", "[", "]"), "teh");
+    }
+}
@@ -1,9 +1,17 @@
+use crate::config;
+use crate::error::TableError;
 use crate::table::{
-    models::{ColumnTable, RowTable},
+    handlers::{
+        audit::AuditLog,
+        computed::{compute_row, parse_computed},
+        hooks::{Hooks, RowChange},
+    },
+    models::{ColumnTable, HookEvent, HookTiming, RowTable, SavedView, Trigger},
     value::{ColumnType, Value},
 };
-use prettytable::{Attr, color};
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataTable {
@@ -11,6 +19,28 @@ pub struct DataTable {
     pub row: Vec<RowTable>,
     pub _increment: i64,
     pub primary_index: Option<usize>,
+    // View tersimpan, urut nama
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub views: BTreeMap<String, SavedView>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<Trigger>,
+    #[serde(skip)]
+    pub hooks: Hooks,
+    #[serde(skip)]
+    pub audit: AuditLog,
+    // Passphrase aktif; None → disimpan sebagai JSON biasa
+    #[serde(skip)]
+    pub passphrase: Option<String>,
+    // Passphrase snapshot di disk; beda dengan `passphrase` setelah
+    // encrypt/decrypt/rekey → snapshot ditulis ulang lewat `rekey_snapshots`
+    #[serde(skip)]
+    pub(crate) snapshot_passphrase: RefCell<Option<String>>,
+}
+
+impl Default for DataTable {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DataTable {
@@ -19,22 +49,31 @@ impl DataTable {
             column: Vec::new(),
             row: Vec::new(),
             primary_index: None,
+            views: BTreeMap::new(),
+            triggers: Vec::new(),
+            hooks: Hooks::default(),
+            audit: AuditLog::default(),
             _increment: 0,
+            passphrase: None,
+            snapshot_passphrase: RefCell::new(None),
         }
     }
 
     // Set primary
-    pub fn set_primary(&mut self, colname: &str) -> Result<(), String> {
+    pub fn set_primary(&mut self, colname: &str) -> Result<(), TableError> {
         let index = self
             .column
             .iter()
             .position(|c| c.colname == colname)
-            .ok_or_else(|| format!("SET_PRIMARY: kolom `{}` tidak ditemukan", colname))?;
+            .ok_or_else(|| TableError::ColumnNotFound(colname.into()))?;
 
         // Jika sudah primary → skip
         if self.primary_index == Some(index) {
             return Ok(());
         }
+        if self.column[index].expr.is_some() {
+            return Err(TableError::ComputedReadOnly(colname.into()));
+        }
 
         // Validasi ketat jika ada row
         if !self.row.is_empty() {
@@ -54,10 +93,10 @@ impl DataTable {
     }
 
     // Tambah kolom
-    pub fn add_column(&mut self, columns: Vec<(&str, ColumnType)>) -> Result<(), String> {
+    pub fn add_column(&mut self, columns: Vec<(&str, ColumnType)>) -> Result<(), TableError> {
         for (colname, coltype) in columns {
             if self.column.iter().any(|c| c.colname == colname) {
-                return Err(format!("ADD_COLUMN: kolom `{}` sudah ada", colname));
+                return Err(TableError::ColumnExists(colname.into()));
             }
 
             self.column.push(ColumnTable {
@@ -65,6 +104,7 @@ impl DataTable {
                 coltype,
                 is_primary: false,
                 _is_auto_increment: false,
+                expr: None,
             });
         }
 
@@ -81,19 +121,18 @@ impl DataTable {
     }
 
     // Tambah row
-    pub fn add_row(&mut self, mut value: Vec<Value>) -> Result<(), String> {
+    pub fn add_row(&mut self, mut value: Vec<Value>) -> Result<(), TableError> {
         if self.primary_index.is_none() {
-            return Err("ADD_ROW: primary belum ditentukan".into());
+            return Err(TableError::NoPrimary);
         }
 
         let col_len = self.column.len();
 
         if value.len() > col_len {
-            return Err(format!(
-                "ADD_ROW: nilai berlebih ({}), kolom hanya ({})",
-                value.len(),
-                col_len
-            ));
+            return Err(TableError::TooManyValues {
+                given: value.len(),
+                columns: col_len,
+            });
         }
 
         // isi kekurangan dengan Empty
@@ -104,15 +143,40 @@ impl DataTable {
         // VALIDASI TIPE
         for (i, val) in value.iter().enumerate() {
             let col = &self.column[i];
+            // Kolom hitung dibiarkan kosong, nilainya diisi di bawah
+            if col.expr.is_some() && !matches!(val, Value::Empty) {
+                return Err(TableError::ComputedReadOnly(col.colname.clone()));
+            }
             if !Self::_validate_type_column_and_row(&col.coltype, val) {
-                return Err(format!(
-                    "ADD_ROW: tipe tidak cocok pada kolom `{}`",
-                    col.colname
-                ));
+                return Err(TableError::TypeMismatch(col.colname.clone()));
             }
         }
 
-        self.row.push(RowTable { value });
+        let exprs = parse_computed(&self.column);
+        compute_row(&self.column, &exprs, &mut value);
+
+        let mut changes = [RowChange {
+            old: None,
+            new: Some(value),
+        }];
+        self.run_hooks(HookTiming::Before, HookEvent::AddRow, &mut changes)?;
+        let Some(value) = changes[0].new.take() else {
+            return Ok(()); // dibatalkan before-hook
+        };
+        let value = self.checked_new_values(value, &exprs)?;
+
+        self.row.push(RowTable {
+            value: value.clone(),
+        });
+        changes[0].new = Some(value);
+        // After-hook gagal → batalkan agar tidak tersimpan (pemanggil bisa mengulang),
+        // termasuk catatan audit yang sudah dibuat hook sebelumnya
+        let audit_mark = self.audit.len();
+        if let Err(e) = self.run_hooks(HookTiming::After, HookEvent::AddRow, &mut changes) {
+            self.row.pop();
+            self.audit.truncate(audit_mark);
+            return Err(e);
+        }
         Ok(())
     }
 
@@ -123,67 +187,142 @@ impl DataTable {
         cond_value: Value,
         target_col: &str,
         new_value: Value,
-    ) -> Result<(), String> {
+    ) -> Result<(), TableError> {
         let cond_index = self
             .column
             .iter()
             .position(|c| c.colname == cond_col)
-            .ok_or_else(|| format!("SET_VALUE: kolom kondisi `{}` tidak ditemukan", cond_col))?;
+            .ok_or_else(|| TableError::ColumnNotFound(cond_col.into()))?;
 
         let target_index = self
             .column
             .iter()
             .position(|c| c.colname == target_col)
-            .ok_or_else(|| format!("SET_VALUE: kolom target `{}` tidak ditemukan", target_col))?;
+            .ok_or_else(|| TableError::ColumnNotFound(target_col.into()))?;
 
         let target_col_def = &self.column[target_index];
+        if target_col_def.expr.is_some() {
+            return Err(TableError::ComputedReadOnly(target_col_def.colname.clone()));
+        }
         if !Self::_validate_type_column_and_row(&target_col_def.coltype, &new_value) {
-            return Err(format!(
-                "SET_VALUE: tipe tidak cocok untuk kolom `{}`",
-                target_col_def.colname
-            ));
+            return Err(TableError::TypeMismatch(target_col_def.colname.clone()));
         }
 
         // Validasi ketat jika target adalah primary
         if self.primary_index == Some(target_index) {
             if matches!(new_value, Value::Empty) {
-                return Err("SET_VALUE: primary tidak boleh Empty".into());
+                return Err(TableError::EmptyPrimary);
             }
             for row in &self.row {
                 if row.value[target_index] == new_value {
-                    return Err("SET_VALUE: nilai primary duplikat".into());
+                    return Err(TableError::DuplicatePrimary);
                 }
             }
         }
 
-        let mut found = false;
-        for row in &mut self.row {
-            if row.value[cond_index] == cond_value {
-                row.value[target_index] = new_value.clone();
-                found = true;
-            }
+        let matched: Vec<usize> = (0..self.row.len())
+            .filter(|&i| self.row[i].value[cond_index] == cond_value)
+            .collect();
+        if matched.is_empty() {
+            return Err(TableError::NoMatchingRow);
         }
 
-        if !found {
-            return Err("SET_VALUE: tidak ada baris yang cocok dengan kondisi".into());
+        let updates = matched
+            .into_iter()
+            .map(|i| {
+                let mut new = self.row[i].value.clone();
+                new[target_index] = new_value.clone();
+                (i, new)
+            })
+            .collect();
+        self.update_rows(updates, self.primary_index == Some(target_index))
+    }
+
+    // Tulis nilai baru ke baris-baris (posisi, nilai) lewat pipeline set_value_where:
+    // before-hook, validasi ulang, lalu after-hook (gagal → semua dikembalikan).
+    // `primary_writable` false → nilai primary tidak boleh berubah
+    pub(crate) fn update_rows(
+        &mut self,
+        updates: Vec<(usize, Vec<Value>)>,
+        primary_writable: bool,
+    ) -> Result<(), TableError> {
+        let exprs = parse_computed(&self.column);
+        let positions: Vec<usize> = updates.iter().map(|(i, _)| *i).collect();
+        let mut changes: Vec<RowChange> = updates
+            .into_iter()
+            .map(|(i, mut new)| {
+                compute_row(&self.column, &exprs, &mut new);
+                RowChange {
+                    old: Some(self.row[i].value.clone()),
+                    new: Some(new),
+                }
+            })
+            .collect();
+        self.run_hooks(HookTiming::Before, HookEvent::SetValueWhere, &mut changes)?;
+
+        // Cek semua dulu agar perubahan tidak setengah jalan
+        let mut checked = Vec::new();
+        for (&i, change) in positions.iter().zip(&changes) {
+            let Some(new) = change.new.clone() else {
+                continue; // dibatalkan before-hook
+            };
+            let new = self.checked_new_values(new, &exprs)?;
+            if let Some(p) = self.primary_index
+                && !primary_writable
+                && new[p] != self.row[i].value[p]
+            {
+                return Err(TableError::PrimaryReadOnly(self.column[p].colname.clone()));
+            }
+            checked.push((i, new));
         }
 
+        let mut applied = Vec::new();
+        let mut positions = Vec::new();
+        for (i, new) in checked {
+            let old = std::mem::replace(&mut self.row[i].value, new.clone());
+            applied.push(RowChange {
+                old: Some(old),
+                new: Some(new),
+            });
+            positions.push(i);
+        }
+        let audit_mark = self.audit.len();
+        if let Err(e) = self.run_hooks(HookTiming::After, HookEvent::SetValueWhere, &mut applied) {
+            for (i, change) in positions.into_iter().zip(applied) {
+                if let Some(old) = change.old {
+                    self.row[i].value = old;
+                }
+            }
+            self.audit.truncate(audit_mark);
+            return Err(e);
+        }
         Ok(())
     }
     // Hapus kolom
-    pub fn remove_column(&mut self, colname: &str) -> Result<(), String> {
+    pub fn remove_column(&mut self, colname: &str) -> Result<(), TableError> {
         let index = self
             .column
             .iter()
             .position(|c| c.colname == colname)
-            .ok_or_else(|| format!("HAPUS_KOLOM: kolom `{}` tidak ditemukan", colname))?;
+            .ok_or_else(|| TableError::ColumnNotFound(colname.into()))?;
 
         // Cegah hapus primary
         if self.primary_index == Some(index) {
-            return Err(format!(
-                "HAPUS_KOLOM: kolom `{}` adalah primary, tidak boleh dihapus",
-                colname
-            ));
+            return Err(TableError::PrimaryRemoval(colname.into()));
+        }
+
+        // Cegah hapus kolom yang masih dipakai kolom hitung / trigger
+        if let Some(dependent) = self.computed_dependent(colname) {
+            return Err(TableError::ColumnReferenced {
+                column: colname.into(),
+                by: dependent.colname.clone(),
+            });
+        }
+        if let Some(trigger) = self.trigger_using(colname) {
+            return Err(TableError::ColumnInTrigger {
+                column: colname.into(),
+                trigger: trigger.name.clone(),
+            });
         }
 
         self.column.remove(index);
@@ -195,24 +334,47 @@ impl DataTable {
         }
 
         // Update primary_index jika perlu
-        if let Some(p_idx) = self.primary_index {
-            if p_idx > index {
-                self.primary_index = Some(p_idx - 1);
-            }
+        if let Some(p_idx) = self.primary_index
+            && p_idx > index
+        {
+            self.primary_index = Some(p_idx - 1);
         }
 
         Ok(())
     }
-    pub fn remove_row(&mut self, colname: &str, value: Value) -> Result<(), String> {
+    pub fn remove_row(&mut self, colname: &str, value: Value) -> Result<(), TableError> {
         let index = self
             .column
             .iter()
             .position(|c| c.colname == colname)
-            .ok_or_else(|| format!("HAPUS_ROW: kolom `{}` tidak ditemukan", colname))?;
-
-        self.row
-            .retain(|row| row.value.get(index).map(|v| v != &value).unwrap_or(true));
+            .ok_or_else(|| TableError::ColumnNotFound(colname.into()))?;
 
+        let matches = |row: &RowTable| row.value.get(index) == Some(&value);
+        let mut changes: Vec<RowChange> = self
+            .row
+            .iter()
+            .filter(|row| matches(row))
+            .map(|row| RowChange {
+                old: Some(row.value.clone()),
+                new: None,
+            })
+            .collect();
+        self.run_hooks(HookTiming::Before, HookEvent::RemoveRow, &mut changes)?;
+
+        let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.row)
+            .into_iter()
+            .enumerate()
+            .partition(|(_, row)| matches(row));
+        self.row = kept.into_iter().map(|(_, row)| row).collect();
+        let audit_mark = self.audit.len();
+        if let Err(e) = self.run_hooks(HookTiming::After, HookEvent::RemoveRow, &mut changes) {
+            // Kembalikan ke posisi semula (urut naik → indeks tetap benar)
+            for (i, row) in removed {
+                self.row.insert(i, row);
+            }
+            self.audit.truncate(audit_mark);
+            return Err(e);
+        }
         Ok(())
     }
     pub fn show_column_types(&self) {
@@ -243,19 +405,22 @@ impl DataTable {
                     ColumnType::Bool => "Bool",
                     ColumnType::Char => "Char",
                 };
-                prettytable::Cell::new(col_type)
+                // Kolom hitung: tipe hasil + ekspresinya
+                match &col.expr {
+                    Some(expr) => prettytable::Cell::new(&format!("{} = {}", col_type, expr))
+                        .with_style(prettytable::Attr::Italic(true)),
+                    None => prettytable::Cell::new(col_type),
+                }
             })
             .collect();
         pt.add_row(prettytable::Row::new(types));
 
         pt.printstd(); // cetak tabel dengan border, rapi, lebar dinamis
     }
-    // Print tabel rapi
-    pub fn show_data_table(&self) {
-        use prettytable::format;
-
+    // Bangun prettytable untuk sebagian/semua baris
+    pub fn pretty_table(&self, rows: &[&RowTable]) -> prettytable::Table {
         let mut table = prettytable::Table::new();
-        table.set_format(*format::consts::FORMAT_BOX_CHARS);
+        table.set_format(config::current().table_format());
 
         // --- HEADER ---
         let header_cells: Vec<prettytable::Cell> = self
@@ -267,37 +432,23 @@ impl DataTable {
                 } else {
                     col.colname.clone()
                 };
-                prettytable::Cell::new(&name)
-                    .style_spec("c")
-                    .with_style(Attr::Bold)
-                    .with_style(Attr::ForegroundColor(color::YELLOW))
+                config::current().style_header(prettytable::Cell::new(&name))
             })
             .collect();
 
         table.add_row(prettytable::Row::new(header_cells));
 
         // --- ROWS ---
-        for row in &self.row {
-            // self.row: Vec<models::Row>
+        for row in rows {
             let row_cells: Vec<prettytable::Cell> = row
                 .value
                 .iter()
-                .map(|val| {
-                    let s = match val {
-                        Value::Text(v) => v.clone(),
-                        Value::Char(c) => c.to_string(),
-                        Value::Numb(n) => n.to_string(),
-                        Value::Bool(b) => b.to_string(),
-                        Value::Empty => "-".into(),
-                    };
-                    prettytable::Cell::new(&s)
-                })
+                .map(|val| prettytable::Cell::new(&val.to_string()))
                 .collect();
 
             table.add_row(prettytable::Row::new(row_cells));
         }
 
-        // --- PRINT TABLE ---
-        table.printstd();
+        table
     }
 }
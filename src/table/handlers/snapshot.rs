@@ -0,0 +1,198 @@
+use crate::error::TableError;
+use crate::table::handlers::{crypto, service::DataTable};
+use crate::tr;
+use chrono::{DateTime, Local};
+use std::fs;
+use std::path::Path;
+
+// Snapshot tersimpan: satu file tabel utuh per nama
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotInfo {
+    pub name: String,
+    pub created: Option<DateTime<Local>>,
+    pub path: String,
+}
+
+// Folder snapshot di samping file tabel
+pub fn snapshot_dir(storage_path: &str) -> String {
+    format!("{}.snapshots", storage_path)
+}
+
+fn snapshot_path(dir: &str, name: &str) -> Result<String, TableError> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(TableError::InvalidValue(tr!("snapshot.name", name)));
+    }
+    Ok(format!("{}/{}.json", dir, name))
+}
+
+impl DataTable {
+    // Simpan salinan tabel saat ini (terenkripsi jika tabel terenkripsi);
+    // nama yang sudah ada tidak ditimpa
+    pub fn save_snapshot(&self, dir: &str, name: &str) -> Result<SnapshotInfo, TableError> {
+        let path = snapshot_path(dir, name)?;
+        if Path::new(&path).exists() {
+            return Err(TableError::SnapshotExists(name.into()));
+        }
+        fs::create_dir_all(dir).map_err(TableError::io(dir))?;
+        self.save(&path)?;
+        Ok(SnapshotInfo {
+            name: name.into(),
+            created: Some(Local::now()),
+            path,
+        })
+    }
+
+    pub fn load_snapshot(
+        dir: &str,
+        name: &str,
+        passphrase: Option<&str>,
+    ) -> Result<DataTable, TableError> {
+        let path = snapshot_path(dir, name)?;
+        if !Path::new(&path).exists() {
+            return Err(TableError::SnapshotNotFound(name.into()));
+        }
+        DataTable::load_with_passphrase(&path, passphrase)
+    }
+
+    // Setelah encrypt/decrypt/rekey: tulis ulang semua snapshot dengan passphrase
+    // tabel saat ini (mirip log audit), agar `diff SNAPSHOT` tetap bisa dibuka.
+    // Snapshot yang sudah memakai passphrase baru dilewati
+    pub fn rekey_snapshots(&self, dir: &str) -> Result<usize, TableError> {
+        let old = self.snapshot_passphrase.borrow().clone();
+        if old == self.passphrase {
+            return Ok(0);
+        }
+        let mut rewritten = 0;
+        for snapshot in list_snapshots(dir)? {
+            let data = fs::read(&snapshot.path).map_err(TableError::io(&snapshot.path))?;
+            let encrypted = crypto::is_encrypted(&data);
+            let plain = match (encrypted, &old) {
+                (false, _) => data,
+                (true, Some(old)) => match crypto::decrypt_bytes(&data, old) {
+                    Ok(plain) => plain,
+                    // Sudah memakai passphrase baru (mis. snapshot dibuat setelah rekey)
+                    Err(_) if self.can_open(&data) => continue,
+                    Err(e) => return Err(e),
+                },
+                (true, None) if self.can_open(&data) => continue,
+                (true, None) => return Err(TableError::WrongPassphrase),
+            };
+            let data = match &self.passphrase {
+                Some(p) => crypto::encrypt_bytes(&plain, p)?,
+                None if encrypted => plain,
+                None => continue,
+            };
+            fs::write(&snapshot.path, data).map_err(TableError::io(&snapshot.path))?;
+            rewritten += 1;
+        }
+        *self.snapshot_passphrase.borrow_mut() = self.passphrase.clone();
+        Ok(rewritten)
+    }
+
+    fn can_open(&self, data: &[u8]) -> bool {
+        self.passphrase
+            .as_deref()
+            .is_some_and(|p| crypto::decrypt_bytes(data, p).is_ok())
+    }
+}
+
+pub fn remove_snapshot(dir: &str, name: &str) -> Result<(), TableError> {
+    let path = snapshot_path(dir, name)?;
+    if !Path::new(&path).exists() {
+        return Err(TableError::SnapshotNotFound(name.into()));
+    }
+    fs::remove_file(&path).map_err(TableError::io(&path))
+}
+
+// Semua snapshot, urut waktu dibuat (terlama dulu); folder belum ada → kosong
+pub fn list_snapshots(dir: &str) -> Result<Vec<SnapshotInfo>, TableError> {
+    if !Path::new(dir).exists() {
+        return Ok(Vec::new());
+    }
+    let mut snapshots = Vec::new();
+    for entry in fs::read_dir(dir).map_err(TableError::io(dir))? {
+        let path = entry.map_err(TableError::io(dir))?.path();
+        let Some(name) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_suffix(".json"))
+        else {
+            continue;
+        };
+        let created = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(DateTime::<Local>::from);
+        snapshots.push(SnapshotInfo {
+            name: name.into(),
+            created,
+            path: path.display().to_string(),
+        });
+    }
+    snapshots.sort_by(|a, b| a.created.cmp(&b.created).then(a.name.cmp(&b.name)));
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::handlers::test_dir;
+    use crate::table::value::{ColumnType, Value};
+
+    fn encrypted_table(dir: &str) -> DataTable {
+        let mut table = DataTable::default();
+        table.add_column(vec![("nama", ColumnType::Text)]).unwrap();
+        table.set_primary("nama").unwrap();
+        table.add_row(vec![Value::Text("a".into())]).unwrap();
+        table.encrypt("lama").unwrap();
+        let path = format!("{}/t.json", dir);
+        table.save(&path).unwrap();
+        DataTable::load_with_passphrase(&path, Some("lama")).unwrap()
+    }
+
+    #[test]
+    fn rekey_rewrites_snapshots_with_new_passphrase() {
+        let dir = test_dir("snapshot-rekey").display().to_string();
+        let snapshots = snapshot_dir(&format!("{}/t.json", dir));
+        let mut table = encrypted_table(&dir);
+        table.save_snapshot(&snapshots, "s1").unwrap();
+
+        table.rekey("baru").unwrap();
+        // Dibuat setelah rekey → sudah memakai passphrase baru
+        table.save_snapshot(&snapshots, "s2").unwrap();
+        assert_eq!(table.rekey_snapshots(&snapshots).unwrap(), 1);
+        for name in ["s1", "s2"] {
+            assert!(DataTable::load_snapshot(&snapshots, name, Some("baru")).is_ok());
+        }
+        assert!(matches!(
+            DataTable::load_snapshot(&snapshots, "s1", Some("lama")),
+            Err(TableError::WrongPassphrase)
+        ));
+        // Sudah sinkron → tidak ada yang ditulis ulang
+        assert_eq!(table.rekey_snapshots(&snapshots).unwrap(), 0);
+    }
+
+    #[test]
+    fn decrypt_and_encrypt_rewrite_snapshots() {
+        let dir = test_dir("snapshot-decrypt").display().to_string();
+        let snapshots = snapshot_dir(&format!("{}/t.json", dir));
+        let mut table = encrypted_table(&dir);
+        table.save_snapshot(&snapshots, "s1").unwrap();
+
+        table.decrypt().unwrap();
+        table.rekey_snapshots(&snapshots).unwrap();
+        let path = format!("{}/s1.json", snapshots);
+        assert!(!crypto::is_encrypted(&fs::read(&path).unwrap()));
+        assert!(DataTable::load_snapshot(&snapshots, "s1", None).is_ok());
+
+        table.encrypt("lagi").unwrap();
+        assert_eq!(table.rekey_snapshots(&snapshots).unwrap(), 1);
+        assert!(crypto::is_encrypted(&fs::read(&path).unwrap()));
+        assert!(DataTable::load_snapshot(&snapshots, "s1", Some("lagi")).is_ok());
+    }
+}
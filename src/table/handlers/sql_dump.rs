@@ -0,0 +1,232 @@
+use crate::error::TableError;
+use crate::table::{
+    handlers::{service::DataTable, sqlite},
+    value::{ColumnType, Value},
+};
+use crate::tr;
+use std::fs;
+
+// Jumlah baris per statement INSERT (default)
+pub const DEFAULT_BATCH: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl SqlDialect {
+    pub fn parse(input: &str) -> Result<Self, TableError> {
+        match input.to_lowercase().as_str() {
+            "postgres" | "postgresql" | "pg" => Ok(SqlDialect::Postgres),
+            "mysql" | "mariadb" => Ok(SqlDialect::MySql),
+            "sqlite" | "sqlite3" => Ok(SqlDialect::Sqlite),
+            _ => Err(TableError::InvalidOption(tr!("opt.dialect", input))),
+        }
+    }
+
+    fn quote_ident(self, name: &str) -> String {
+        match self {
+            SqlDialect::MySql => format!("`{}`", name.replace('`', "``")),
+            SqlDialect::Postgres | SqlDialect::Sqlite => sqlite::quote_ident(name),
+        }
+    }
+
+    // Literal string: ' digandakan; MySQL juga memperlakukan \ sebagai escape
+    fn quote_str(self, s: &str) -> String {
+        let escaped = s.replace('\'', "''");
+        match self {
+            SqlDialect::MySql => format!("'{}'", escaped.replace('\\', "\\\\")),
+            SqlDialect::Postgres | SqlDialect::Sqlite => format!("'{}'", escaped),
+        }
+    }
+
+    fn type_name(self, col_type: &ColumnType, is_primary: bool) -> &'static str {
+        match (self, col_type) {
+            (SqlDialect::Sqlite, t) => sqlite::sqlite_decl_type(t),
+            // MySQL tidak bisa memakai TEXT sebagai PRIMARY KEY tanpa panjang
+            (SqlDialect::MySql, ColumnType::Text) if is_primary => "VARCHAR(255)",
+            (_, ColumnType::Text) => "TEXT",
+            (_, ColumnType::Numb) => "BIGINT",
+            (_, ColumnType::Bool) => "BOOLEAN",
+            (_, ColumnType::Char) => "CHAR(1)",
+        }
+    }
+
+    fn literal(self, value: &Value) -> String {
+        match value {
+            Value::Text(s) => self.quote_str(s),
+            Value::Char(c) => self.quote_str(&c.to_string()),
+            Value::Numb(n) => n.to_string(),
+            Value::Bool(b) => match self {
+                SqlDialect::Sqlite => (*b as i64).to_string(),
+                SqlDialect::Postgres | SqlDialect::MySql => b.to_string().to_uppercase(),
+            },
+            Value::Empty => "NULL".into(),
+        }
+    }
+
+    fn begin(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "BEGIN;",
+            SqlDialect::MySql => "START TRANSACTION;",
+            SqlDialect::Sqlite => "BEGIN TRANSACTION;",
+        }
+    }
+}
+
+impl DataTable {
+    // Script SQL: CREATE TABLE + INSERT per batch, dibungkus transaksi
+    pub fn to_sql_dump(
+        &self,
+        table_name: &str,
+        dialect: SqlDialect,
+        batch: usize,
+    ) -> Result<String, TableError> {
+        if self.column.is_empty() {
+            return Err(TableError::NoColumns);
+        }
+        if batch == 0 {
+            return Err(TableError::InvalidOption(tr!("opt.batch")));
+        }
+
+        let name = dialect.quote_ident(table_name);
+        let mut out = String::new();
+        out.push_str(dialect.begin());
+        out.push('\n');
+
+        // --- CREATE TABLE ---
+        let defs: Vec<String> = self
+            .column
+            .iter()
+            .map(|col| {
+                let mut def = format!(
+                    "    {} {}",
+                    dialect.quote_ident(&col.colname),
+                    dialect.type_name(&col.coltype, col.is_primary)
+                );
+                if col.is_primary {
+                    def.push_str(" NOT NULL PRIMARY KEY");
+                }
+                def
+            })
+            .collect();
+        out.push_str(&format!(
+            "CREATE TABLE {} (\n{}\n);\n",
+            name,
+            defs.join(",\n")
+        ));
+
+        // --- INSERT ---
+        let cols: Vec<String> = self
+            .column
+            .iter()
+            .map(|c| dialect.quote_ident(&c.colname))
+            .collect();
+        for chunk in self.row.chunks(batch) {
+            let tuples: Vec<String> = chunk
+                .iter()
+                .map(|row| {
+                    let values: Vec<String> =
+                        row.value.iter().map(|v| dialect.literal(v)).collect();
+                    format!("    ({})", values.join(", "))
+                })
+                .collect();
+            out.push_str(&format!(
+                "INSERT INTO {} ({}) VALUES\n{};\n",
+                name,
+                cols.join(", "),
+                tuples.join(",\n")
+            ));
+        }
+
+        out.push_str("COMMIT;\n");
+        Ok(out)
+    }
+
+    // Tulis dump ke file, atau stdout jika path None
+    pub fn export_sql_dump(
+        &self,
+        table_name: &str,
+        dialect: SqlDialect,
+        batch: usize,
+        path: Option<&str>,
+    ) -> Result<(), TableError> {
+        let out = self.to_sql_dump(table_name, dialect, batch)?;
+        match path {
+            Some(path) => fs::write(path, out).map_err(TableError::io(path)),
+            None => {
+                print!("{}", out);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![
+                ("kode`\"", ColumnType::Text),
+                ("jumlah", ColumnType::Numb),
+                ("aktif", ColumnType::Bool),
+            ])
+            .unwrap();
+        table.set_primary("kode`\"").unwrap();
+        for (kode, jumlah, aktif) in [
+            ("O'Brien \\n", Value::Numb(-3), Value::Bool(true)),
+            ("b", Value::Empty, Value::Bool(false)),
+            ("c", Value::Numb(7), Value::Empty),
+        ] {
+            table
+                .add_row(vec![Value::Text(kode.into()), jumlah, aktif])
+                .unwrap();
+        }
+        table
+    }
+
+    #[test]
+    fn quoting_follows_dialect() {
+        let table = table();
+        let pg = table.to_sql_dump("t", SqlDialect::Postgres, 100).unwrap();
+        assert!(pg.starts_with("BEGIN;\nCREATE TABLE \"t\" (\n    \"kode`\"\"\" TEXT NOT NULL"));
+        assert!(pg.contains("('O''Brien \\n', -3, TRUE)"));
+        assert!(pg.contains("('b', NULL, FALSE)"));
+
+        let my = table.to_sql_dump("t", SqlDialect::MySql, 100).unwrap();
+        assert!(my.starts_with("START TRANSACTION;\nCREATE TABLE `t`"));
+        assert!(my.contains("`kode``\"` VARCHAR(255) NOT NULL PRIMARY KEY"));
+        assert!(my.contains("('O''Brien \\\\n', -3, TRUE)"));
+        assert!(my.ends_with("COMMIT;\n"));
+    }
+
+    #[test]
+    fn inserts_are_batched() {
+        let dump = table().to_sql_dump("t", SqlDialect::Sqlite, 2).unwrap();
+        assert_eq!(dump.matches("INSERT INTO").count(), 2);
+        assert!(matches!(
+            table().to_sql_dump("t", SqlDialect::Sqlite, 0),
+            Err(TableError::InvalidOption(_))
+        ));
+        assert!(matches!(
+            DataTable::default().to_sql_dump("t", SqlDialect::Sqlite, 1),
+            Err(TableError::NoColumns)
+        ));
+    }
+
+    #[test]
+    fn sqlite_dump_runs_and_keeps_values() {
+        let table = table();
+        let dump = table.to_sql_dump("t'x", SqlDialect::Sqlite, 2).unwrap();
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(&dump).unwrap();
+        let back = DataTable::read_sqlite(&conn, "t'x", None).unwrap();
+        let values = |t: &DataTable| t.row.iter().map(|r| r.value.clone()).collect::<Vec<_>>();
+        assert_eq!(values(&back), values(&table));
+    }
+}
@@ -0,0 +1,340 @@
+use crate::error::TableError;
+use crate::table::{
+    handlers::service::DataTable,
+    models::{ColumnTable, RowTable},
+    value::{ColumnType, Value},
+};
+use crate::tr;
+use rusqlite::{Connection, types::Value as SqlValue};
+
+// Kutip identifier SQL: "nama" (tanda kutip di dalam digandakan)
+pub fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// ColumnType → tipe deklarasi SQLite (affinity: TEXT, INTEGER, NUMERIC, TEXT)
+pub(crate) fn sqlite_decl_type(col_type: &ColumnType) -> &'static str {
+    match col_type {
+        ColumnType::Text => "TEXT",
+        ColumnType::Numb => "INTEGER",
+        ColumnType::Bool => "BOOLEAN",
+        ColumnType::Char => "CHAR(1)",
+    }
+}
+
+// Tipe deklarasi SQLite → ColumnType; None jika harus ditebak dari data
+fn column_type_from_decl(decl: &str) -> Option<ColumnType> {
+    let decl = decl.to_uppercase();
+    if decl.contains("BOOL") {
+        Some(ColumnType::Bool)
+    } else if decl == "CHAR(1)" || decl == "CHARACTER(1)" {
+        Some(ColumnType::Char)
+    } else if decl.contains("INT") {
+        Some(ColumnType::Numb)
+    } else if decl.contains("CHAR") || decl.contains("CLOB") || decl.contains("TEXT") {
+        Some(ColumnType::Text)
+    } else {
+        None
+    }
+}
+
+// Tebak tipe dari isi kolom: semua integer → Numb, selain itu Text
+fn infer_column_type(rows: &[Vec<SqlValue>], index: usize) -> ColumnType {
+    let mut values = rows
+        .iter()
+        .map(|r| &r[index])
+        .filter(|v| **v != SqlValue::Null);
+    if values.clone().next().is_some() && values.all(|v| matches!(v, SqlValue::Integer(_))) {
+        ColumnType::Numb
+    } else {
+        ColumnType::Text
+    }
+}
+
+fn value_to_sql(value: &Value) -> SqlValue {
+    match value {
+        Value::Text(s) => SqlValue::Text(s.clone()),
+        Value::Char(c) => SqlValue::Text(c.to_string()),
+        Value::Numb(n) => SqlValue::Integer(*n),
+        Value::Bool(b) => SqlValue::Integer(*b as i64),
+        Value::Empty => SqlValue::Null,
+    }
+}
+
+fn sql_to_value(sql: &SqlValue, col_type: &ColumnType) -> Result<Value, String> {
+    match (sql, col_type) {
+        (SqlValue::Null, _) => Ok(Value::Empty),
+        (SqlValue::Integer(n), ColumnType::Numb) => Ok(Value::Numb(*n)),
+        (SqlValue::Integer(0), ColumnType::Bool) => Ok(Value::Bool(false)),
+        (SqlValue::Integer(1), ColumnType::Bool) => Ok(Value::Bool(true)),
+        (SqlValue::Integer(n), ColumnType::Text) => Ok(Value::Text(n.to_string())),
+        (SqlValue::Text(s), ColumnType::Text) => Ok(Value::Text(s.clone())),
+        (SqlValue::Text(s), ColumnType::Char) => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(Value::Char(c)),
+                _ => Err(tr!("val.char_len_value", s)),
+            }
+        }
+        (SqlValue::Real(r), _) => Err(tr!("val.sql_real", r)),
+        (SqlValue::Blob(_), _) => Err(tr!("val.sql_blob")),
+        (other, col_type) => Err(tr!(
+            "val.sql_mismatch",
+            format!("{:?}", other),
+            format!("{:?}", col_type)
+        )),
+    }
+}
+
+impl DataTable {
+    // Tulis tabel ke file SQLite; `replace` → hapus tabel lama jika ada
+    pub fn export_sqlite(
+        &self,
+        path: &str,
+        table_name: &str,
+        replace: bool,
+    ) -> Result<(), TableError> {
+        let mut conn = Connection::open(path)?;
+        let tx = conn.transaction()?;
+        self.write_sqlite(&tx, table_name, replace)?;
+        tx.commit().map_err(TableError::from)
+    }
+
+    pub(crate) fn write_sqlite(
+        &self,
+        conn: &Connection,
+        table_name: &str,
+        replace: bool,
+    ) -> Result<(), TableError> {
+        if self.column.is_empty() {
+            return Err(TableError::NoColumns);
+        }
+
+        let name = quote_ident(table_name);
+        if replace {
+            conn.execute_batch(&format!("DROP TABLE IF EXISTS {};", name))?;
+        }
+
+        let defs: Vec<String> = self
+            .column
+            .iter()
+            .map(|col| {
+                let mut def = format!(
+                    "{} {}",
+                    quote_ident(&col.colname),
+                    sqlite_decl_type(&col.coltype)
+                );
+                if col.is_primary {
+                    def.push_str(" PRIMARY KEY NOT NULL");
+                }
+                def
+            })
+            .collect();
+        conn.execute_batch(&format!("CREATE TABLE {} ({});", name, defs.join(", ")))?;
+
+        let placeholders = vec!["?"; self.column.len()].join(", ");
+        let mut stmt = conn.prepare(&format!("INSERT INTO {} VALUES ({})", name, placeholders))?;
+        for row in &self.row {
+            let params: Vec<SqlValue> = row.value.iter().map(value_to_sql).collect();
+            stmt.execute(rusqlite::params_from_iter(params))?;
+        }
+
+        Ok(())
+    }
+
+    // Baca tabel SQLite menjadi DataTable baru.
+    // `primary` wajib jika tabel sumber tidak punya PRIMARY KEY tunggal
+    pub fn import_sqlite(
+        path: &str,
+        table_name: &str,
+        primary: Option<&str>,
+    ) -> Result<DataTable, TableError> {
+        let conn = Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        Self::read_sqlite(&conn, table_name, primary)
+    }
+
+    pub(crate) fn read_sqlite(
+        conn: &Connection,
+        table_name: &str,
+        primary: Option<&str>,
+    ) -> Result<DataTable, TableError> {
+        let name = quote_ident(table_name);
+
+        // (nama, tipe deklarasi, posisi pk)
+        let mut info = conn.prepare(&format!("PRAGMA table_info({})", name))?;
+        let columns: Vec<(String, String, i64)> = info
+            .query_map([], |r| Ok((r.get(1)?, r.get(2)?, r.get(5)?)))
+            .and_then(|rows| rows.collect())?;
+        if columns.is_empty() {
+            return Err(TableError::TableNotFound(table_name.into()));
+        }
+
+        let mut stmt = conn.prepare(&format!("SELECT * FROM {}", name))?;
+        let raw_rows: Vec<Vec<SqlValue>> = stmt
+            .query_map([], |r| (0..columns.len()).map(|i| r.get(i)).collect())
+            .and_then(|rows| rows.collect())?;
+
+        let mut table = DataTable::new();
+        for (i, (colname, decl, _)) in columns.iter().enumerate() {
+            let coltype =
+                column_type_from_decl(decl).unwrap_or_else(|| infer_column_type(&raw_rows, i));
+            table.column.push(ColumnTable {
+                colname: colname.clone(),
+                coltype,
+                is_primary: false,
+                _is_auto_increment: false,
+                expr: None,
+            });
+        }
+
+        // Konversi nilai; kumpulkan semua error agar dilaporkan sekaligus
+        let mut errors: Vec<String> = Vec::new();
+        for (n, raw) in raw_rows.iter().enumerate() {
+            let mut value = Vec::with_capacity(raw.len());
+            for (i, sql) in raw.iter().enumerate() {
+                match sql_to_value(sql, &table.column[i].coltype) {
+                    Ok(v) => value.push(v),
+                    Err(e) => {
+                        errors.push(tr!("val.row_column", n + 1, table.column[i].colname, e));
+                        value.push(Value::Empty);
+                    }
+                }
+            }
+            table.row.push(RowTable { value });
+        }
+        if !errors.is_empty() {
+            return Err(TableError::InvalidRecords(errors));
+        }
+
+        // Primary: dari argumen, atau PRIMARY KEY tunggal di SQLite
+        let pk_columns: Vec<&String> = columns
+            .iter()
+            .filter(|(_, _, pk)| *pk > 0)
+            .map(|(name, _, _)| name)
+            .collect();
+        let primary = match (primary, pk_columns.as_slice()) {
+            (Some(p), _) => p.to_string(),
+            (None, [single]) => single.to_string(),
+            _ => {
+                return Err(TableError::InvalidOption(tr!("opt.single_pk", table_name)));
+            }
+        };
+        table.set_primary(&primary)?;
+
+        Ok(table)
+    }
+}
+
+// Daftar nama tabel user di file SQLite
+pub fn sqlite_table_names(conn: &Connection) -> Result<Vec<String>, TableError> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table' \
+             AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )?;
+    stmt.query_map([], |r| r.get(0))
+        .and_then(|rows| rows.collect())
+        .map_err(TableError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::handlers::test_dir;
+
+    fn table() -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![
+                ("id", ColumnType::Numb),
+                ("nama \"x\"", ColumnType::Text),
+                ("kode", ColumnType::Char),
+                ("aktif", ColumnType::Bool),
+            ])
+            .unwrap();
+        table.set_primary("id").unwrap();
+        table
+            .add_row(vec![
+                Value::Numb(1),
+                Value::Text("O'Brien".into()),
+                Value::Char('é'),
+                Value::Bool(true),
+            ])
+            .unwrap();
+        table
+            .add_row(vec![
+                Value::Numb(2),
+                Value::Empty,
+                Value::Empty,
+                Value::Bool(false),
+            ])
+            .unwrap();
+        table
+    }
+
+    #[test]
+    fn export_import_round_trip_keeps_types_and_primary() {
+        let dir = test_dir("sqlite-round-trip");
+        let path = dir.join("t.db").display().to_string();
+        let table = table();
+        table.export_sqlite(&path, "data barang", false).unwrap();
+        assert!(table.export_sqlite(&path, "data barang", false).is_err());
+        table.export_sqlite(&path, "data barang", true).unwrap();
+
+        let back = DataTable::import_sqlite(&path, "data barang", None).unwrap();
+        let types: Vec<&ColumnType> = back.column.iter().map(|c| &c.coltype).collect();
+        assert_eq!(
+            types,
+            [
+                &ColumnType::Numb,
+                &ColumnType::Text,
+                &ColumnType::Char,
+                &ColumnType::Bool
+            ]
+        );
+        assert_eq!(back.column[1].colname, "nama \"x\"");
+        assert_eq!(back.primary_index, Some(0));
+        let values = |t: &DataTable| t.row.iter().map(|r| r.value.clone()).collect::<Vec<_>>();
+        assert_eq!(values(&back), values(&table));
+    }
+
+    #[test]
+    fn import_infers_types_and_needs_single_primary() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE t (a, b, c NUMERIC, PRIMARY KEY (a, b));
+             INSERT INTO t VALUES (1, 'x', 5), (2, 3, NULL);",
+        )
+        .unwrap();
+        assert!(matches!(
+            DataTable::read_sqlite(&conn, "t", None),
+            Err(TableError::InvalidOption(_))
+        ));
+        let table = DataTable::read_sqlite(&conn, "t", Some("a")).unwrap();
+        let types: Vec<&ColumnType> = table.column.iter().map(|c| &c.coltype).collect();
+        assert_eq!(
+            types,
+            [&ColumnType::Numb, &ColumnType::Text, &ColumnType::Numb]
+        );
+        assert_eq!(table.row[1].value[1], Value::Text("3".into()));
+        assert!(matches!(
+            DataTable::read_sqlite(&conn, "tidak_ada", None),
+            Err(TableError::TableNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn unconvertible_values_are_all_reported() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE t (id INTEGER PRIMARY KEY, harga, ok BOOLEAN);
+             INSERT INTO t VALUES (1, 1.5, 1), (2, 2, 7);",
+        )
+        .unwrap();
+        let Err(TableError::InvalidRecords(errors)) = DataTable::read_sqlite(&conn, "t", None)
+        else {
+            panic!("import harus gagal");
+        };
+        assert_eq!(errors.len(), 2);
+        assert_eq!(sqlite_table_names(&conn).unwrap(), ["t"]);
+    }
+}
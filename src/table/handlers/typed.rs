@@ -0,0 +1,316 @@
+use crate::error::TableError;
+use crate::table::{
+    handlers::service::DataTable,
+    value::{ColumnType, Value},
+};
+use crate::tr;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, Visitor,
+};
+use serde::{Serialize, forward_to_deserialize_any};
+use serde_json::Value as Json;
+use std::fmt;
+
+impl DataTable {
+    // Buat tabel baru dari definisi struct: nama field → kolom, tipe field → ColumnType
+    pub fn create_from_struct<T: DeserializeOwned>(primary: &str) -> Result<DataTable, TableError> {
+        let mut columns: Vec<(String, ColumnType)> = Vec::new();
+        T::deserialize(SchemaTracer {
+            columns: &mut columns,
+        })
+        .map_err(|e| TableError::InvalidValue(e.0))?;
+
+        let mut table = DataTable::new();
+        table.add_column(
+            columns
+                .iter()
+                .map(|(name, coltype)| (name.as_str(), coltype.clone()))
+                .collect(),
+        )?;
+        table.set_primary(primary)?;
+        Ok(table)
+    }
+
+    // Tambah satu baris dari struct; field dipetakan ke kolom berdasarkan nama
+    pub fn insert<T: Serialize>(&mut self, record: &T) -> Result<(), TableError> {
+        let primary_index = self.primary_index.ok_or(TableError::NoPrimary)?;
+        let Json::Object(map) = serde_json::to_value(record)? else {
+            return Err(TableError::InvalidValue(tr!("val.record_struct")));
+        };
+        let values = self.json_object_to_values(&map)?;
+
+        let primary = &values[primary_index];
+        if matches!(primary, Value::Empty) {
+            return Err(TableError::EmptyPrimary);
+        }
+        if self.row.iter().any(|r| &r.value[primary_index] == primary) {
+            return Err(TableError::DuplicatePrimary);
+        }
+        self.add_row(values)
+    }
+
+    // Semua baris sebagai struct; Empty dibaca sebagai null (cocok untuk Option)
+    pub fn rows_as<T: DeserializeOwned>(&self) -> Result<Vec<T>, TableError> {
+        self.row
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                serde_json::from_value(Json::Object(self.row_to_json(row)))
+                    .map_err(|e| TableError::Parse(tr!("val.row", i + 1, e)))
+            })
+            .collect()
+    }
+}
+
+// Error internal pelacak skema (serde butuh tipe yang implement de::Error)
+#[derive(Debug)]
+struct TraceError(String);
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+impl de::Error for TraceError {
+    fn custom<M: fmt::Display>(msg: M) -> Self {
+        TraceError(msg.to_string())
+    }
+}
+
+// Deserializer palsu: mencatat field & tipe yang diminta oleh Deserialize milik struct
+struct SchemaTracer<'a> {
+    columns: &'a mut Vec<(String, ColumnType)>,
+}
+
+impl<'de> Deserializer<'de> for SchemaTracer<'_> {
+    type Error = TraceError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, TraceError> {
+        Err(TraceError(tr!("val.struct_required")))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        if fields.is_empty() {
+            return Err(TraceError(tr!("val.struct_empty")));
+        }
+        visitor.visit_map(FieldAccess {
+            fields,
+            index: 0,
+            columns: self.columns,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map enum
+        identifier ignored_any
+    }
+}
+
+struct FieldAccess<'a> {
+    fields: &'static [&'static str],
+    index: usize,
+    columns: &'a mut Vec<(String, ColumnType)>,
+}
+
+impl<'de> MapAccess<'de> for FieldAccess<'_> {
+    type Error = TraceError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, TraceError> {
+        match self.fields.get(self.index) {
+            Some(field) => seed.deserialize(field.into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, TraceError> {
+        let field = self.fields[self.index];
+        self.index += 1;
+
+        let mut coltype = None;
+        let value = seed
+            .deserialize(FieldTracer {
+                coltype: &mut coltype,
+            })
+            .map_err(|e| TraceError(tr!("val.field", field, e)))?;
+        let coltype = coltype.ok_or_else(|| TraceError(tr!("val.field_unknown", field)))?;
+        self.columns.push((field.to_string(), coltype));
+        Ok(value)
+    }
+}
+
+// Catat tipe satu field lalu kembalikan nilai dummy agar Deserialize bisa lanjut
+struct FieldTracer<'a> {
+    coltype: &'a mut Option<ColumnType>,
+}
+
+impl<'de> Deserializer<'de> for FieldTracer<'_> {
+    type Error = TraceError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, TraceError> {
+        Err(TraceError(tr!("val.field_unsupported")))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        *self.coltype = Some(ColumnType::Bool);
+        visitor.visit_bool(false)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        *self.coltype = Some(ColumnType::Numb);
+        visitor.visit_i64(0)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        *self.coltype = Some(ColumnType::Numb);
+        visitor.visit_u64(0)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        *self.coltype = Some(ColumnType::Char);
+        visitor.visit_char(' ')
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        *self.coltype = Some(ColumnType::Text);
+        visitor.visit_str("")
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        self.deserialize_str(visitor)
+    }
+
+    // Option<T> → tipe dari T; None disimpan sebagai Empty
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        f32 f64 bytes byte_buf unit unit_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Barang {
+        kode: u32,
+        nama: String,
+        aktif: bool,
+        grade: char,
+        catatan: Option<String>,
+    }
+
+    fn barang(kode: u32, catatan: Option<&str>) -> Barang {
+        Barang {
+            kode,
+            nama: format!("barang {}", kode),
+            aktif: kode.is_multiple_of(2),
+            grade: 'A',
+            catatan: catatan.map(String::from),
+        }
+    }
+
+    #[test]
+    fn skema_diambil_dari_struct() {
+        let table = DataTable::create_from_struct::<Barang>("kode").unwrap();
+        let columns: Vec<(&str, &ColumnType)> = table
+            .column
+            .iter()
+            .map(|c| (c.colname.as_str(), &c.coltype))
+            .collect();
+        assert_eq!(
+            columns,
+            [
+                ("kode", &ColumnType::Numb),
+                ("nama", &ColumnType::Text),
+                ("aktif", &ColumnType::Bool),
+                ("grade", &ColumnType::Char),
+                ("catatan", &ColumnType::Text),
+            ]
+        );
+        assert_eq!(table.primary_index, Some(0));
+
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Pecahan {
+            harga: f64,
+        }
+        assert!(matches!(
+            DataTable::create_from_struct::<Pecahan>("harga"),
+            Err(TableError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            DataTable::create_from_struct::<Barang>("tidak_ada"),
+            Err(TableError::ColumnNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn insert_lalu_rows_as_kembali_utuh() {
+        let mut table = DataTable::create_from_struct::<Barang>("kode").unwrap();
+        let records = [barang(1, Some("rapuh")), barang(2, None)];
+        for record in &records {
+            table.insert(record).unwrap();
+        }
+        assert_eq!(table.row[1].value[4], Value::Empty);
+        assert_eq!(table.rows_as::<Barang>().unwrap(), records);
+
+        assert!(matches!(
+            table.insert(&barang(1, None)),
+            Err(TableError::DuplicatePrimary)
+        ));
+        assert!(matches!(table.insert(&5), Err(TableError::InvalidValue(_))));
+        assert_eq!(table.row.len(), 2);
+    }
+}
@@ -0,0 +1,279 @@
+use crate::error::TableError;
+use crate::table::{
+    expr::Expr,
+    handlers::service::DataTable,
+    models::{RowTable, SavedView, SortKey},
+    value::{ColumnType, Value},
+};
+use crate::tr;
+use std::cmp::Ordering;
+
+impl SortKey {
+    // `kolom` → naik, `-kolom` → turun
+    pub fn parse(input: &str) -> Result<Self, TableError> {
+        let input = input.trim();
+        let (column, descending) = match input.strip_prefix('-') {
+            Some(rest) => (rest, true),
+            None => (input.strip_prefix('+').unwrap_or(input), false),
+        };
+        if column.is_empty() {
+            return Err(TableError::InvalidOption(tr!("view.sort_key", input)));
+        }
+        Ok(Self {
+            column: column.into(),
+            descending,
+        })
+    }
+}
+
+impl DataTable {
+    // Simpan (atau timpa) view; definisinya dicek dulu terhadap kolom saat ini
+    pub fn save_view(&mut self, name: &str, view: SavedView) -> Result<(), TableError> {
+        self.check_view(&view).map_err(|e| invalid(name, e))?;
+        self.views.insert(name.into(), view);
+        Ok(())
+    }
+
+    pub fn remove_view(&mut self, name: &str) -> Result<SavedView, TableError> {
+        self.views
+            .remove(name)
+            .ok_or_else(|| TableError::ViewNotFound(name.into()))
+    }
+
+    pub fn view(&self, name: &str) -> Result<&SavedView, TableError> {
+        self.views
+            .get(name)
+            .ok_or_else(|| TableError::ViewNotFound(name.into()))
+    }
+
+    // Cek view terhadap kolom saat ini (kolom bisa sudah dihapus sejak view disimpan)
+    pub fn check_view(&self, view: &SavedView) -> Result<(), TableError> {
+        for colname in view
+            .columns
+            .iter()
+            .chain(view.sort.iter().map(|k| &k.column))
+        {
+            self.column_position(colname)?;
+        }
+        if let Some(filter) = &view.filter {
+            let result = Expr::parse(filter)?.result_type(&self.column)?;
+            if result != ColumnType::Bool {
+                return Err(TableError::InvalidValue(tr!(
+                    "view.filter_type",
+                    format!("{:?}", result)
+                )));
+            }
+        }
+        if view.limit == Some(0) {
+            return Err(TableError::InvalidValue(tr!("opt.positive", "limit", 0)));
+        }
+        Ok(())
+    }
+
+    // View yang tidak bisa dipakai lagi beserta alasannya
+    pub fn invalid_views(&self) -> Vec<(&str, TableError)> {
+        self.views
+            .iter()
+            .filter_map(|(name, view)| self.check_view(view).err().map(|e| (name.as_str(), e)))
+            .collect()
+    }
+
+    // Hasil view sebagai tabel baru: filter → urut → limit → pilih kolom.
+    // Tabel hasil bisa dipakai perintah baca lain (print, export, dump)
+    pub fn view_table(&self, name: &str) -> Result<DataTable, TableError> {
+        let view = self.view(name)?;
+        self.check_view(view).map_err(|e| invalid(name, e))?;
+
+        let filter = view.filter.as_deref().map(Expr::parse).transpose()?;
+        let mut rows: Vec<&RowTable> = self
+            .row
+            .iter()
+            .filter(|row| {
+                filter
+                    .as_ref()
+                    .is_none_or(|f| f.eval(&self.column, &row.value) == Value::Bool(true))
+            })
+            .collect();
+
+        let keys = view
+            .sort
+            .iter()
+            .map(|k| Ok((self.column_position(&k.column)?, k.descending)))
+            .collect::<Result<Vec<_>, TableError>>()?;
+        // sort_by stabil: urutan asli dipertahankan untuk nilai yang sama
+        rows.sort_by(|a, b| {
+            keys.iter()
+                .map(|&(i, descending)| compare_values(&a.value[i], &b.value[i], descending))
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        if let Some(limit) = view.limit {
+            rows.truncate(limit);
+        }
+
+        let indices = if view.columns.is_empty() {
+            (0..self.column.len()).collect()
+        } else {
+            view.columns
+                .iter()
+                .map(|c| self.column_position(c))
+                .collect::<Result<Vec<_>, TableError>>()?
+        };
+
+        let mut table = DataTable::new();
+        table.column = indices.iter().map(|&i| self.column[i].clone()).collect();
+        table.primary_index = indices.iter().position(|&i| Some(i) == self.primary_index);
+        table.row = rows
+            .into_iter()
+            .map(|row| RowTable {
+                value: indices.iter().map(|&i| row.value[i].clone()).collect(),
+            })
+            .collect();
+        Ok(table)
+    }
+}
+
+fn invalid(view: &str, err: TableError) -> TableError {
+    TableError::ViewInvalid {
+        view: view.into(),
+        reason: err.to_string(),
+    }
+}
+
+// Urutan nilai satu kolom; Empty selalu di akhir, naik maupun turun
+fn compare_values(a: &Value, b: &Value, descending: bool) -> Ordering {
+    let ordering = match (a, b) {
+        (Value::Empty, Value::Empty) => return Ordering::Equal,
+        (Value::Empty, _) => return Ordering::Greater,
+        (_, Value::Empty) => return Ordering::Less,
+        (Value::Numb(a), Value::Numb(b)) => a.cmp(b),
+        (Value::Text(a), Value::Text(b)) => a.cmp(b),
+        (Value::Char(a), Value::Char(b)) => a.cmp(b),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        _ => Ordering::Equal,
+    };
+    if descending {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> DataTable {
+        let mut table = DataTable::default();
+        table
+            .add_column(vec![
+                ("kode", ColumnType::Numb),
+                ("nama", ColumnType::Text),
+                ("stok", ColumnType::Numb),
+            ])
+            .unwrap();
+        table.set_primary("kode").unwrap();
+        for (kode, nama, stok) in [
+            (1, "apel", Value::Numb(5)),
+            (2, "jeruk", Value::Empty),
+            (3, "mangga", Value::Numb(9)),
+            (4, "salak", Value::Numb(0)),
+            (5, "pisang", Value::Numb(9)),
+        ] {
+            table
+                .add_row(vec![Value::Numb(kode), Value::Text(nama.into()), stok])
+                .unwrap();
+        }
+        table
+    }
+
+    fn names(table: &DataTable) -> Vec<String> {
+        table.row.iter().map(|r| r.value[0].to_string()).collect()
+    }
+
+    #[test]
+    fn sort_key_dari_teks() {
+        let key = SortKey::parse(" -stok").unwrap();
+        assert_eq!((key.column.as_str(), key.descending), ("stok", true));
+        assert!(!SortKey::parse("+nama").unwrap().descending);
+        assert!(SortKey::parse("-").is_err());
+    }
+
+    #[test]
+    fn view_menyaring_mengurutkan_dan_membatasi() {
+        let mut table = table();
+        let view = SavedView {
+            columns: vec!["nama".into(), "kode".into()],
+            filter: Some("stok > 0".into()),
+            sort: vec![SortKey::parse("-stok").unwrap()],
+            limit: Some(2),
+        };
+        table.save_view("laris", view).unwrap();
+        let result = table.view_table("laris").unwrap();
+        assert_eq!(names(&result), ["mangga", "pisang"]);
+        assert_eq!(result.column.len(), 2);
+        assert_eq!(result.primary_index, Some(1));
+
+        // Empty tetap di akhir walau urutan naik maupun turun
+        for sort in ["stok", "-stok"] {
+            let view = SavedView {
+                columns: vec!["nama".into()],
+                sort: vec![SortKey::parse(sort).unwrap()],
+                ..SavedView::default()
+            };
+            table.save_view("semua", view).unwrap();
+            let result = table.view_table("semua").unwrap();
+            assert_eq!(names(&result).last().map(String::as_str), Some("jeruk"));
+        }
+        assert!(matches!(
+            table.view_table("tidak_ada"),
+            Err(TableError::ViewNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn definisi_salah_ditolak_saat_disimpan() {
+        let mut table = table();
+        for view in [
+            SavedView {
+                columns: vec!["harga".into()],
+                ..SavedView::default()
+            },
+            SavedView {
+                filter: Some("stok + 1".into()),
+                ..SavedView::default()
+            },
+            SavedView {
+                limit: Some(0),
+                ..SavedView::default()
+            },
+        ] {
+            assert!(matches!(
+                table.save_view("v", view),
+                Err(TableError::ViewInvalid { .. })
+            ));
+        }
+        assert!(table.views.is_empty());
+    }
+
+    #[test]
+    fn kolom_dihapus_membuat_view_tidak_valid() {
+        let mut table = table();
+        let view = SavedView {
+            filter: Some("stok > 0".into()),
+            ..SavedView::default()
+        };
+        table.save_view("ada_stok", view).unwrap();
+        table.remove_column("stok").unwrap();
+
+        let invalid = table.invalid_views();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].0, "ada_stok");
+        assert!(matches!(
+            table.view_table("ada_stok"),
+            Err(TableError::ViewInvalid { view, .. }) if view == "ada_stok"
+        ));
+        assert!(table.remove_view("ada_stok").is_ok());
+        assert!(table.invalid_views().is_empty());
+    }
+}
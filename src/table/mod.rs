@@ -1,3 +1,4 @@
+pub mod expr;
 pub mod handlers;
 pub mod models;
 pub mod value;
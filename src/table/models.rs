@@ -7,9 +7,86 @@ pub struct ColumnTable {
     pub coltype: ColumnType,
     pub is_primary: bool,
     pub _is_auto_increment: bool,
+    // Ekspresi kolom hitung; None → kolom biasa
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expr: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RowTable {
     pub value: Vec<Value>,
 }
+
+// Query bernama yang disimpan bersama tabel; kolom dirujuk lewat nama
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedView {
+    // Kolom yang ditampilkan (urut); kosong → semua kolom
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<String>,
+    // Ekspresi Bool (sintaks sama dengan kolom hitung)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sort: Vec<SortKey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SortKey {
+    pub column: String,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+// Kapan trigger/hook dijalankan relatif terhadap perubahan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HookTiming {
+    Before, // boleh mengubah nilai baru atau membatalkan perubahan
+    After,
+}
+
+// Operasi tabel yang memicu trigger/hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    AddRow,
+    SetValueWhere,
+    RemoveRow,
+}
+
+// Trigger deklaratif yang disimpan bersama tabel (diatur lewat CLI `trigger`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trigger {
+    pub name: String,
+    pub timing: HookTiming,
+    pub event: HookEvent,
+    #[serde(flatten)]
+    pub action: TriggerAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TriggerAction {
+    // Isi kolom dengan waktu sekarang (Text → tanggal-jam lokal, Numb → detik Unix)
+    Touch {
+        column: String,
+    },
+    // Isi kolom dengan hasil ekspresi atas baris baru
+    Set {
+        column: String,
+        expr: String,
+    },
+    // Batalkan perubahan jika kondisi bernilai true
+    Reject {
+        condition: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+    // Tambahkan satu baris JSON per baris yang berubah ke file
+    Log {
+        path: String,
+    },
+}
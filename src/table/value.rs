@@ -1,6 +1,8 @@
+use crate::error::TableError;
+use crate::tr;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ColumnType {
     Text,
     Numb,
@@ -8,6 +10,20 @@ pub enum ColumnType {
     Char,
 }
 
+impl ColumnType {
+    // Parse nama tipe (tidak peka huruf besar/kecil)
+    pub fn parse(input: &str) -> Result<Self, TableError> {
+        match input.to_lowercase().as_str() {
+            "text" => Ok(ColumnType::Text),
+            "numb" => Ok(ColumnType::Numb),
+            "bool" => Ok(ColumnType::Bool),
+            "char" => Ok(ColumnType::Char),
+            _ => Err(TableError::InvalidOption(tr!("val.column_type", input))),
+        }
+    }
+}
+
+use crate::config;
 use std::hash::Hash;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -18,3 +34,16 @@ pub enum Value {
     Bool(bool),
     Empty,
 }
+
+// Representasi teks nilai (dipakai saat render tabel)
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Text(v) => write!(f, "{}", v),
+            Value::Char(c) => write!(f, "{}", c),
+            Value::Numb(n) => write!(f, "{}", n),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Empty => write!(f, "{}", config::current().empty_display),
+        }
+    }
+}